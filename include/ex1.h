@@ -0,0 +1,165 @@
+/* Gerado pelo cbindgen a partir de src/ffi.rs. Não edite à mão. */
+
+#ifndef EX1_H
+#define EX1_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Representação por lista de adjacência ([`GraphAdj`]), para [`ex1_graph_new`].
+#define EX1_ADJ 0
+
+// Representação por matriz de adjacência ([`GraphMat`]), para [`ex1_graph_new`].
+#define EX1_MAT 1
+
+// Resultado das funções que podem falhar.
+typedef enum Ex1Status {
+  EX1_STATUS_OK = 0,
+  // Um dos ponteiros recebidos é nulo.
+  EX1_STATUS_NULL_POINTER,
+  // A representação não é [`EX1_ADJ`] nem [`EX1_MAT`].
+  EX1_STATUS_INVALID_REPRESENTATION,
+  // Um dos nós não existe no grafo.
+  EX1_STATUS_INVALID_NODE,
+  // A aresta ou o caminho procurado não existe.
+  EX1_STATUS_NOT_FOUND,
+  // O arquivo de entrada não está no formato do exercício.
+  EX1_STATUS_PARSE_ERROR,
+  // Falha ao abrir ou ler o arquivo de entrada.
+  EX1_STATUS_IO_ERROR,
+  // Um erro inesperado dentro da biblioteca.
+  EX1_STATUS_PANIC,
+} Ex1Status;
+
+// Grafo opaco, acessível apenas pelas funções deste módulo.
+typedef struct Ex1Graph Ex1Graph;
+
+// Nós são identificados pelo tipo `u32`, que é um inteiro de 32 bits positivo.
+//
+// Equivale a um typedef em C++.
+typedef uint32_t Ex1Node;
+
+// Definimos pesos das arestas como sendo inteiros de 32bits positivos.
+//
+// Esse é o tipo de peso padrão dos grafos, mas qualquer tipo que implemente [`Num`] pode ser
+// usado, como em `GraphAdj<f64>` ou `GraphMat<i64>`.
+typedef uint32_t Ex1Weight;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Cria um grafo vazio, com a representação [`EX1_ADJ`] ou [`EX1_MAT`]. Retorna um ponteiro
+// nulo caso a representação seja inválida.
+struct Ex1Graph *ex1_graph_new(bool directed, uint32_t representation);
+
+// Lê um grafo de `path`, no formato de entrada do exercício, escrevendo-o em `out`.
+//
+// # Safety
+//
+// `path` precisa ser uma string terminada em `\0`, e `out` um ponteiro válido para escrita.
+enum Ex1Status ex1_graph_read(const char *path,
+                              bool directed,
+                              uint32_t representation,
+                              struct Ex1Graph **out);
+
+// Libera um grafo criado por [`ex1_graph_new`] ou [`ex1_graph_read`]. Não faz nada com um
+// ponteiro nulo.
+//
+// # Safety
+//
+// `graph` precisa ter sido criado por este módulo, e não pode ser usado depois.
+void ex1_graph_free(struct Ex1Graph *graph);
+
+// Cria um nó, escrevendo o seu identificador em `out`.
+//
+// # Safety
+//
+// `graph` e `out` precisam ser ponteiros válidos.
+enum Ex1Status ex1_graph_add_node(struct Ex1Graph *graph, Ex1Node *out);
+
+// Adiciona a aresta `a - b`, substituindo o peso caso ela já exista.
+//
+// # Safety
+//
+// `graph` precisa ser um ponteiro válido.
+enum Ex1Status ex1_graph_add_edge(struct Ex1Graph *graph, Ex1Node a, Ex1Node b, Ex1Weight weight);
+
+// Remove a aresta `a - b`, ou retorna [`Ex1Status::NotFound`] caso ela não exista.
+//
+// # Safety
+//
+// `graph` precisa ser um ponteiro válido.
+enum Ex1Status ex1_graph_remove_edge(struct Ex1Graph *graph, Ex1Node a, Ex1Node b);
+
+// Retorna a quantidade de nós, ou zero com um ponteiro nulo.
+//
+// # Safety
+//
+// `graph` precisa ser nulo ou um ponteiro válido.
+size_t ex1_graph_node_count(const struct Ex1Graph *graph);
+
+// Retorna a quantidade de arestas, ou zero com um ponteiro nulo.
+//
+// # Safety
+//
+// `graph` precisa ser nulo ou um ponteiro válido.
+size_t ex1_graph_edge_count(const struct Ex1Graph *graph);
+
+// Indica se o nó `n` existe, retornando `false` com um ponteiro nulo.
+//
+// # Safety
+//
+// `graph` precisa ser nulo ou um ponteiro válido.
+bool ex1_graph_has_node(const struct Ex1Graph *graph, Ex1Node n);
+
+// Escreve em `out` o peso da aresta `a - b`, ou retorna [`Ex1Status::NotFound`] caso ela não
+// exista.
+//
+// # Safety
+//
+// `graph` e `out` precisam ser ponteiros válidos.
+enum Ex1Status ex1_graph_edge_weight(const struct Ex1Graph *graph,
+                                     Ex1Node a,
+                                     Ex1Node b,
+                                     Ex1Weight *out);
+
+// Escreve os vizinhos de `n` em `buffer`, que tem espaço para `capacity` nós, e a quantidade
+// total de vizinhos em `len`.
+//
+// Caso `len` seja maior que `capacity`, só os primeiros vizinhos foram escritos. Chamar a função
+// com `capacity` zero (e `buffer` nulo) permite descobrir o tamanho necessário.
+//
+// # Safety
+//
+// `graph` e `len` precisam ser ponteiros válidos, e `buffer` precisa ter espaço para `capacity`
+// nós, ou ser nulo caso `capacity` seja zero.
+enum Ex1Status ex1_graph_neighbors(const struct Ex1Graph *graph,
+                                   Ex1Node n,
+                                   Ex1Node *buffer,
+                                   size_t capacity,
+                                   size_t *len);
+
+// Escreve em `out` o peso de um caminho mínimo de `source` até `target`, ou retorna
+// [`Ex1Status::NotFound`] caso `target` não seja alcançável.
+//
+// # Safety
+//
+// `graph` e `out` precisam ser ponteiros válidos.
+enum Ex1Status ex1_shortest_path_length(const struct Ex1Graph *graph,
+                                        Ex1Node source,
+                                        Ex1Node target,
+                                        Ex1Weight *out);
+
+// Retorna uma descrição de `status`, em uma string estática terminada em `\0`, que não deve ser
+// liberada.
+const char *ex1_status_message(enum Ex1Status status);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* EX1_H */
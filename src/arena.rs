@@ -0,0 +1,449 @@
+use core::ops::Range;
+
+use crate::prelude::*;
+use crate::{
+    compact_mapping, sort_last_wins, validate_edges, vec_bytes, Edge, GraphError, GraphMut,
+    GraphRef, Map, Node, Num, SelfLoops, Weight,
+};
+
+/// Capacidade do primeiro bloco de um nó, quando ele recebe a sua primeira aresta.
+const FIRST_BLOCK: usize = 4;
+
+/// Trecho de `targets` e `weights` reservado para as arestas que saem de um nó.
+#[derive(Debug, Clone, Copy)]
+struct Block {
+    start: usize,
+    /// Quantidade de arestas no bloco, que ocupam as primeiras posições dele.
+    len: usize,
+    /// Quantidade de posições reservadas para o nó a partir de `start`.
+    cap: usize,
+}
+
+/// Struct que representa um grafo por listas de adjacência guardadas em um único vetor (a
+/// _arena_).
+///
+/// Cada nó tem um bloco de posições consecutivas da arena, com as suas arestas ordenadas pelo
+/// destino, como em [`crate::GraphCsr`]. Diferente do CSR, cada bloco tem espaço sobrando: quando
+/// um bloco enche, ele é copiado para o fim da arena com o dobro do tamanho, e o espaço antigo
+/// fica sem uso. Assim, adicionar uma aresta custa O(d), e não O(V + E) como no CSR, e percorrer
+/// os vizinhos de um nó continua sendo percorrer um trecho contínuo de memória.
+///
+/// Comparado a [`crate::GraphAdj`], não há uma alocação para cada nó, nem uma `BTreeMap` para
+/// encontrar a lista de um nó: o bloco do nó `n` é a posição `n` de um vetor. Isso é bom para
+/// grafos que são montados uma vez e depois percorridos muitas vezes. Quando mais da metade da
+/// arena fica sem uso, ela é compactada, com os blocos em ordem de nó.
+#[derive(Default, Debug, Clone)]
+pub struct GraphArena<W = Weight> {
+    /// Bloco de cada nó, ou `None` para nós removidos.
+    blocks: Vec<Option<Block>>,
+    /// Destino e peso de cada aresta, na mesma posição.
+    targets: Vec<Node>,
+    weights: Vec<W>,
+    /// Quantidade de posições da arena que não pertencem a nenhum bloco.
+    garbage: usize,
+    node_count: usize,
+    /// Quantidade de posições ocupadas e de laços, assim como em [`crate::GraphAdj`].
+    entry_count: usize,
+    loop_count: usize,
+    directed: bool,
+    self_loops: SelfLoops,
+}
+
+impl<W: Num> GraphArena<W> {
+    /// Cria um grafo direcionado vazio.
+    ///
+    /// `GraphArena::default()` continua criando um grafo não direcionado.
+    pub fn directed() -> Self {
+        Self {
+            directed: true,
+            ..Default::default()
+        }
+    }
+    /// Define a política do grafo para laços. Só afeta as arestas adicionadas depois.
+    pub fn with_self_loops(mut self, policy: SelfLoops) -> Self {
+        self.self_loops = policy;
+        self
+    }
+    /// Retorna os destinos das arestas que saem de `n`, em ordem crescente.
+    pub fn neighbor_slice(&self, n: Node) -> &[Node] {
+        &self.targets[self.range(n)]
+    }
+    /// Retorna os pesos das arestas que saem de `n`, na mesma ordem de
+    /// [`GraphArena::neighbor_slice`].
+    pub fn weight_slice(&self, n: Node) -> &[W] {
+        &self.weights[self.range(n)]
+    }
+    /// Retorna quantos bytes o grafo ocupa na heap, sem contar o próprio `GraphArena`.
+    ///
+    /// Inclui o espaço sobrando nos blocos e o espaço sem uso da arena, que podem ser liberados
+    /// com [`GraphArena::shrink_to_fit`].
+    pub fn memory_usage(&self) -> usize {
+        vec_bytes(&self.blocks) + vec_bytes(&self.targets) + vec_bytes(&self.weights)
+    }
+    /// Compacta a arena, deixando cada bloco com o tamanho exato das suas arestas, e libera a
+    /// memória que sobrar.
+    pub fn shrink_to_fit(&mut self) {
+        self.repack(0);
+        self.blocks.shrink_to_fit();
+        self.targets.shrink_to_fit();
+        self.weights.shrink_to_fit();
+    }
+
+    /// Intervalo da arena que contém as arestas de `n`.
+    fn range(&self, n: Node) -> Range<usize> {
+        match self.blocks.get(n as usize) {
+            Some(Some(block)) => block.start..block.start + block.len,
+            _ => 0..0,
+        }
+    }
+    fn check_node(&self, n: Node, action: &str) {
+        if !self.has_node(n) {
+            panic!("Tried to {action} inexistent node {n}");
+        }
+    }
+    /// Copia os blocos para uma nova arena, em ordem de nó, cada um com `slack` posições
+    /// sobrando além das suas arestas.
+    fn repack(&mut self, slack: usize) {
+        let size = self.entry_count + slack * self.node_count;
+        let mut targets = Vec::with_capacity(size);
+        let mut weights = Vec::with_capacity(size);
+        for block in self.blocks.iter_mut().flatten() {
+            let old = block.start..block.start + block.len;
+            block.start = targets.len();
+            block.cap = block.len + slack;
+            targets.extend_from_slice(&self.targets[old.clone()]);
+            weights.extend_from_slice(&self.weights[old]);
+            // As posições sobrando recebem valores quaisquer, que nunca são lidos.
+            targets.resize(block.start + block.cap, 0);
+            weights.resize(block.start + block.cap, W::ONE);
+        }
+        self.targets = targets;
+        self.weights = weights;
+        self.garbage = 0;
+    }
+    /// Garante que o bloco de `n` tenha espaço para mais uma aresta, retornando-o.
+    fn grow(&mut self, n: Node) -> Block {
+        let block = self.blocks[n as usize].unwrap();
+        if block.len < block.cap {
+            return block;
+        }
+        let cap = (2 * block.cap).max(FIRST_BLOCK);
+        let end = self.targets.len();
+        let start = if block.start + block.cap == end {
+            // O bloco já está no fim da arena, então basta aumentá-la.
+            block.start
+        } else {
+            // O bloco é copiado para o fim da arena, e o espaço antigo fica sem uso.
+            self.targets
+                .extend_from_within(block.start..block.start + block.len);
+            self.weights
+                .extend_from_within(block.start..block.start + block.len);
+            self.garbage += block.cap;
+            end
+        };
+        self.targets.resize(start + cap, 0);
+        self.weights.resize(start + cap, W::ONE);
+        let block = Block {
+            start,
+            cap,
+            ..block
+        };
+        self.blocks[n as usize] = Some(block);
+        block
+    }
+    /// Insere ou atualiza a entrada `a -> b`.
+    fn insert_entry(&mut self, a: Node, b: Node, weight: W) {
+        let range = self.range(a);
+        let pos = match self.targets[range.clone()].binary_search(&b) {
+            Ok(pos) => {
+                self.weights[range.start + pos] = weight;
+                return;
+            }
+            Err(pos) => pos,
+        };
+        let block = self.grow(a);
+        // Abre espaço na posição da nova aresta, deslocando as seguintes dentro do bloco.
+        let (at, end) = (block.start + pos, block.start + block.len);
+        self.targets.copy_within(at..end, at + 1);
+        self.weights.copy_within(at..end, at + 1);
+        self.targets[at] = b;
+        self.weights[at] = weight;
+        self.blocks[a as usize] = Some(Block {
+            len: block.len + 1,
+            ..block
+        });
+        self.entry_count += 1;
+        self.loop_count += usize::from(a == b);
+        self.collect_garbage();
+    }
+    /// Compacta a arena caso mais da metade dela esteja sem uso.
+    ///
+    /// A compactação só acontece depois de vários blocos serem movidos ou removidos, então o seu
+    /// custo é dividido entre as operações que os moveram.
+    fn collect_garbage(&mut self) {
+        if self.garbage > self.targets.len() / 2 {
+            self.repack(0);
+        }
+    }
+    /// Remove a entrada `a -> b`, retornando o seu peso.
+    fn remove_entry(&mut self, a: Node, b: Node) -> Option<W> {
+        let range = self.range(a);
+        let pos = self.targets[range.clone()].binary_search(&b).ok()?;
+        let at = range.start + pos;
+        let weight = self.weights[at];
+        self.targets.copy_within(at + 1..range.end, at);
+        self.weights.copy_within(at + 1..range.end, at);
+        let block = self.blocks[a as usize].as_mut().unwrap();
+        block.len -= 1;
+        self.entry_count -= 1;
+        self.loop_count -= usize::from(a == b);
+        Some(weight)
+    }
+    /// Substitui todas as arestas do grafo por `entries`, que pode estar fora de ordem. Os nós
+    /// continuam os mesmos.
+    fn set_entries(&mut self, mut entries: Vec<Edge<W>>) {
+        sort_last_wins(&mut entries);
+        for block in self.blocks.iter_mut().flatten() {
+            block.len = 0;
+        }
+        // Como as arestas estão ordenadas, cada bloco é preenchido em ordem, e a arena
+        // compactada a seguir fica com os blocos do tamanho exato.
+        self.targets = entries.iter().map(|e| e.1).collect();
+        self.weights = entries.iter().map(|e| e.2).collect();
+        let mut start = 0;
+        for group in entries.chunk_by(|x, y| x.0 == y.0) {
+            let block = self.blocks[group[0].0 as usize].as_mut().unwrap();
+            *block = Block {
+                start,
+                len: group.len(),
+                cap: group.len(),
+            };
+            start += group.len();
+        }
+        self.entry_count = entries.len();
+        self.loop_count = entries.iter().filter(|e| e.0 == e.1).count();
+        // Os blocos vazios apontam para o fim da arena, com capacidade zero.
+        for block in self.blocks.iter_mut().flatten() {
+            if block.len == 0 {
+                *block = Block {
+                    start,
+                    len: 0,
+                    cap: 0,
+                };
+            }
+        }
+        self.garbage = 0;
+    }
+}
+
+impl<W: Num> GraphRef<W> for GraphArena<W> {
+    fn validate(&self) -> Result<(), GraphError> {
+        let invalid = |message: String| Err(GraphError::InvalidStorage(message));
+        if self.targets.len() != self.weights.len() {
+            return invalid("targets and weights have different lengths".to_string());
+        }
+        // Os blocos não podem se sobrepor, e junto com o espaço sem uso precisam cobrir a arena.
+        let mut blocks: Vec<_> = self.blocks.iter().flatten().filter(|b| b.cap > 0).collect();
+        blocks.sort_by_key(|b| b.start);
+        if blocks
+            .windows(2)
+            .any(|w| w[0].start + w[0].cap > w[1].start)
+            || blocks
+                .iter()
+                .any(|b| b.len > b.cap || b.start + b.cap > self.targets.len())
+        {
+            return invalid("blocks overlap or are outside of the arena".to_string());
+        }
+        let used: usize = blocks.iter().map(|b| b.cap).sum();
+        if used + self.garbage != self.targets.len() {
+            return invalid(format!(
+                "blocks use {used} positions and {} are garbage, but the arena has {}",
+                self.garbage,
+                self.targets.len()
+            ));
+        }
+        for n in 0..self.blocks.len() as Node {
+            if self.neighbor_slice(n).windows(2).any(|w| w[0] >= w[1]) {
+                return invalid(format!("neighbors of node {n} are not sorted"));
+            }
+        }
+        let entries = self.blocks.iter().flatten().map(|b| b.len).sum();
+        let loops = (0..self.blocks.len() as Node)
+            .filter(|&n| self.neighbor_slice(n).binary_search(&n).is_ok())
+            .count();
+        if (self.entry_count, self.loop_count) != (entries, loops) {
+            return invalid(format!(
+                "edge count says {} entries and {} self-loops, but {entries} and {loops} are stored",
+                self.entry_count, self.loop_count
+            ));
+        }
+        if self.blocks.iter().flatten().count() != self.node_count {
+            return invalid(format!("node count is not {}", self.node_count));
+        }
+        validate_edges(self.edges_iter(), self)
+    }
+    fn edges(&self) -> Vec<Edge<W>> {
+        // Os nós são percorridos em ordem, e cada bloco está ordenado.
+        self.edges_iter().collect()
+    }
+    fn edges_iter(&self) -> Box<dyn Iterator<Item = Edge<W>> + '_> {
+        Box::new((0..self.blocks.len() as Node).flat_map(move |a| {
+            self.neighbor_slice(a)
+                .iter()
+                .zip(self.weight_slice(a))
+                .map(move |(&b, &weight)| (a, b, weight))
+        }))
+    }
+    fn node_count(&self) -> usize {
+        self.node_count
+    }
+    fn edge_count(&self) -> usize {
+        if self.directed {
+            self.entry_count
+        } else {
+            (self.entry_count + self.loop_count) / 2
+        }
+    }
+    fn has_node(&self, n: Node) -> bool {
+        matches!(self.blocks.get(n as usize), Some(Some(_)))
+    }
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+    fn self_loops(&self) -> SelfLoops {
+        self.self_loops
+    }
+    fn out_edges(&self, a: Node) -> Vec<Edge<W>> {
+        self.neighbor_slice(a)
+            .iter()
+            .zip(self.weight_slice(a))
+            .map(|(&b, &weight)| (a, b, weight))
+            .collect()
+    }
+    fn neighbors(&self, n: Node) -> Box<dyn Iterator<Item = Node> + '_> {
+        Box::new(self.neighbor_slice(n).iter().copied())
+    }
+    fn out_degree(&self, n: Node) -> usize {
+        self.range(n).len()
+    }
+    fn for_each_neighbor(&self, n: Node, f: &mut dyn FnMut(Node)) {
+        for &b in self.neighbor_slice(n) {
+            f(b);
+        }
+    }
+    fn get_edge_weight(&self, a: Node, b: Node) -> Option<W> {
+        let pos = self.neighbor_slice(a).binary_search(&b).ok()?;
+        Some(self.weight_slice(a)[pos])
+    }
+}
+
+impl<W: Num> GraphMut<W> for GraphArena<W> {
+    fn add_node(&mut self) -> Node {
+        self.add_nodes(1).start
+    }
+    fn add_nodes(&mut self, count: usize) -> Range<Node> {
+        let first_node = self.blocks.len() as Node;
+        // Os novos nós ainda não têm espaço na arena, que só é reservado na primeira aresta.
+        let empty = Block {
+            start: self.targets.len(),
+            len: 0,
+            cap: 0,
+        };
+        self.blocks.extend(core::iter::repeat_n(Some(empty), count));
+        self.node_count += count;
+        first_node..self.blocks.len() as Node
+    }
+    fn add_edge(&mut self, a: Node, b: Node, weight: W) {
+        self.self_loops.check(a, b);
+        self.check_node(a, "add edge to");
+        self.check_node(b, "add edge to");
+        self.insert_entry(a, b, weight);
+        if !self.directed && a != b {
+            self.insert_entry(b, a, weight);
+        }
+    }
+    fn add_edges<I>(&mut self, edges: I)
+    where
+        I: IntoIterator<Item = Edge<W>>,
+    {
+        // Assim como em `GraphCsr`, junta as arestas antigas com as novas e monta a arena uma
+        // única vez, já com os blocos do tamanho exato.
+        let mut entries: Vec<Edge<W>> = self.edges_iter().collect();
+        for (a, b, weight) in edges {
+            self.self_loops.check(a, b);
+            self.check_node(a, "add edge to");
+            self.check_node(b, "add edge to");
+            entries.push((a, b, weight));
+            if !self.directed && a != b {
+                entries.push((b, a, weight));
+            }
+        }
+        self.set_entries(entries);
+    }
+    fn remove_node(&mut self, n: Node) {
+        self.check_node(n, "remove");
+        let neighbors = self.neighbor_slice(n).to_vec();
+        if self.directed {
+            // Sem listas de entrada, a aresta `a -> n` é procurada no bloco de cada nó.
+            for a in 0..self.blocks.len() as Node {
+                if a != n && self.has_node(a) {
+                    self.remove_entry(a, n);
+                }
+            }
+        } else {
+            // Em grafos não direcionados, as arestas que chegam em `n` espelham as que saem.
+            for &b in neighbors.iter().filter(|&&b| b != n) {
+                self.remove_entry(b, n);
+            }
+        }
+        let block = self.blocks[n as usize].take().unwrap();
+        self.garbage += block.cap;
+        self.entry_count -= block.len;
+        self.loop_count -= usize::from(neighbors.binary_search(&n).is_ok());
+        self.node_count -= 1;
+        self.collect_garbage();
+    }
+    fn remove_edge(&mut self, a: Node, b: Node) -> Option<W> {
+        let weight = self.remove_entry(a, b)?;
+        if !self.directed && a != b {
+            self.remove_entry(b, a);
+        }
+        Some(weight)
+    }
+    fn clear(&mut self) {
+        self.blocks.clear();
+        self.targets.clear();
+        self.weights.clear();
+        self.garbage = 0;
+        self.node_count = 0;
+        self.entry_count = 0;
+        self.loop_count = 0;
+    }
+    fn compact(&mut self) -> Map<Node, Node> {
+        let mapping = compact_mapping(self);
+        let entries = self
+            .edges_iter()
+            .map(|(a, b, weight)| (mapping[&a], mapping[&b], weight))
+            .collect();
+        self.blocks = vec![
+            Some(Block {
+                start: 0,
+                len: 0,
+                cap: 0
+            });
+            mapping.len()
+        ];
+        self.set_entries(entries);
+        mapping
+    }
+    fn reverse(&mut self) {
+        if !self.directed {
+            return;
+        }
+        let entries = self
+            .edges_iter()
+            .map(|(a, b, weight)| (b, a, weight))
+            .collect();
+        self.set_entries(entries);
+    }
+}
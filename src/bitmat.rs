@@ -0,0 +1,266 @@
+use core::ops::Range;
+
+use crate::prelude::*;
+use crate::{
+    compact_mapping, map_bytes, validate_edges, vec_bytes, Edge, GraphError, GraphMut, GraphRef,
+    Map, Node, Num, SelfLoops, Set, Weight,
+};
+
+/// Quantidade de bits em cada palavra da matriz.
+pub(crate) const WORD_BITS: usize = u64::BITS as usize;
+
+/// Struct que representa um grafo sem pesos por uma matriz de adjacência de bits.
+///
+/// Cada par de nós ocupa um único bit, em vez de um `Option<u32>` inteiro como em
+/// [`crate::GraphMat`]. As linhas da matriz são formadas por palavras de 64 bits, o que permite
+/// operar sobre uma linha inteira de uma vez usando operações bit a bit (veja
+/// [`GraphBitMat::row_words`]).
+///
+/// Como não há pesos, `add_edge` ignora o peso recebido e todas as arestas são retornadas com peso
+/// `1`.
+#[derive(Default, Debug, Clone)]
+pub struct GraphBitMat {
+    node_count: usize,
+    /// Quantidade de palavras em cada linha, ou seja, `node_count` dividido por 64 e arredondado
+    /// para cima.
+    words_per_row: usize,
+    /// Matriz armazenada linha a linha: a aresta `a -> b` é o bit `b % 64` da palavra
+    /// `a * words_per_row + b / 64`.
+    bits: Vec<u64>,
+    directed: bool,
+    /// Nós removidos. Suas linhas e colunas continuam na matriz, mas ficam zeradas.
+    removed: Set<Node>,
+    self_loops: SelfLoops,
+}
+
+impl GraphBitMat {
+    /// Cria um grafo direcionado vazio.
+    ///
+    /// `GraphBitMat::default()` continua criando um grafo não direcionado.
+    pub fn directed() -> Self {
+        Self {
+            directed: true,
+            ..Default::default()
+        }
+    }
+    /// Define a política do grafo para laços. Só afeta as arestas adicionadas depois.
+    pub fn with_self_loops(mut self, policy: SelfLoops) -> Self {
+        self.self_loops = policy;
+        self
+    }
+    /// Retorna as palavras que formam a linha do nó `n`.
+    ///
+    /// O bit `b % 64` da palavra `b / 64` indica se existe a aresta `n -> b`. Bits além de
+    /// `node_count` são sempre zero.
+    pub fn row_words(&self, n: Node) -> &[u64] {
+        let start = n as usize * self.words_per_row;
+        self.bits
+            .get(start..start + self.words_per_row)
+            .unwrap_or_default()
+    }
+    /// Conta quantos vizinhos de saída `a` e `b` têm em comum, fazendo o `AND` de suas linhas
+    /// palavra por palavra.
+    pub fn common_neighbor_count(&self, a: Node, b: Node) -> usize {
+        self.row_words(a)
+            .iter()
+            .zip(self.row_words(b))
+            .map(|(x, y)| (x & y).count_ones() as usize)
+            .sum()
+    }
+    /// Retorna quantos bytes o grafo ocupa na heap, sem contar o próprio `GraphBitMat`.
+    ///
+    /// Cada par de nós ocupa um único bit, ou seja, cerca de 1/64 do espaço de um
+    /// [`crate::GraphMat`] com pesos `u32` (que usa 8 bytes por posição, por causa do `Option`).
+    pub fn memory_usage(&self) -> usize {
+        vec_bytes(&self.bits) + map_bytes::<Node, ()>(self.removed.len())
+    }
+
+    /// Retorna a palavra e a máscara do bit que representa a aresta `a -> b`.
+    fn position(&self, a: Node, b: Node) -> (usize, u64) {
+        let (a, b) = (a as usize, b as usize);
+        (a * self.words_per_row + b / WORD_BITS, 1 << (b % WORD_BITS))
+    }
+    fn get(&self, a: Node, b: Node) -> bool {
+        if a as usize >= self.node_count || b as usize >= self.node_count {
+            return false;
+        }
+        let (word, mask) = self.position(a, b);
+        self.bits[word] & mask != 0
+    }
+    fn set(&mut self, a: Node, b: Node, value: bool) {
+        let (word, mask) = self.position(a, b);
+        if value {
+            self.bits[word] |= mask;
+        } else {
+            self.bits[word] &= !mask;
+        }
+    }
+    fn check_node(&self, n: Node, action: &str) {
+        if !self.has_node(n) {
+            panic!("Tried to {action} inexistent node {n}");
+        }
+    }
+}
+
+impl GraphRef for GraphBitMat {
+    fn validate(&self) -> Result<(), GraphError> {
+        if self.words_per_row != self.node_count.div_ceil(WORD_BITS)
+            || self.bits.len() != self.node_count * self.words_per_row
+        {
+            return Err(GraphError::InvalidStorage(format!(
+                "bit matrix has {} words for {} nodes",
+                self.bits.len(),
+                self.node_count
+            )));
+        }
+        // Bits ligados além de `node_count` aparecem como arestas soltas.
+        validate_edges(self.edges_iter(), self)
+    }
+    fn edges(&self) -> Vec<Edge> {
+        self.edges_iter().collect()
+    }
+    fn edges_iter(&self) -> Box<dyn Iterator<Item = Edge> + '_> {
+        Box::new(
+            (0..self.node_count as Node)
+                .flat_map(move |a| self.neighbors(a).map(move |b| (a, b, Weight::ONE))),
+        )
+    }
+    fn node_count(&self) -> usize {
+        self.node_count - self.removed.len()
+    }
+    fn has_node(&self, n: Node) -> bool {
+        (n as usize) < self.node_count && !self.removed.contains(&n)
+    }
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+    fn self_loops(&self) -> SelfLoops {
+        self.self_loops
+    }
+    fn out_edges(&self, a: Node) -> Vec<Edge> {
+        self.neighbors(a).map(|b| (a, b, Weight::ONE)).collect()
+    }
+    fn in_neighbors(&self, n: Node) -> Box<dyn Iterator<Item = Node> + '_> {
+        if !self.directed {
+            return self.neighbors(n);
+        }
+        Box::new((0..self.node_count as Node).filter(move |&a| self.get(a, n)))
+    }
+    fn neighbors(&self, n: Node) -> Box<dyn Iterator<Item = Node> + '_> {
+        Box::new(self.row_words(n).iter().enumerate().flat_map(|(i, &word)| {
+            // Percorre apenas os bits ligados da palavra: `trailing_zeros` encontra o
+            // menor bit ligado, e `word & (word - 1)` o desliga.
+            core::iter::successors(Some(word), |&w| Some(w & w.wrapping_sub(1)))
+                .take_while(|&w| w != 0)
+                .map(move |w| (i * WORD_BITS) as Node + w.trailing_zeros())
+        }))
+    }
+    fn out_degree(&self, n: Node) -> usize {
+        self.row_words(n)
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+    fn get_edge_weight(&self, a: Node, b: Node) -> Option<Weight> {
+        self.get(a, b).then_some(Weight::ONE)
+    }
+}
+
+impl GraphMut for GraphBitMat {
+    fn add_node(&mut self) -> Node {
+        self.add_nodes(1).start
+    }
+    fn add_nodes(&mut self, count: usize) -> Range<Node> {
+        let first_node = self.node_count as Node;
+        let new_node_count = self.node_count + count;
+        let new_words_per_row = new_node_count.div_ceil(WORD_BITS);
+
+        if new_words_per_row == self.words_per_row {
+            // Ainda cabem as novas colunas nas linhas atuais, então basta adicionar novas linhas.
+            self.bits.resize(new_node_count * self.words_per_row, 0);
+        } else if self.node_count == 0 {
+            // Não há nada para copiar, então a memória do vetor atual é reaproveitada.
+            self.bits.resize(new_node_count * new_words_per_row, 0);
+            self.words_per_row = new_words_per_row;
+        } else {
+            // As linhas precisam de mais palavras. Adicionando um nó por vez, isso só acontece a
+            // cada 64 nós, então copiar a matriz inteira aqui é pouco frequente.
+            let mut new_bits = vec![0; new_node_count * new_words_per_row];
+            if self.words_per_row > 0 {
+                let new_lines = new_bits.chunks_mut(new_words_per_row);
+                let old_lines = self.bits.chunks(self.words_per_row);
+                for (new_line, old_line) in new_lines.zip(old_lines) {
+                    new_line[..self.words_per_row].copy_from_slice(old_line);
+                }
+            }
+            self.bits = new_bits;
+            self.words_per_row = new_words_per_row;
+        }
+        self.node_count = new_node_count;
+
+        first_node..new_node_count as Node
+    }
+    fn add_edge(&mut self, a: Node, b: Node, _weight: Weight) {
+        self.self_loops.check(a, b);
+        self.check_node(a, "add edge to");
+        self.check_node(b, "add edge to");
+        self.set(a, b, true);
+        if !self.directed {
+            self.set(b, a, true);
+        }
+    }
+    fn remove_node(&mut self, n: Node) {
+        self.check_node(n, "remove");
+        self.removed.insert(n);
+        let start = n as usize * self.words_per_row;
+        self.bits[start..start + self.words_per_row].fill(0);
+        for a in 0..self.node_count as Node {
+            self.set(a, n, false);
+        }
+    }
+    fn remove_edge(&mut self, a: Node, b: Node) -> Option<Weight> {
+        if !self.get(a, b) {
+            return None;
+        }
+        self.set(a, b, false);
+        if !self.directed {
+            self.set(b, a, false);
+        }
+        Some(Weight::ONE)
+    }
+    fn clear(&mut self) {
+        // `Vec::clear` mantém a capacidade do vetor, que é reaproveitada por `add_nodes`.
+        self.bits.clear();
+        self.node_count = 0;
+        self.words_per_row = 0;
+        self.removed.clear();
+    }
+    fn compact(&mut self) -> Map<Node, Node> {
+        let mapping = compact_mapping(self);
+        let edges: Vec<_> = self.edges_iter().collect();
+        // Recria a matriz do zero, já com a quantidade final de nós.
+        *self = Self {
+            directed: self.directed,
+            self_loops: self.self_loops,
+            ..Default::default()
+        };
+        self.add_nodes(mapping.len());
+        for (a, b, _) in edges {
+            self.set(mapping[&a], mapping[&b], true);
+        }
+        mapping
+    }
+    fn reverse(&mut self) {
+        if !self.directed {
+            return;
+        }
+        // Transpõe a matriz trocando cada bit acima da diagonal com o seu espelho.
+        for a in 0..self.node_count as Node {
+            for b in a + 1..self.node_count as Node {
+                let (ab, ba) = (self.get(a, b), self.get(b, a));
+                self.set(a, b, ba);
+                self.set(b, a, ab);
+            }
+        }
+    }
+}
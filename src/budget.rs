@@ -0,0 +1,89 @@
+//! Limites para algoritmos demorados, que podem parar antes do fim e retornar um resultado parcial.
+
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+/// Permite cancelar, de outra thread, um algoritmo que recebeu um [`Budget`] com este token.
+///
+/// Os clones compartilham o mesmo estado: basta guardar um clone e chamar
+/// [`CancelToken::cancel`] nele.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Limites de um algoritmo demorado: uma quantidade máxima de passos, um prazo e um
+/// [`CancelToken`]. O que conta como um passo depende do algoritmo, e está na sua documentação.
+///
+/// `Budget::default()` não tem limites. Os limites são verificados entre os passos, então um
+/// algoritmo pode passar um pouco do prazo, no máximo pela duração de um passo.
+#[derive(Debug, Clone, Default)]
+pub struct Budget {
+    max_steps: Option<usize>,
+    #[cfg(feature = "std")]
+    deadline: Option<Instant>,
+    cancel: Option<CancelToken>,
+}
+
+impl Budget {
+    /// Limita o algoritmo a `steps` passos.
+    pub fn with_max_steps(mut self, steps: usize) -> Self {
+        self.max_steps = Some(steps);
+        self
+    }
+    /// Para o algoritmo ao atingir o instante `deadline`.
+    #[cfg(feature = "std")]
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+    /// Para o algoritmo depois de `timeout`, contado a partir de agora.
+    #[cfg(feature = "std")]
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+    /// Para o algoritmo quando `token` for cancelado.
+    pub fn with_cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+    /// Indica se um algoritmo que já fez `steps` passos deve parar. Pode ser usado para aplicar
+    /// os mesmos limites em algoritmos escritos fora do crate.
+    pub fn is_exhausted(&self, steps: usize) -> bool {
+        if self.max_steps.is_some_and(|max| steps >= max) {
+            return true;
+        }
+        if self.cancel.as_ref().is_some_and(CancelToken::is_cancelled) {
+            return true;
+        }
+        #[cfg(feature = "std")]
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return true;
+        }
+        false
+    }
+}
+
+/// Resultado de um algoritmo com um [`Budget`]. Caso os limites tenham sido atingidos,
+/// `complete` é `false`, e `value` é o melhor resultado encontrado até então, com o significado
+/// descrito em cada algoritmo.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Partial<T> {
+    pub value: T,
+    pub complete: bool,
+}
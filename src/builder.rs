@@ -0,0 +1,293 @@
+use core::ops::Range;
+#[cfg(feature = "std")]
+use core::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "std")]
+use std::sync::{Mutex, PoisonError};
+
+use crate::prelude::*;
+use crate::{
+    Edge, GraphAdj, GraphArena, GraphCsr, GraphError, GraphMat, GraphMut, Node, Num, SelfLoops,
+    Weight,
+};
+
+/// Define o que [`GraphBuilder`] faz quando uma mesma aresta é adicionada mais de uma vez.
+///
+/// Em grafos não direcionados, `a - b` e `b - a` são a mesma aresta.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateEdges {
+    /// Mantém o último peso, como chamadas repetidas de `add_edge` fariam.
+    #[default]
+    KeepLast,
+    /// Mantém o primeiro peso e ignora os seguintes.
+    KeepFirst,
+    /// Arestas repetidas são um erro ([`GraphError::DuplicateEdge`]).
+    Reject,
+}
+
+/// Acumula nós e arestas e só constrói o grafo depois de verificar tudo.
+///
+/// Os métodos de [`GraphMut`] causam um `panic` assim que encontram um problema, como uma aresta
+/// para um nó inexistente. Já o `GraphBuilder` aceita qualquer aresta, e os métodos `build_*`
+/// retornam um [`GraphError`] caso alguma delas seja inválida, sem construir nada. Isso é mais
+/// adequado para dados vindos de fora do programa, como um arquivo de entrada.
+#[derive(Default, Debug, Clone)]
+pub struct GraphBuilder<W = Weight> {
+    node_count: usize,
+    edges: Vec<Edge<W>>,
+    directed: bool,
+    self_loops: SelfLoops,
+    duplicates: DuplicateEdges,
+}
+
+impl<W: Num> GraphBuilder<W> {
+    /// Cria um construtor de grafos não direcionados, sem nós.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Cria um construtor de grafos direcionados, sem nós.
+    pub fn directed() -> Self {
+        Self {
+            directed: true,
+            ..Default::default()
+        }
+    }
+    /// Define a política para laços. Com [`SelfLoops::Reject`], laços são um erro
+    /// ([`GraphError::RejectedSelfLoop`]).
+    pub fn with_self_loops(mut self, policy: SelfLoops) -> Self {
+        self.self_loops = policy;
+        self
+    }
+    /// Define a política para arestas repetidas. Veja [`DuplicateEdges`].
+    pub fn with_duplicates(mut self, policy: DuplicateEdges) -> Self {
+        self.duplicates = policy;
+        self
+    }
+    /// Adiciona um nó, retornando o seu identificador.
+    pub fn add_node(&mut self) -> Node {
+        self.add_nodes(1).start
+    }
+    /// Adiciona `count` nós, retornando o intervalo de seus identificadores.
+    pub fn add_nodes(&mut self, count: usize) -> Range<Node> {
+        let start = self.node_count as Node;
+        self.node_count += count;
+        start..self.node_count as Node
+    }
+    /// Adiciona uma aresta entre `a` e `b`. A aresta só é verificada ao construir o grafo.
+    pub fn add_edge(&mut self, a: Node, b: Node, weight: W) {
+        self.edges.push((a, b, weight));
+    }
+    /// Adiciona uma aresta sem peso entre `a` e `b`, o que equivale a uma aresta de peso `1`.
+    pub fn add_edge_unweighted(&mut self, a: Node, b: Node) {
+        self.add_edge(a, b, W::ONE);
+    }
+    /// Adiciona todas as arestas de `edges`. Veja [`GraphBuilder::add_edge`].
+    pub fn add_edges(&mut self, edges: impl IntoIterator<Item = Edge<W>>) {
+        self.edges.extend(edges);
+    }
+
+    /// Constrói um [`GraphAdj`].
+    pub fn build_adj(&self) -> Result<GraphAdj<W>, GraphError> {
+        let base = if self.directed {
+            GraphAdj::directed()
+        } else {
+            GraphAdj::default()
+        };
+        let mut graph = base.with_self_loops(self.self_loops);
+        self.build_into(&mut graph)?;
+        Ok(graph)
+    }
+    /// Constrói um [`GraphMat`].
+    pub fn build_mat(&self) -> Result<GraphMat<W>, GraphError> {
+        let base = if self.directed {
+            GraphMat::directed()
+        } else {
+            GraphMat::default()
+        };
+        let mut graph = base.with_self_loops(self.self_loops);
+        self.build_into(&mut graph)?;
+        Ok(graph)
+    }
+    /// Constrói um [`GraphArena`], com todas as arestas adicionadas de uma vez.
+    pub fn build_arena(&self) -> Result<GraphArena<W>, GraphError> {
+        let base = if self.directed {
+            GraphArena::directed()
+        } else {
+            GraphArena::default()
+        };
+        let mut graph = base.with_self_loops(self.self_loops);
+        let edges = self.checked_edges()?;
+        graph.add_nodes(self.node_count);
+        graph.add_edges(edges);
+        Ok(graph)
+    }
+    /// Constrói um [`GraphCsr`].
+    pub fn build_csr(&self) -> Result<GraphCsr<W>, GraphError> {
+        let edges = self.checked_edges()?;
+        let graph = if self.directed {
+            GraphCsr::directed_from_edges(self.node_count, edges)
+        } else {
+            GraphCsr::from_edges(self.node_count, edges)
+        };
+        Ok(graph.with_self_loops(self.self_loops))
+    }
+    /// Adiciona os nós e arestas a um grafo já existente, mantendo a direção dele.
+    ///
+    /// Os nós são criados com [`GraphMut::add_nodes`], e as arestas são deslocadas de acordo com o
+    /// primeiro nó criado. Ou seja, o nó `n` do construtor vira o nó `n + start`. Em um grafo
+    /// vazio, os identificadores são os mesmos.
+    ///
+    /// Caso haja algum erro, `graph` não é modificado.
+    pub fn build_into(&self, graph: &mut dyn GraphMut<W>) -> Result<(), GraphError> {
+        let edges = self.checked_edges()?;
+        let start = graph.add_nodes(self.node_count).start;
+        for (a, b, weight) in edges {
+            graph.add_edge(a + start, b + start, weight);
+        }
+        Ok(())
+    }
+
+    /// Verifica todas as arestas, retornando-as já sem repetições, de acordo com
+    /// [`DuplicateEdges`].
+    fn checked_edges(&self) -> Result<Vec<Edge<W>>, GraphError> {
+        let mut seen: Vec<(Node, Node, usize)> = Vec::with_capacity(self.edges.len());
+        for (i, &(a, b, _)) in self.edges.iter().enumerate() {
+            if a as usize >= self.node_count || b as usize >= self.node_count {
+                return Err(GraphError::DanglingEdge { a, b });
+            }
+            if a == b && self.self_loops == SelfLoops::Reject {
+                return Err(GraphError::RejectedSelfLoop { n: a });
+            }
+            // Em grafos não direcionados, o par é ordenado para que `a - b` e `b - a` tenham a
+            // mesma chave.
+            let (x, y) = if self.directed || a <= b {
+                (a, b)
+            } else {
+                (b, a)
+            };
+            seen.push((x, y, i));
+        }
+        // A ordenação é estável, então as repetições de um par continuam na ordem de inserção.
+        seen.sort_by_key(|&(x, y, _)| (x, y));
+        let mut kept = Vec::with_capacity(seen.len());
+        for group in seen.chunk_by(|p, q| (p.0, p.1) == (q.0, q.1)) {
+            let (a, b, _) = self.edges[group[0].2];
+            if group.len() > 1 && self.duplicates == DuplicateEdges::Reject {
+                return Err(GraphError::DuplicateEdge { a, b });
+            }
+            let chosen = match self.duplicates {
+                DuplicateEdges::KeepFirst => group[0],
+                _ => group[group.len() - 1],
+            };
+            kept.push(self.edges[chosen.2]);
+        }
+        Ok(kept)
+    }
+}
+
+/// Versão de [`GraphBuilder`] que pode receber nós e arestas de várias threads ao mesmo tempo.
+///
+/// Os métodos recebem `&self`, então o construtor pode ser compartilhado entre as threads, por
+/// exemplo dentro de um `for_each` do `rayon` ou de um `std::thread::scope`. As arestas ficam em
+/// vários vetores, cada um protegido por um `Mutex`, e cada thread usa sempre o mesmo vetor. Assim,
+/// as threads quase nunca esperam umas pelas outras.
+///
+/// Nada é verificado durante a inserção: [`ConcurrentBuilder::into_builder`] junta as arestas em
+/// um [`GraphBuilder`], que as verifica ao construir o grafo. Arestas de threads diferentes não
+/// têm uma ordem definida, então, caso uma mesma aresta possa vir de mais de uma thread, o peso
+/// mantido por [`DuplicateEdges::KeepFirst`] e [`DuplicateEdges::KeepLast`] também não é definido.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ConcurrentBuilder<W = Weight> {
+    node_count: AtomicUsize,
+    shards: Vec<Mutex<Vec<Edge<W>>>>,
+    directed: bool,
+    self_loops: SelfLoops,
+    duplicates: DuplicateEdges,
+}
+
+/// Próximo vetor de arestas a ser atribuído a uma thread.
+#[cfg(feature = "std")]
+static NEXT_SHARD: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "std")]
+thread_local! {
+    /// Vetor de arestas usado pela thread atual, escolhido na primeira inserção.
+    static SHARD: usize = NEXT_SHARD.fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg(feature = "std")]
+impl<W: Num> Default for ConcurrentBuilder<W> {
+    fn default() -> Self {
+        // Com mais vetores que threads, duas threads raramente dividem o mesmo vetor.
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Self {
+            node_count: AtomicUsize::new(0),
+            shards: (0..threads * 4).map(|_| Mutex::new(Vec::new())).collect(),
+            directed: false,
+            self_loops: SelfLoops::default(),
+            duplicates: DuplicateEdges::default(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Num> ConcurrentBuilder<W> {
+    /// Cria um construtor de grafos não direcionados, sem nós.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Cria um construtor de grafos direcionados, sem nós.
+    pub fn directed() -> Self {
+        Self {
+            directed: true,
+            ..Default::default()
+        }
+    }
+    /// Define a política para laços, como em [`GraphBuilder::with_self_loops`].
+    pub fn with_self_loops(mut self, policy: SelfLoops) -> Self {
+        self.self_loops = policy;
+        self
+    }
+    /// Define a política para arestas repetidas, como em [`GraphBuilder::with_duplicates`].
+    pub fn with_duplicates(mut self, policy: DuplicateEdges) -> Self {
+        self.duplicates = policy;
+        self
+    }
+    /// Adiciona `count` nós, retornando o intervalo de seus identificadores.
+    ///
+    /// Threads que adicionam nós ao mesmo tempo recebem intervalos diferentes.
+    pub fn add_nodes(&self, count: usize) -> Range<Node> {
+        let start = self.node_count.fetch_add(count, Ordering::Relaxed);
+        start as Node..(start + count) as Node
+    }
+    /// Adiciona uma aresta entre `a` e `b`. A aresta só é verificada ao construir o grafo.
+    pub fn add_edge(&self, a: Node, b: Node, weight: W) {
+        self.shard().push((a, b, weight));
+    }
+    /// Adiciona todas as arestas de `edges` de uma vez, travando o vetor da thread uma única vez.
+    pub fn add_edges(&self, edges: impl IntoIterator<Item = Edge<W>>) {
+        self.shard().extend(edges);
+    }
+    /// Junta as arestas de todas as threads em um [`GraphBuilder`], com os mesmos nós e políticas.
+    pub fn into_builder(self) -> GraphBuilder<W> {
+        let mut edges = Vec::new();
+        for shard in self.shards {
+            edges.append(&mut shard.into_inner().unwrap_or_else(PoisonError::into_inner));
+        }
+        GraphBuilder {
+            node_count: self.node_count.into_inner(),
+            edges,
+            directed: self.directed,
+            self_loops: self.self_loops,
+            duplicates: self.duplicates,
+        }
+    }
+    /// Trava o vetor de arestas da thread atual.
+    fn shard(&self) -> std::sync::MutexGuard<'_, Vec<Edge<W>>> {
+        let i = SHARD.with(|&i| i) % self.shards.len();
+        // Uma thread que causou um `panic` durante um `push` não deixa o vetor inválido.
+        self.shards[i]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
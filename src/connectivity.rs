@@ -0,0 +1,296 @@
+use core::ops::Range;
+
+use crate::prelude::*;
+use crate::{node_ids, total_order, Edge, GraphAdj, GraphMut, GraphRef, Map, Node, Num};
+
+/// Estrutura de conjuntos disjuntos (_union-find_) sobre os nós `0..len()`.
+///
+/// Cada conjunto é uma árvore, representada pelo vetor `parent`, cuja raiz identifica o conjunto.
+/// Usamos as duas otimizações clássicas: ao unir, a árvore menor vira filha da maior, e ao buscar,
+/// os nós do caminho passam a apontar diretamente para a raiz. Juntas, elas tornam cada operação
+/// praticamente constante (O(α(n)), onde α é a inversa da função de Ackermann).
+#[derive(Debug, Clone, Default)]
+pub struct UnionFind {
+    parent: Vec<Node>,
+    /// Quantidade de nós da árvore de cada raiz. Só é significativo para raízes.
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    /// Cria `len` conjuntos, cada um com um único nó.
+    pub fn new(len: usize) -> Self {
+        let mut sets = Self::default();
+        sets.grow(len);
+        sets
+    }
+    /// Quantidade de nós, ou seja, de conjuntos criados no início.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+    /// Indica se não há nenhum nó.
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+    /// Cria conjuntos unitários para novos nós, até que existam `len` nós. Não faz nada caso já
+    /// existam pelo menos `len` nós.
+    pub fn grow(&mut self, len: usize) {
+        for n in self.parent.len()..len {
+            self.parent.push(n as Node);
+            self.size.push(1);
+        }
+    }
+    /// Retorna a raiz do conjunto de `n`.
+    ///
+    /// Causa um `panic` caso `n` esteja fora de `0..len()`.
+    pub fn find(&mut self, n: Node) -> Node {
+        let mut root = n;
+        while self.parent[root as usize] != root {
+            root = self.parent[root as usize];
+        }
+        // Compressão de caminho: percorre o caminho de novo, ligando cada nó direto à raiz.
+        let mut n = n;
+        while n != root {
+            let next = self.parent[n as usize];
+            self.parent[n as usize] = root;
+            n = next;
+        }
+        root
+    }
+    /// Une os conjuntos de `a` e `b`, retornando `false` caso eles já fossem o mesmo conjunto.
+    pub fn union(&mut self, a: Node, b: Node) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (big, small) = if self.size[a as usize] >= self.size[b as usize] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[small as usize] = big;
+        self.size[big as usize] += self.size[small as usize];
+        true
+    }
+    /// Indica se `a` e `b` estão no mesmo conjunto.
+    pub fn same_set(&mut self, a: Node, b: Node) -> bool {
+        self.find(a) == self.find(b)
+    }
+}
+
+/// Separa os nós de `graph` nas suas componentes conexas, ignorando a direção das arestas.
+///
+/// Cada componente é um vetor com os seus nós em ordem crescente, e as componentes são ordenadas
+/// pelo seu menor nó. Usa um [`UnionFind`], então o custo é praticamente O(V + E).
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "connected_components", level = "debug", skip_all)
+)]
+pub fn connected_components<W: Num>(graph: &dyn GraphRef<W>) -> Vec<Vec<Node>> {
+    let bound = node_ids(graph).last().map_or(0, |n| n as usize + 1);
+    let mut sets = UnionFind::new(bound);
+    for (a, b, _) in graph.edges_iter() {
+        sets.union(a, b);
+    }
+    // Como os nós são visitados em ordem crescente, a primeira vez que uma raiz aparece é no menor
+    // nó da sua componente.
+    let mut index = Map::new();
+    let mut components: Vec<Vec<Node>> = Vec::new();
+    for n in node_ids(graph) {
+        let root = sets.find(n);
+        let i = *index.entry(root).or_insert_with(|| {
+            components.push(Vec::new());
+            components.len() - 1
+        });
+        components[i].push(n);
+    }
+    trace_event!("components found", components = components.len());
+    components
+}
+
+/// Calcula uma árvore geradora mínima de `graph` pelo algoritmo de Kruskal.
+///
+/// As arestas são percorridas da mais leve para a mais pesada, e cada uma entra na árvore caso
+/// ligue dois nós que ainda estão em conjuntos diferentes do [`UnionFind`], ou seja, caso não forme
+/// um ciclo. Isso custa O(E log E), dominado pela ordenação.
+///
+/// Caso o grafo não seja conexo, o resultado é uma floresta, com uma árvore por componente. A
+/// direção das arestas é ignorada, e em grafos não direcionados cada aresta aparece como `a <= b`.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "minimum_spanning_tree", level = "debug", skip_all)
+)]
+pub fn minimum_spanning_tree<W: Num>(graph: &dyn GraphRef<W>) -> Vec<Edge<W>> {
+    let mut edges: Vec<_> = graph
+        .edges_iter()
+        .filter(|&(a, b, _)| graph.is_directed() || a <= b)
+        .collect();
+    // Pesos como `f64` não têm ordem total. Com `total_order`, arestas com peso `NaN` são
+    // consideradas mais pesadas que todas as outras, como em `GraphRef::heaviest_edges`.
+    edges.sort_by(|x, y| total_order(x.2, y.2));
+    let bound = node_ids(graph).last().map_or(0, |n| n as usize + 1);
+    let mut sets = UnionFind::new(bound);
+    let candidates = edges.len();
+    edges.retain(|&(a, b, _)| sets.union(a, b));
+    trace_event!(
+        "spanning forest built",
+        candidates = candidates,
+        tree_edges = edges.len(),
+    );
+    edges
+}
+
+/// Grafo que responde rapidamente se dois nós estão conectados, mesmo enquanto é modificado.
+///
+/// Mantém um [`UnionFind`] junto do grafo envolvido (por padrão, [`GraphAdj`]): cada aresta
+/// adicionada une os conjuntos dos seus nós, em tempo praticamente constante. Remover arestas é
+/// mais difícil, já que um conjunto não pode ser separado. Por isso, remoções apenas marcam a
+/// estrutura como desatualizada, e ela é reconstruída, em O(V + E), na próxima consulta. Assim,
+/// várias remoções seguidas custam uma única reconstrução.
+///
+/// Em grafos direcionados, a direção das arestas é ignorada, ou seja, as consultas tratam de
+/// conectividade fraca.
+#[derive(Debug, Clone)]
+pub struct Connectivity<G = GraphAdj> {
+    graph: G,
+    sets: UnionFind,
+    /// Indica se `sets` precisa ser reconstruído antes da próxima consulta.
+    stale: bool,
+}
+
+impl<G: Default> Default for Connectivity<G> {
+    fn default() -> Self {
+        Self {
+            graph: G::default(),
+            sets: UnionFind::default(),
+            stale: true,
+        }
+    }
+}
+
+impl<G> Connectivity<G> {
+    /// Envolve o grafo `graph`. A estrutura é construída na primeira consulta.
+    pub fn new(graph: G) -> Self {
+        Self {
+            graph,
+            sets: UnionFind::default(),
+            stale: true,
+        }
+    }
+    /// Retorna uma referência ao grafo envolvido.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+    /// Descarta a estrutura e retorna o grafo envolvido.
+    pub fn into_graph(self) -> G {
+        self.graph
+    }
+}
+
+impl<G> Connectivity<G> {
+    /// Indica se existe um caminho entre `a` e `b`, ignorando a direção das arestas.
+    ///
+    /// Retorna `false` caso algum dos nós não exista.
+    pub fn connected<W: Num>(&mut self, a: Node, b: Node) -> bool
+    where
+        G: GraphRef<W>,
+    {
+        if !self.graph.has_node(a) || !self.graph.has_node(b) {
+            return false;
+        }
+        self.refresh();
+        self.sets.same_set(a, b)
+    }
+    /// Retorna a quantidade de componentes conexas do grafo, ignorando a direção das arestas.
+    pub fn component_count<W: Num>(&mut self) -> usize
+    where
+        G: GraphRef<W>,
+    {
+        self.refresh();
+        node_ids(&self.graph)
+            .filter(|&n| self.sets.find(n) == n)
+            .count()
+    }
+
+    /// Reconstrói `sets` a partir das arestas do grafo, caso esteja desatualizado.
+    fn refresh<W: Num>(&mut self)
+    where
+        G: GraphRef<W>,
+    {
+        if !self.stale {
+            return;
+        }
+        let bound = node_ids(&self.graph).last().map_or(0, |n| n as usize + 1);
+        self.sets = UnionFind::new(bound);
+        for (a, b, _) in self.graph.edges_iter() {
+            self.sets.union(a, b);
+        }
+        self.stale = false;
+    }
+    /// Inclui em `sets` os nós até `end - 1`, caso ainda não estejam lá.
+    fn cover(&mut self, end: Node) {
+        // Se `sets` está desatualizado, os nós novos serão incluídos na reconstrução.
+        if !self.stale {
+            self.sets.grow(end as usize);
+        }
+    }
+    /// Une os conjuntos de `a` e `b` após a criação da aresta `a - b`.
+    fn link(&mut self, a: Node, b: Node) {
+        if !self.stale {
+            self.sets.union(a, b);
+        }
+    }
+}
+
+impl<W: Num, G: GraphRef<W>> GraphRef<W> for Connectivity<G> {
+    delegate_graph_queries!();
+}
+
+impl<W: Num, G: GraphMut<W>> GraphMut<W> for Connectivity<G> {
+    fn add_node(&mut self) -> Node {
+        let node = self.graph.add_node();
+        self.cover(node + 1);
+        node
+    }
+    fn add_nodes(&mut self, count: usize) -> Range<Node> {
+        let nodes = self.graph.add_nodes(count);
+        self.cover(nodes.end);
+        nodes
+    }
+    fn add_edge(&mut self, a: Node, b: Node, weight: W) {
+        self.graph.add_edge(a, b, weight);
+        self.link(a, b);
+    }
+    fn add_edges<I>(&mut self, edges: I)
+    where
+        I: IntoIterator<Item = Edge<W>>,
+    {
+        let edges: Vec<_> = edges.into_iter().collect();
+        let pairs: Vec<_> = edges.iter().map(|&(a, b, _)| (a, b)).collect();
+        self.graph.add_edges(edges);
+        for (a, b) in pairs {
+            self.link(a, b);
+        }
+    }
+    fn remove_node(&mut self, n: Node) {
+        self.graph.remove_node(n);
+        self.stale = true;
+    }
+    fn remove_edge(&mut self, a: Node, b: Node) -> Option<W> {
+        let weight = self.graph.remove_edge(a, b)?;
+        self.stale = true;
+        Some(weight)
+    }
+    fn clear(&mut self) {
+        self.graph.clear();
+        self.sets = UnionFind::default();
+        self.stale = false;
+    }
+    fn compact(&mut self) -> Map<Node, Node> {
+        self.stale = true;
+        self.graph.compact()
+    }
+    fn reverse(&mut self) {
+        // A direção das arestas não afeta a conectividade fraca.
+        self.graph.reverse()
+    }
+}
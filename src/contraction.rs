@@ -0,0 +1,396 @@
+//! Hierarquias de contração, para responder muitas consultas de caminho mínimo em um mesmo grafo.
+//!
+//! A ideia é remover (contrair) os nós um a um, do menos importante para o mais importante. Ao
+//! contrair `v`, cada caminho `u -> v -> x` que é o único caminho mínimo de `u` até `x` vira um
+//! atalho `u -> x`, com o mesmo peso, para que as distâncias entre os nós restantes não mudem. Ao
+//! final, todo caminho mínimo pode ser trocado por um que primeiro só "sobe" na ordem de contração
+//! e depois só "desce". Uma consulta é então uma busca de Dijkstra a partir da origem que só sobe,
+//! e outra a partir do destino, de trás para frente, que também só sobe, e as duas se encontram no
+//! nó mais importante do caminho. Em grafos como redes de estradas, cada busca visita poucas
+//! centenas de nós, mesmo com milhões de nós no grafo.
+
+use alloc::collections::BinaryHeap;
+use core::cmp::Reverse;
+
+use crate::paths::Entry;
+use crate::prelude::*;
+use crate::{node_ids, GraphRef, Map, Node, Num, Weight};
+
+/// Quantidade máxima de nós visitados por cada busca por testemunhas em [`Contractor`]. Com um
+/// limite pequeno, a contração é mais rápida, mas algumas testemunhas deixam de ser encontradas e
+/// viram atalhos desnecessários, que tornam as consultas mais lentas (mas continuam corretas).
+const WITNESS_LIMIT: usize = 256;
+
+/// Aresta da hierarquia. Em um atalho, `middle` é o nó contraído que ele substitui: o atalho
+/// `a -> b` equivale às arestas `a -> middle` e `middle -> b`, que também estão na hierarquia.
+#[derive(Debug, Clone, Copy)]
+struct Link<W> {
+    weight: W,
+    middle: Option<Node>,
+}
+
+/// Estrutura de busca que responde consultas de caminho mínimo entre dois nós de um grafo fixo,
+/// criada por [`ContractionHierarchy::new`].
+///
+/// A criação contrai todos os nós, o que custa bem mais que um [`crate::dijkstra`], mas cada
+/// consulta depois disso é muito mais rápida que um [`crate::shortest_path`], então a estrutura
+/// compensa quando muitas consultas são feitas no mesmo grafo. Os resultados são os mesmos de
+/// `shortest_path`, exceto a escolha entre caminhos de mesmo peso. Assim como Dijkstra, supõe que
+/// nenhum peso seja negativo.
+///
+/// A estrutura é uma cópia: modificações no grafo depois da criação não são vistas.
+#[derive(Debug, Clone)]
+pub struct ContractionHierarchy<W = Weight> {
+    /// Posição de cada nó na ordem de contração, ou `None` caso ele não exista no grafo.
+    rank: Vec<Option<usize>>,
+    /// Arestas que saem de cada nó para nós contraídos depois dele, percorridas pela busca a partir
+    /// da origem.
+    up: Vec<Vec<(Node, Link<W>)>>,
+    /// Arestas que chegam em cada nó vindas de nós contraídos depois dele, percorridas de trás para
+    /// frente pela busca a partir do destino.
+    down: Vec<Vec<(Node, Link<W>)>>,
+    shortcuts: usize,
+}
+
+/// Arestas de um nó, indexadas pelo outro nó.
+type Links<W> = Map<Node, Link<W>>;
+
+/// Grafo restante durante a contração, com as arestas entre os nós ainda não contraídos.
+struct Contractor<W> {
+    outgoing: Vec<Links<W>>,
+    incoming: Vec<Links<W>>,
+    /// Quantos vizinhos de cada nó já foram contraídos. Entra na prioridade para que as
+    /// contrações fiquem espalhadas pelo grafo, em vez de concentradas em uma região.
+    contracted_neighbors: Vec<usize>,
+    /// Distâncias da busca por testemunhas, reaproveitadas entre as buscas.
+    distance: Vec<Option<W>>,
+    touched: Vec<Node>,
+}
+
+impl<W: Num> Contractor<W> {
+    /// Calcula em `distance` as distâncias a partir de `source` sem passar por `skip`, até `limit`,
+    /// visitando no máximo [`WITNESS_LIMIT`] nós. Para antes, assim que as distâncias de todos os
+    /// `targets` forem definitivas.
+    ///
+    /// As posições preenchidas ficam em `touched`, para que possam ser limpas sem percorrer o vetor
+    /// inteiro, que tem uma posição por nó.
+    fn witness_search(&mut self, source: Node, skip: Node, limit: W, targets: &[(Node, W)]) {
+        let mut remaining = targets.len();
+        self.distance[source as usize] = Some(W::ZERO);
+        self.touched.push(source);
+        let mut queue = BinaryHeap::from([Entry(W::ZERO, source)]);
+        let mut settled = 0;
+        while let Some(Entry(dist, a)) = queue.pop() {
+            if self.distance[a as usize].is_some_and(|current| current < dist) {
+                continue;
+            }
+            if dist > limit || settled == WITNESS_LIMIT || remaining == 0 {
+                break;
+            }
+            settled += 1;
+            if targets.iter().any(|&(x, _)| x == a) {
+                remaining -= 1;
+            }
+            for (&b, link) in &self.outgoing[a as usize] {
+                let candidate = dist + link.weight;
+                let current = &mut self.distance[b as usize];
+                if b != skip && current.is_none_or(|current| candidate < current) {
+                    if current.is_none() {
+                        self.touched.push(b);
+                    }
+                    *current = Some(candidate);
+                    queue.push(Entry(candidate, b));
+                }
+            }
+        }
+    }
+    /// Atalhos `(u, x, peso)` necessários para contrair `v`: os caminhos `u -> v -> x` para os
+    /// quais nenhum caminho sem `v` (uma testemunha) foi encontrado com peso até o deles.
+    fn shortcuts(&mut self, v: Node) -> Vec<(Node, Node, W)> {
+        let mut shortcuts = Vec::new();
+        let incoming: Vec<_> = self.incoming[v as usize]
+            .iter()
+            .map(|(&u, link)| (u, link.weight))
+            .collect();
+        for (u, first) in incoming {
+            let targets: Vec<_> = self.outgoing[v as usize]
+                .iter()
+                .filter(|(&x, _)| x != u)
+                .map(|(&x, link)| (x, first + link.weight))
+                .collect();
+            // A busca por testemunhas só precisa ir até o peso do caminho mais longo por `v`.
+            let mut limit = None;
+            for &(_, weight) in &targets {
+                if limit.is_none_or(|limit| weight > limit) {
+                    limit = Some(weight);
+                }
+            }
+            let Some(limit) = limit else {
+                continue;
+            };
+            self.witness_search(u, v, limit, &targets);
+            for (x, weight) in targets {
+                if self.distance[x as usize].is_none_or(|witness| witness > weight) {
+                    shortcuts.push((u, x, weight));
+                }
+            }
+            for n in self.touched.drain(..) {
+                self.distance[n as usize] = None;
+            }
+        }
+        shortcuts
+    }
+    /// Prioridade de `v`, menor para os nós que devem ser contraídos primeiro: quantas arestas a
+    /// contração adiciona (`shortcuts`) a mais do que remove, mais quantos vizinhos já foram
+    /// contraídos.
+    fn priority(&self, v: Node, shortcuts: usize) -> i64 {
+        let v = v as usize;
+        let removed = self.outgoing[v].len() + self.incoming[v].len();
+        shortcuts as i64 - removed as i64 + self.contracted_neighbors[v] as i64
+    }
+    /// Contrai `v`, adicionando os atalhos calculados por [`Contractor::shortcuts`], e retorna as
+    /// suas arestas de saída e de entrada, além de quantos atalhos foram adicionados.
+    fn contract(
+        &mut self,
+        v: Node,
+        shortcuts: Vec<(Node, Node, W)>,
+    ) -> (Links<W>, Links<W>, usize) {
+        let outgoing = core::mem::take(&mut self.outgoing[v as usize]);
+        let incoming = core::mem::take(&mut self.incoming[v as usize]);
+        for &x in outgoing.keys() {
+            self.incoming[x as usize].remove(&v);
+            self.contracted_neighbors[x as usize] += 1;
+        }
+        for &u in incoming.keys() {
+            self.outgoing[u as usize].remove(&v);
+            self.contracted_neighbors[u as usize] += 1;
+        }
+        let mut added = 0;
+        for (u, x, weight) in shortcuts {
+            // Outro atalho, ou uma aresta do grafo, pode já ligar os dois nós com peso menor.
+            if self.outgoing[u as usize]
+                .get(&x)
+                .is_some_and(|link| link.weight <= weight)
+            {
+                continue;
+            }
+            let link = Link {
+                weight,
+                middle: Some(v),
+            };
+            self.outgoing[u as usize].insert(x, link);
+            self.incoming[x as usize].insert(u, link);
+            added += 1;
+        }
+        (outgoing, incoming, added)
+    }
+}
+
+/// Uma das duas buscas de uma consulta: as distâncias encontradas, o nó anterior de cada nó e a
+/// fila de prioridade.
+struct Search<W> {
+    distance: Map<Node, W>,
+    parent: Map<Node, Node>,
+    queue: BinaryHeap<Entry<W>>,
+}
+
+impl<W: Num> Search<W> {
+    fn new(source: Node) -> Self {
+        Self {
+            distance: Map::from([(source, W::ZERO)]),
+            parent: Map::new(),
+            queue: BinaryHeap::from([Entry(W::ZERO, source)]),
+        }
+    }
+}
+
+impl<W: Num> ContractionHierarchy<W> {
+    /// Contrai todos os nós de `graph`, criando a hierarquia. Os laços são ignorados, já que não
+    /// fazem parte de caminhos mínimos.
+    ///
+    /// Os primeiros nós contraídos são os que adicionam menos atalhos em relação às arestas que
+    /// removem. A prioridade é atualizada de forma preguiçosa: ao sair da fila, a prioridade de um
+    /// nó é recalculada, e ele volta para a fila caso tenha deixado de ser a menor.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "contraction_hierarchy", level = "debug", skip_all)
+    )]
+    pub fn new(graph: &dyn GraphRef<W>) -> Self {
+        let slots = node_ids(graph).last().map_or(0, |n| n as usize + 1);
+        let mut contractor = Contractor {
+            outgoing: vec![Map::new(); slots],
+            incoming: vec![Map::new(); slots],
+            contracted_neighbors: vec![0; slots],
+            distance: vec![None; slots],
+            touched: Vec::new(),
+        };
+        graph.for_each_edge(&mut |(a, b, weight)| {
+            if a != b {
+                let link = Link {
+                    weight,
+                    middle: None,
+                };
+                contractor.outgoing[a as usize].insert(b, link);
+                contractor.incoming[b as usize].insert(a, link);
+            }
+        });
+
+        let mut queue = BinaryHeap::new();
+        for v in node_ids(graph) {
+            let shortcuts = contractor.shortcuts(v).len();
+            queue.push(Reverse((contractor.priority(v, shortcuts), v)));
+        }
+        let mut hierarchy = Self {
+            rank: vec![None; slots],
+            up: vec![Vec::new(); slots],
+            down: vec![Vec::new(); slots],
+            shortcuts: 0,
+        };
+        let mut order = 0;
+        while let Some(Reverse((_, v))) = queue.pop() {
+            let shortcuts = contractor.shortcuts(v);
+            let priority = contractor.priority(v, shortcuts.len());
+            if queue
+                .peek()
+                .is_some_and(|Reverse((next, _))| priority > *next)
+            {
+                queue.push(Reverse((priority, v)));
+                continue;
+            }
+            let (outgoing, incoming, added) = contractor.contract(v, shortcuts);
+            hierarchy.rank[v as usize] = Some(order);
+            hierarchy.up[v as usize] = outgoing.into_iter().collect();
+            hierarchy.down[v as usize] = incoming.into_iter().collect();
+            hierarchy.shortcuts += added;
+            order += 1;
+        }
+        trace_event!(
+            "hierarchy built",
+            nodes = order,
+            shortcuts = hierarchy.shortcuts
+        );
+        hierarchy
+    }
+    /// Quantidade de atalhos adicionados durante a contração.
+    pub fn shortcut_count(&self) -> usize {
+        self.shortcuts
+    }
+    /// Retorna a distância de `source` até `target`, ou `None` caso `target` não seja alcançável.
+    ///
+    /// Causa um `panic` caso `source` ou `target` não existam.
+    pub fn distance(&self, source: Node, target: Node) -> Option<W> {
+        self.query(source, target).map(|(_, _, distance)| distance)
+    }
+    /// Retorna os nós de um caminho mínimo de `source` até `target`, incluindo os dois, e o seu
+    /// peso total, ou `None` caso `target` não seja alcançável, como em [`crate::shortest_path`].
+    ///
+    /// Os atalhos do caminho encontrado são desfeitos, então o caminho usa apenas arestas do grafo
+    /// original. Causa um `panic` caso `source` ou `target` não existam.
+    pub fn shortest_path(&self, source: Node, target: Node) -> Option<(Vec<Node>, W)> {
+        let ([forward, backward], meeting, distance) = self.query(source, target)?;
+        // Arestas da hierarquia no caminho, na ordem do caminho: subindo da origem até o encontro
+        // e descendo do encontro até o destino.
+        let mut links = Vec::new();
+        let mut n = meeting;
+        while let Some(&previous) = forward.parent.get(&n) {
+            links.push((previous, n));
+            n = previous;
+        }
+        links.reverse();
+        let mut n = meeting;
+        while let Some(&next) = backward.parent.get(&n) {
+            links.push((n, next));
+            n = next;
+        }
+        let mut path = vec![source];
+        for (a, b) in links {
+            self.unpack(a, b, &mut path);
+        }
+        Some((path, distance))
+    }
+
+    /// Executa as duas buscas, retornando-as junto com o nó em que se encontram e a distância.
+    fn query(&self, source: Node, target: Node) -> Option<([Search<W>; 2], Node, W)> {
+        for n in [source, target] {
+            if self.rank.get(n as usize).is_none_or(Option::is_none) {
+                panic!("Tried to search between inexistent node {n}");
+            }
+        }
+        let mut searches = [Search::new(source), Search::new(target)];
+        let mut best: Option<(W, Node)> = (source == target).then_some((W::ZERO, source));
+        loop {
+            // Cada busca para quando a sua menor distância não pode mais melhorar o caminho; entre
+            // as que continuam, avança a de menor distância.
+            let open = |search: &Search<W>| {
+                let next = search.queue.peek()?.0;
+                best.is_none_or(|(best, _)| next < best).then_some(next)
+            };
+            let side = match (open(&searches[0]), open(&searches[1])) {
+                (None, None) => break,
+                (Some(_), None) => 0,
+                (None, Some(_)) => 1,
+                (Some(x), Some(y)) => usize::from(y < x),
+            };
+            let Some(Entry(dist, a)) = searches[side].queue.pop() else {
+                break;
+            };
+            if searches[side].distance[&a] < dist {
+                continue;
+            }
+            if let Some(&other) = searches[1 - side].distance.get(&a) {
+                let total = dist + other;
+                if best.is_none_or(|(best, _)| total < best) {
+                    best = Some((total, a));
+                }
+            }
+            let (links, reverse) = match side {
+                0 => (&self.up[a as usize], &self.down[a as usize]),
+                _ => (&self.down[a as usize], &self.up[a as usize]),
+            };
+            let search = &mut searches[side];
+            // Caso a busca chegue em `a` por um nó mais importante com uma distância menor, `a`
+            // não está em um caminho mínimo da busca, e as suas arestas podem ser ignoradas
+            // (_stall-on-demand_).
+            let stalled = reverse.iter().any(|&(b, link)| {
+                (search.distance.get(&b)).is_some_and(|&current| current + link.weight < dist)
+            });
+            if stalled {
+                continue;
+            }
+            for &(b, link) in links {
+                let candidate = dist + link.weight;
+                if search
+                    .distance
+                    .get(&b)
+                    .is_none_or(|&current| candidate < current)
+                {
+                    search.distance.insert(b, candidate);
+                    search.parent.insert(b, a);
+                    search.queue.push(Entry(candidate, b));
+                }
+            }
+        }
+        let (distance, meeting) = best?;
+        Some((searches, meeting, distance))
+    }
+    /// Aresta `a -> b` da hierarquia, guardada no nó que foi contraído primeiro.
+    fn link(&self, a: Node, b: Node) -> Link<W> {
+        let up = self.up[a as usize].iter().find(|(x, _)| *x == b);
+        let down = || self.down[b as usize].iter().find(|(x, _)| *x == a);
+        up.or_else(down).expect("hierarchy edge should exist").1
+    }
+    /// Adiciona a `path` os nós depois de `a` no caminho que a aresta `a -> b` representa,
+    /// desfazendo os atalhos. Usa uma pilha, já que os atalhos podem estar aninhados.
+    fn unpack(&self, a: Node, b: Node, path: &mut Vec<Node>) {
+        let mut stack = vec![(a, b)];
+        while let Some((a, b)) = stack.pop() {
+            match self.link(a, b).middle {
+                Some(middle) => {
+                    stack.push((middle, b));
+                    stack.push((a, middle));
+                }
+                None => path.push(b),
+            }
+        }
+    }
+}
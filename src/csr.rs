@@ -0,0 +1,324 @@
+use core::ops::Range;
+
+use crate::prelude::*;
+use crate::{
+    compact_mapping, map_bytes, sort_last_wins, validate_edges, vec_bytes, Edge, GraphError,
+    GraphMut, GraphRef, Map, Node, Num, SelfLoops, Set, Weight,
+};
+
+/// Struct que representa um grafo no formato CSR (compressed sparse row).
+///
+/// Os vizinhos de todos os nós ficam em um único vetor contíguo, ordenados por nó de origem e,
+/// dentro de cada nó, por nó de destino. O vetor `offsets` indica onde começam os vizinhos de cada
+/// nó: os vizinhos do nó `n` ficam em `targets[offsets[n]..offsets[n + 1]]`.
+///
+/// Essa representação é pensada para ser construída uma vez, a partir de uma lista de arestas, e
+/// depois apenas consultada: percorrer os vizinhos de um nó é percorrer um trecho contínuo de
+/// memória, e o grau de um nó é obtido em O(1). Os métodos que modificam o grafo funcionam, mas
+/// precisam deslocar os vetores e custam O(V + E).
+#[derive(Default, Debug, Clone)]
+pub struct GraphCsr<W = Weight> {
+    /// Possui um elemento a mais que a quantidade de nós, para que o fim dos vizinhos do último nó
+    /// também seja representado.
+    offsets: Vec<usize>,
+    targets: Vec<Node>,
+    /// Peso de cada aresta, na mesma posição do seu destino em `targets`.
+    weights: Vec<W>,
+    directed: bool,
+    /// Nós removidos. Eles continuam tendo uma posição em `offsets`, mas sem vizinhos.
+    removed: Set<Node>,
+    self_loops: SelfLoops,
+}
+
+impl<W: Num> GraphCsr<W> {
+    /// Constrói um grafo não direcionado com `node_count` nós a partir de uma lista de arestas.
+    ///
+    /// Assim como em `add_edge`, caso uma aresta apareça mais de uma vez, o último peso é mantido.
+    /// Causa um `panic` caso alguma aresta referencie um nó fora de `0..node_count`.
+    pub fn from_edges(node_count: usize, edges: impl IntoIterator<Item = Edge<W>>) -> Self {
+        Self::build(node_count, edges, false)
+    }
+    /// Constrói um grafo direcionado com `node_count` nós a partir de uma lista de arestas.
+    ///
+    /// Veja [`GraphCsr::from_edges`].
+    pub fn directed_from_edges(
+        node_count: usize,
+        edges: impl IntoIterator<Item = Edge<W>>,
+    ) -> Self {
+        Self::build(node_count, edges, true)
+    }
+    /// Define a política do grafo para laços. Só afeta as arestas adicionadas depois.
+    pub fn with_self_loops(mut self, policy: SelfLoops) -> Self {
+        self.self_loops = policy;
+        self
+    }
+    /// Retorna os destinos das arestas que saem de `n`, em ordem crescente.
+    pub fn neighbor_slice(&self, n: Node) -> &[Node] {
+        &self.targets[self.range(n)]
+    }
+    /// Retorna os pesos das arestas que saem de `n`, na mesma ordem de
+    /// [`GraphCsr::neighbor_slice`].
+    pub fn weight_slice(&self, n: Node) -> &[W] {
+        &self.weights[self.range(n)]
+    }
+
+    /// Retorna quantos bytes o grafo ocupa na heap, sem contar o próprio `GraphCsr`.
+    pub fn memory_usage(&self) -> usize {
+        vec_bytes(&self.offsets)
+            + vec_bytes(&self.targets)
+            + vec_bytes(&self.weights)
+            + map_bytes::<Node, ()>(self.removed.len())
+    }
+
+    fn build(node_count: usize, edges: impl IntoIterator<Item = Edge<W>>, directed: bool) -> Self {
+        let mut entries = Vec::new();
+        for (a, b, weight) in edges {
+            if a as usize >= node_count || b as usize >= node_count {
+                panic!("Tried to add edge {a} {b} to a graph with {node_count} nodes");
+            }
+            entries.push((a, b, weight));
+            if !directed && a != b {
+                entries.push((b, a, weight));
+            }
+        }
+        let mut graph = Self {
+            offsets: vec![0; node_count + 1],
+            directed,
+            ..Default::default()
+        };
+        graph.set_entries(entries);
+        graph
+    }
+    /// Substitui todas as arestas do grafo por `entries`, que pode ter repetições e estar fora de
+    /// ordem. Os nós continuam os mesmos.
+    fn set_entries(&mut self, mut entries: Vec<Edge<W>>) {
+        sort_last_wins(&mut entries);
+
+        // Conta quantas arestas saem de cada nó e acumula as contagens, de forma que
+        // `offsets[n]` seja a quantidade de arestas que saem dos nós anteriores a `n`.
+        self.offsets.fill(0);
+        for e in &entries {
+            self.offsets[e.0 as usize + 1] += 1;
+        }
+        for n in 0..self.slots() {
+            self.offsets[n + 1] += self.offsets[n];
+        }
+
+        self.targets = entries.iter().map(|e| e.1).collect();
+        self.weights = entries.iter().map(|e| e.2).collect();
+    }
+    /// Quantidade de posições em `offsets`, incluindo nós removidos.
+    fn slots(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+    /// Intervalo de `targets` e `weights` que contém os vizinhos de `n`.
+    fn range(&self, n: Node) -> core::ops::Range<usize> {
+        let n = n as usize;
+        if n >= self.slots() {
+            return 0..0;
+        }
+        self.offsets[n]..self.offsets[n + 1]
+    }
+    fn check_node(&self, n: Node, action: &str) {
+        if !self.has_node(n) {
+            panic!("Tried to {action} inexistent node {n}");
+        }
+    }
+    /// Insere ou atualiza a entrada `a -> b`.
+    fn insert_entry(&mut self, a: Node, b: Node, weight: W) {
+        let range = self.range(a);
+        match self.targets[range.clone()].binary_search(&b) {
+            Ok(pos) => self.weights[range.start + pos] = weight,
+            Err(pos) => {
+                self.targets.insert(range.start + pos, b);
+                self.weights.insert(range.start + pos, weight);
+                for offset in &mut self.offsets[a as usize + 1..] {
+                    *offset += 1;
+                }
+            }
+        }
+    }
+    /// Remove a entrada `a -> b`, retornando o seu peso.
+    fn remove_entry(&mut self, a: Node, b: Node) -> Option<W> {
+        let range = self.range(a);
+        let pos = self.targets[range.clone()].binary_search(&b).ok()?;
+        self.targets.remove(range.start + pos);
+        let weight = self.weights.remove(range.start + pos);
+        for offset in &mut self.offsets[a as usize + 1..] {
+            *offset -= 1;
+        }
+        Some(weight)
+    }
+}
+
+impl<W: Num> GraphRef<W> for GraphCsr<W> {
+    fn validate(&self) -> Result<(), GraphError> {
+        let invalid = |message: &str| Err(GraphError::InvalidStorage(message.to_string()));
+        if self.targets.len() != self.weights.len() {
+            return invalid("targets and weights have different lengths");
+        }
+        if self.offsets.last().copied().unwrap_or(0) != self.targets.len() {
+            return invalid("last offset does not match the number of edges");
+        }
+        if self.offsets.windows(2).any(|w| w[0] > w[1]) {
+            return invalid("offsets are not in non-decreasing order");
+        }
+        // Os vizinhos de cada nó precisam estar em ordem estritamente crescente, o que também
+        // garante que não há arestas repetidas.
+        for n in 0..self.slots() as Node {
+            if self.neighbor_slice(n).windows(2).any(|w| w[0] >= w[1]) {
+                return Err(GraphError::InvalidStorage(format!(
+                    "neighbors of node {n} are not sorted"
+                )));
+            }
+        }
+        validate_edges(self.edges_iter(), self)
+    }
+    fn edges(&self) -> Vec<Edge<W>> {
+        // Os vetores já estão ordenados por origem e destino.
+        self.edges_iter().collect()
+    }
+    fn edges_iter(&self) -> Box<dyn Iterator<Item = Edge<W>> + '_> {
+        Box::new((0..self.slots() as Node).flat_map(move |a| {
+            self.neighbor_slice(a)
+                .iter()
+                .zip(self.weight_slice(a))
+                .map(move |(&b, &weight)| (a, b, weight))
+        }))
+    }
+    fn node_count(&self) -> usize {
+        self.slots() - self.removed.len()
+    }
+    fn has_node(&self, n: Node) -> bool {
+        (n as usize) < self.slots() && !self.removed.contains(&n)
+    }
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+    fn self_loops(&self) -> SelfLoops {
+        self.self_loops
+    }
+    fn out_edges(&self, a: Node) -> Vec<Edge<W>> {
+        self.neighbor_slice(a)
+            .iter()
+            .zip(self.weight_slice(a))
+            .map(|(&b, &weight)| (a, b, weight))
+            .collect()
+    }
+    fn neighbors(&self, n: Node) -> Box<dyn Iterator<Item = Node> + '_> {
+        Box::new(self.neighbor_slice(n).iter().copied())
+    }
+    fn out_degree(&self, n: Node) -> usize {
+        self.range(n).len()
+    }
+    fn for_each_neighbor(&self, n: Node, f: &mut dyn FnMut(Node)) {
+        for &b in self.neighbor_slice(n) {
+            f(b);
+        }
+    }
+    fn get_edge_weight(&self, a: Node, b: Node) -> Option<W> {
+        let pos = self.neighbor_slice(a).binary_search(&b).ok()?;
+        Some(self.weight_slice(a)[pos])
+    }
+}
+
+impl<W: Num> GraphMut<W> for GraphCsr<W> {
+    fn add_node(&mut self) -> Node {
+        self.add_nodes(1).start
+    }
+    fn add_nodes(&mut self, count: usize) -> Range<Node> {
+        if self.offsets.is_empty() {
+            self.offsets.push(0);
+        }
+        let first_node = self.slots() as Node;
+        // Os novos nós não possuem vizinhos, então seus intervalos começam e terminam no fim do
+        // vetor.
+        let end = self.targets.len();
+        self.offsets.extend(core::iter::repeat_n(end, count));
+        first_node..self.slots() as Node
+    }
+    fn add_edge(&mut self, a: Node, b: Node, weight: W) {
+        self.self_loops.check(a, b);
+        self.check_node(a, "add edge to");
+        self.check_node(b, "add edge to");
+        self.insert_entry(a, b, weight);
+        if !self.directed && a != b {
+            self.insert_entry(b, a, weight);
+        }
+    }
+    fn add_edges<I>(&mut self, edges: I)
+    where
+        I: IntoIterator<Item = Edge<W>>,
+    {
+        // Em vez de deslocar os vetores a cada aresta, junta as arestas antigas com as novas e
+        // reconstrói os vetores uma única vez. As novas vêm depois, para que seus pesos prevaleçam.
+        let mut entries: Vec<Edge<W>> = self.edges_iter().collect();
+        for (a, b, weight) in edges {
+            self.self_loops.check(a, b);
+            self.check_node(a, "add edge to");
+            self.check_node(b, "add edge to");
+            entries.push((a, b, weight));
+            if !self.directed && a != b {
+                entries.push((b, a, weight));
+            }
+        }
+        self.set_entries(entries);
+    }
+    fn remove_node(&mut self, n: Node) {
+        self.check_node(n, "remove");
+        self.removed.insert(n);
+        // Reconstrói os vetores sem as arestas que incidem em `n`.
+        let mut offsets = Vec::with_capacity(self.offsets.len());
+        let mut targets = Vec::with_capacity(self.targets.len());
+        let mut weights = Vec::with_capacity(self.weights.len());
+        offsets.push(0);
+        for a in 0..self.slots() as Node {
+            if a != n {
+                for i in self.range(a) {
+                    if self.targets[i] != n {
+                        targets.push(self.targets[i]);
+                        weights.push(self.weights[i]);
+                    }
+                }
+            }
+            offsets.push(targets.len());
+        }
+        self.offsets = offsets;
+        self.targets = targets;
+        self.weights = weights;
+    }
+    fn remove_edge(&mut self, a: Node, b: Node) -> Option<W> {
+        let weight = self.remove_entry(a, b)?;
+        if !self.directed && a != b {
+            self.remove_entry(b, a);
+        }
+        Some(weight)
+    }
+    fn clear(&mut self) {
+        self.offsets.clear();
+        self.targets.clear();
+        self.weights.clear();
+        self.removed.clear();
+    }
+    fn compact(&mut self) -> Map<Node, Node> {
+        let mapping = compact_mapping(self);
+        let entries = self
+            .edges_iter()
+            .map(|(a, b, weight)| (mapping[&a], mapping[&b], weight))
+            .collect();
+        self.offsets = vec![0; mapping.len() + 1];
+        self.removed.clear();
+        self.set_entries(entries);
+        mapping
+    }
+    fn reverse(&mut self) {
+        if !self.directed {
+            return;
+        }
+        let entries = self
+            .edges_iter()
+            .map(|(a, b, weight)| (b, a, weight))
+            .collect();
+        self.set_entries(entries);
+    }
+}
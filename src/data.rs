@@ -0,0 +1,265 @@
+use core::ops::Range;
+
+use crate::prelude::*;
+use crate::{Edge, GraphAdj, GraphMut, GraphRef, Map, Node, Num};
+
+/// Grafo cujos nós podem carregar um dado do tipo `N` (um rótulo, uma struct, etc).
+///
+/// Envolve qualquer implementação de [`GraphMut`] (por padrão, [`GraphAdj`]) e também implementa
+/// [`GraphMut`], mantendo os dados sincronizados com o grafo: ao remover um nó, o seu dado também é
+/// removido. Nós criados por `add_node` não possuem dado até que um seja atribuído por
+/// [`NodeData::set_node_data`].
+#[derive(Debug, Clone)]
+pub struct NodeData<N, G = GraphAdj> {
+    graph: G,
+    data: Map<Node, N>,
+}
+
+// Implementamos `Default` manualmente pois o `derive` exigiria que `N` também implementasse
+// `Default`, o que não é necessário.
+impl<N, G: Default> Default for NodeData<N, G> {
+    fn default() -> Self {
+        Self::new(G::default())
+    }
+}
+
+impl<N, G> NodeData<N, G> {
+    /// Envolve o grafo `graph`. Os nós já existentes começam sem dados.
+    pub fn new(graph: G) -> Self {
+        Self {
+            graph,
+            data: Map::new(),
+        }
+    }
+    /// Retorna uma referência ao grafo envolvido.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+    /// Descarta os dados dos nós e retorna o grafo envolvido.
+    pub fn into_graph(self) -> G {
+        self.graph
+    }
+    /// Retorna o dado do nó `n`, caso exista.
+    pub fn node_data(&self, n: Node) -> Option<&N> {
+        self.data.get(&n)
+    }
+    /// Retorna uma referência mutável ao dado do nó `n`, caso exista.
+    pub fn node_data_mut(&mut self, n: Node) -> Option<&mut N> {
+        self.data.get_mut(&n)
+    }
+}
+
+impl<N, G> NodeData<N, G> {
+    /// Cria um novo nó carregando o dado `data`.
+    pub fn add_node_with<W: Num>(&mut self, data: N) -> Node
+    where
+        G: GraphMut<W>,
+    {
+        let node = self.graph.add_node();
+        self.data.insert(node, data);
+        node
+    }
+    /// Atribui o dado `data` ao nó `n`, retornando o dado anterior, caso houvesse um.
+    ///
+    /// Causa um `panic` caso `n` não exista no grafo.
+    pub fn set_node_data<W: Num>(&mut self, n: Node, data: N) -> Option<N>
+    where
+        G: GraphRef<W>,
+    {
+        if !self.graph.has_node(n) {
+            panic!("Tried to set data of inexistent node {n}");
+        }
+        self.data.insert(n, data)
+    }
+}
+
+impl<N, W: Num, G: GraphRef<W>> GraphRef<W> for NodeData<N, G> {
+    delegate_graph_queries!();
+}
+
+impl<N, W: Num, G: GraphMut<W>> GraphMut<W> for NodeData<N, G> {
+    fn add_node(&mut self) -> Node {
+        self.graph.add_node()
+    }
+    fn add_nodes(&mut self, count: usize) -> Range<Node> {
+        self.graph.add_nodes(count)
+    }
+    fn add_edge(&mut self, a: Node, b: Node, weight: W) {
+        self.graph.add_edge(a, b, weight)
+    }
+    fn add_edges<I>(&mut self, edges: I)
+    where
+        I: IntoIterator<Item = Edge<W>>,
+    {
+        self.graph.add_edges(edges)
+    }
+    fn remove_node(&mut self, n: Node) {
+        self.graph.remove_node(n);
+        self.data.remove(&n);
+    }
+    fn clear(&mut self) {
+        self.graph.clear();
+        self.data.clear();
+    }
+    fn compact(&mut self) -> Map<Node, Node> {
+        let mapping = self.graph.compact();
+        let data = core::mem::take(&mut self.data);
+        self.data = data.into_iter().map(|(n, d)| (mapping[&n], d)).collect();
+        mapping
+    }
+    fn remove_edge(&mut self, a: Node, b: Node) -> Option<W> {
+        self.graph.remove_edge(a, b)
+    }
+    fn reverse(&mut self) {
+        self.graph.reverse()
+    }
+}
+
+/// Grafo cujas arestas podem carregar, além do peso, um dado do tipo `E` (capacidade e custo,
+/// rótulos, timestamps, etc).
+///
+/// Assim como [`NodeData`], envolve qualquer implementação de [`GraphMut`] e mantém os dados
+/// sincronizados com o grafo: remover uma aresta, ou um nó em que ela incide, também remove o seu
+/// dado. Os dois invólucros podem ser combinados, por exemplo `NodeData<N, EdgeData<E>>`.
+#[derive(Debug, Clone)]
+pub struct EdgeData<E, G = GraphAdj> {
+    graph: G,
+    data: Map<(Node, Node), E>,
+}
+
+impl<E, G: Default> Default for EdgeData<E, G> {
+    fn default() -> Self {
+        Self::new(G::default())
+    }
+}
+
+impl<E, G> EdgeData<E, G> {
+    /// Envolve o grafo `graph`. As arestas já existentes começam sem dados.
+    pub fn new(graph: G) -> Self {
+        Self {
+            graph,
+            data: Map::new(),
+        }
+    }
+    /// Retorna uma referência ao grafo envolvido.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+    /// Descarta os dados das arestas e retorna o grafo envolvido.
+    pub fn into_graph(self) -> G {
+        self.graph
+    }
+}
+
+// Os métodos abaixo são genéricos sobre o tipo de peso `W` do grafo envolvido. Na prática, `W` é
+// inferido automaticamente, já que cada grafo implementa `GraphRef` para um único tipo de peso.
+impl<E, G> EdgeData<E, G> {
+    /// Retorna a chave usada para armazenar o dado da aresta entre `a` e `b`.
+    ///
+    /// Em grafos não direcionados, `(a, b)` e `(b, a)` são a mesma aresta, então ordenamos o par.
+    fn key<W: Num>(&self, a: Node, b: Node) -> (Node, Node)
+    where
+        G: GraphRef<W>,
+    {
+        if self.graph.is_directed() || a <= b {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+    /// Adiciona uma aresta entre `a` e `b` carregando o dado `data`.
+    ///
+    /// Assim como em `add_edge`, caso a aresta já exista, seu peso e seu dado são substituídos.
+    pub fn add_edge_with<W: Num>(&mut self, a: Node, b: Node, weight: W, data: E)
+    where
+        G: GraphMut<W>,
+    {
+        self.graph.add_edge(a, b, weight);
+        let key = self.key(a, b);
+        self.data.insert(key, data);
+    }
+    /// Retorna o dado da aresta entre `a` e `b`, caso exista.
+    pub fn edge_data<W: Num>(&self, a: Node, b: Node) -> Option<&E>
+    where
+        G: GraphRef<W>,
+    {
+        self.data.get(&self.key(a, b))
+    }
+    /// Retorna uma referência mutável ao dado da aresta entre `a` e `b`, caso exista.
+    pub fn edge_data_mut<W: Num>(&mut self, a: Node, b: Node) -> Option<&mut E>
+    where
+        G: GraphRef<W>,
+    {
+        let key = self.key(a, b);
+        self.data.get_mut(&key)
+    }
+    /// Atribui o dado `data` à aresta entre `a` e `b`, retornando o dado anterior, caso houvesse
+    /// um.
+    ///
+    /// Causa um `panic` caso a aresta não exista.
+    pub fn set_edge_data<W: Num>(&mut self, a: Node, b: Node, data: E) -> Option<E>
+    where
+        G: GraphRef<W>,
+    {
+        if !self.graph.has_edge(a, b) {
+            panic!("Tried to set data of inexistent edge {a} {b}");
+        }
+        let key = self.key(a, b);
+        self.data.insert(key, data)
+    }
+}
+
+impl<E, W: Num, G: GraphRef<W>> GraphRef<W> for EdgeData<E, G> {
+    delegate_graph_queries!();
+}
+
+impl<E, W: Num, G: GraphMut<W>> GraphMut<W> for EdgeData<E, G> {
+    fn add_node(&mut self) -> Node {
+        self.graph.add_node()
+    }
+    fn add_nodes(&mut self, count: usize) -> Range<Node> {
+        self.graph.add_nodes(count)
+    }
+    fn add_edge(&mut self, a: Node, b: Node, weight: W) {
+        self.graph.add_edge(a, b, weight)
+    }
+    fn add_edges<I>(&mut self, edges: I)
+    where
+        I: IntoIterator<Item = Edge<W>>,
+    {
+        self.graph.add_edges(edges)
+    }
+    fn remove_node(&mut self, n: Node) {
+        self.graph.remove_node(n);
+        self.data.retain(|&(a, b), _| a != n && b != n);
+    }
+    fn clear(&mut self) {
+        self.graph.clear();
+        self.data.clear();
+    }
+    fn compact(&mut self) -> Map<Node, Node> {
+        let mapping = self.graph.compact();
+        // A ordem dos nós é mantida, então as chaves de grafos não direcionados continuam
+        // ordenadas.
+        let data = core::mem::take(&mut self.data);
+        self.data = data
+            .into_iter()
+            .map(|((a, b), e)| ((mapping[&a], mapping[&b]), e))
+            .collect();
+        mapping
+    }
+    fn remove_edge(&mut self, a: Node, b: Node) -> Option<W> {
+        let key = self.key(a, b);
+        self.data.remove(&key);
+        self.graph.remove_edge(a, b)
+    }
+    fn reverse(&mut self) {
+        if !self.graph.is_directed() {
+            return;
+        }
+        self.graph.reverse();
+        // Os dados acompanham as arestas, que agora vão de `b` para `a`.
+        let data = core::mem::take(&mut self.data);
+        self.data = data.into_iter().map(|((a, b), e)| ((b, a), e)).collect();
+    }
+}
@@ -0,0 +1,67 @@
+use crate::prelude::*;
+use crate::{Edge, GraphRef, Map, Node, Num};
+
+/// Diferenças entre dois grafos, calculadas por [`diff`].
+///
+/// Os nós são comparados pelos seus identificadores: a aresta `a -> b` de um grafo corresponde à
+/// aresta `a -> b` do outro. As listas estão em ordem crescente de `(a, b)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphDiff<W: Num> {
+    /// Quantidade de nós do primeiro e do segundo grafo.
+    pub node_counts: (usize, usize),
+    /// Arestas que só existem no segundo grafo.
+    pub added: Vec<Edge<W>>,
+    /// Arestas que só existem no primeiro grafo.
+    pub removed: Vec<Edge<W>>,
+    /// Arestas que existem nos dois, mas com pesos diferentes: `(a, b, antes, depois)`.
+    pub reweighted: Vec<(Node, Node, W, W)>,
+}
+
+impl<W: Num> GraphDiff<W> {
+    /// Indica se os dois grafos têm as mesmas arestas, com os mesmos pesos, e a mesma quantidade
+    /// de nós.
+    pub fn is_empty(&self) -> bool {
+        self.node_counts.0 == self.node_counts.1
+            && self.added.is_empty()
+            && self.removed.is_empty()
+            && self.reweighted.is_empty()
+    }
+}
+
+/// Compara as arestas de `before` com as de `after`.
+///
+/// Em um grafo não direcionado, cada aresta aparece uma única vez, como `(a, b)` com `a <= b`.
+/// Caso um grafo seja direcionado e o outro não, a aresta não direcionada `{a, b}` equivale ao par
+/// de arestas `a -> b` e `b -> a`.
+pub fn diff<W: Num>(before: &dyn GraphRef<W>, after: &dyn GraphRef<W>) -> GraphDiff<W> {
+    let directed = before.is_directed() || after.is_directed();
+    // Com algum grafo direcionado, as duas direções de cada aresta não direcionada são mantidas.
+    let edges = |graph: &dyn GraphRef<W>| -> Map<(Node, Node), W> {
+        graph
+            .edges_iter()
+            .filter(|&(a, b, _)| directed || a <= b)
+            .map(|(a, b, w)| ((a, b), w))
+            .collect()
+    };
+    let (old, new) = (edges(before), edges(after));
+
+    let mut result = GraphDiff {
+        node_counts: (before.node_count(), after.node_count()),
+        added: Vec::new(),
+        removed: Vec::new(),
+        reweighted: Vec::new(),
+    };
+    for (&(a, b), &w) in &old {
+        match new.get(&(a, b)) {
+            None => result.removed.push((a, b, w)),
+            Some(&v) if v != w => result.reweighted.push((a, b, w, v)),
+            Some(_) => {}
+        }
+    }
+    for (&(a, b), &w) in &new {
+        if !old.contains_key(&(a, b)) {
+            result.added.push((a, b, w));
+        }
+    }
+    result
+}
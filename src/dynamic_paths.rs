@@ -0,0 +1,226 @@
+use alloc::collections::BinaryHeap;
+use core::ops::Range;
+
+use crate::paths::Entry;
+use crate::prelude::*;
+use crate::{dijkstra, GraphAdj, GraphMut, GraphRef, Map, Node, Num, PropertyMap, ShortestPaths};
+use crate::{Set, Weight};
+
+/// Grafo que mantém os caminhos mínimos a partir de uma origem fixa atualizados a cada
+/// modificação.
+///
+/// Em uma simulação que muda poucos pesos por vez, executar [`dijkstra`] de novo a cada mudança
+/// refaz quase todo o trabalho. Aqui, cada modificação corrige apenas os nós afetados:
+///
+/// - Quando uma aresta fica mais leve ou é adicionada, os nós que passam a ter um caminho mais
+///   curto por ela são atualizados por uma busca de Dijkstra que começa na aresta e só continua
+///   enquanto as distâncias diminuem.
+/// - Quando uma aresta da árvore de caminhos mínimos fica mais pesada ou é removida, as distâncias
+///   da subárvore abaixo dela são descartadas. Cada nó da subárvore recebe a melhor distância por
+///   um vizinho de fora dela, e uma busca de Dijkstra a partir deles corrige o restante. Arestas
+///   fora da árvore podem ficar mais pesadas sem custo algum.
+///
+/// O custo de cada modificação depende apenas dos nós cuja distância muda, e não do grafo inteiro.
+/// Em grafos direcionados, a correção da subárvore usa as arestas de entrada dos seus nós
+/// (veja [`GraphRef::in_edges`]), que em [`GraphAdj`] custam O(V log d) por nó.
+///
+/// Envolve qualquer implementação de [`GraphMut`] (por padrão, [`GraphAdj`]) e também implementa
+/// [`GraphMut`], assim como [`crate::ObservedGraph`]. `reverse` e `compact` recalculam tudo. Assim
+/// como em Dijkstra, os pesos não podem ser negativos.
+#[derive(Debug, Clone)]
+pub struct DynamicShortestPaths<G = GraphAdj, W = Weight> {
+    graph: G,
+    paths: ShortestPaths<W>,
+}
+
+impl<W: Num, G: GraphMut<W>> DynamicShortestPaths<G, W> {
+    /// Envolve o grafo `graph`, calculando os caminhos mínimos a partir de `source`.
+    ///
+    /// Causa um `panic` caso `source` não exista.
+    pub fn new(graph: G, source: Node) -> Self {
+        let paths = dijkstra(&graph, source);
+        Self { graph, paths }
+    }
+    /// Retorna uma referência ao grafo envolvido.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+    /// Retorna o grafo envolvido.
+    pub fn into_graph(self) -> G {
+        self.graph
+    }
+    /// Retorna os caminhos mínimos a partir da origem, sempre de acordo com o grafo atual.
+    ///
+    /// Caso a origem seja removida, nenhum nó volta a ser alcançável, mesmo que outro nó receba o
+    /// mesmo identificador.
+    pub fn paths(&self) -> &ShortestPaths<W> {
+        &self.paths
+    }
+
+    /// Sentidos em que a aresta `a - b` é armazenada.
+    fn directions(&self, a: Node, b: Node) -> Vec<(Node, Node)> {
+        match self.graph.is_directed() || a == b {
+            true => vec![(a, b)],
+            false => vec![(a, b), (b, a)],
+        }
+    }
+    /// Adiciona a `subtree` os nós da árvore de caminhos mínimos abaixo de `root`, incluindo-o.
+    fn collect_subtree(&self, root: Node, subtree: &mut Set<Node>) {
+        if !self.paths.distance.contains(root) || !subtree.insert(root) {
+            return;
+        }
+        let mut stack = vec![root];
+        while let Some(n) = stack.pop() {
+            self.graph.for_each_neighbor(n, &mut |child| {
+                if self.paths.parent.get(child) == Some(&n) && subtree.insert(child) {
+                    stack.push(child);
+                }
+            });
+        }
+    }
+    /// Usa a aresta `a -> b` caso ela melhore a distância de `b`, colocando `b` na fila.
+    fn offer(&mut self, a: Node, b: Node, weight: W, queue: &mut BinaryHeap<Entry<W>>) {
+        let Some(&distance) = self.paths.distance.get(a) else {
+            return;
+        };
+        let candidate = distance + weight;
+        if (self.paths.distance.get(b)).is_none_or(|&current| candidate < current) {
+            self.paths.distance.insert(b, candidate);
+            self.paths.parent.insert(b, a);
+            queue.push(Entry(candidate, b));
+        }
+    }
+    /// Aplica `change` ao grafo e corrige os caminhos.
+    ///
+    /// As distâncias das subárvores abaixo de `roots`, que podem aumentar, são descartadas antes da
+    /// modificação, enquanto a árvore ainda corresponde ao grafo. Depois dela, os nós descartados
+    /// recebem distâncias pelas suas arestas de entrada, as arestas `changed` são usadas caso
+    /// encurtem algum caminho, e as distâncias novas são propagadas como em Dijkstra.
+    fn update<R>(
+        &mut self,
+        roots: &[Node],
+        changed: &[(Node, Node)],
+        change: impl FnOnce(&mut G) -> R,
+    ) -> R {
+        let mut affected = Set::new();
+        for &root in roots {
+            self.collect_subtree(root, &mut affected);
+        }
+        for &n in &affected {
+            self.paths.distance.remove(n);
+            self.paths.parent.remove(n);
+        }
+        let result = change(&mut self.graph);
+
+        let mut queue = BinaryHeap::new();
+        for &n in &affected {
+            if !self.graph.has_node(n) {
+                continue;
+            }
+            for (a, _, weight) in self.graph.in_edges(n) {
+                self.offer(a, n, weight, &mut queue);
+            }
+        }
+        for &(a, b) in changed {
+            if let Some(weight) = self.graph.get_edge_weight(a, b) {
+                self.offer(a, b, weight, &mut queue);
+            }
+        }
+        // Um nó pode entrar na fila mais de uma vez, caso a sua distância melhore de novo.
+        while let Some(Entry(distance, a)) = queue.pop() {
+            if self.paths.distance[a] < distance {
+                continue;
+            }
+            for (_, b, weight) in self.graph.out_edges(a) {
+                self.offer(a, b, weight, &mut queue);
+            }
+        }
+        result
+    }
+    /// Recalcula todos os caminhos, para as modificações que mudam o grafo inteiro. Enquanto a
+    /// origem existe, ela é o único nó sem anterior, com distância zero.
+    fn recompute(&mut self) {
+        if self.paths.distance.contains(self.paths.source) {
+            self.paths = dijkstra(&self.graph, self.paths.source);
+        }
+    }
+}
+
+impl<W: Num, G: GraphRef<W>> GraphRef<W> for DynamicShortestPaths<G, W> {
+    delegate_graph_queries!();
+}
+
+impl<W: Num, G: GraphMut<W>> GraphMut<W> for DynamicShortestPaths<G, W> {
+    // Nós novos não têm arestas, então não mudam os caminhos.
+    fn add_node(&mut self) -> Node {
+        self.graph.add_node()
+    }
+    fn add_nodes(&mut self, count: usize) -> Range<Node> {
+        self.graph.add_nodes(count)
+    }
+    fn add_edge(&mut self, a: Node, b: Node, weight: W) {
+        let changed = self.directions(a, b);
+        // Caso a aresta fique mais pesada, os caminhos que passam por ela podem ficar mais longos.
+        let heavier = self
+            .graph
+            .get_edge_weight(a, b)
+            .is_some_and(|previous| weight > previous);
+        let roots: Vec<_> = changed
+            .iter()
+            .filter(|&&(x, y)| heavier && self.paths.parent.get(y) == Some(&x))
+            .map(|&(_, y)| y)
+            .collect();
+        self.update(&roots, &changed, |graph| graph.add_edge(a, b, weight));
+    }
+    fn remove_node(&mut self, n: Node) {
+        self.update(&[n], &[], |graph| graph.remove_node(n));
+    }
+    fn remove_edge(&mut self, a: Node, b: Node) -> Option<W> {
+        let roots: Vec<_> = (self.directions(a, b).into_iter())
+            .filter(|&(x, y)| self.paths.parent.get(y) == Some(&x))
+            .map(|(_, y)| y)
+            .collect();
+        self.update(&roots, &[], |graph| graph.remove_edge(a, b))
+    }
+    fn reverse(&mut self) {
+        if self.graph.is_directed() {
+            self.graph.reverse();
+            self.recompute();
+        }
+    }
+    fn compact(&mut self) -> Map<Node, Node> {
+        // As distâncias usam os identificadores antigos, então é preciso saber antes da compactação
+        // se a origem ainda existe. Uma origem removida não está no mapeamento, e a estrutura
+        // continua vazia.
+        let alive = self.paths.distance.contains(self.paths.source);
+        let mapping = self.graph.compact();
+        if let Some(&source) = mapping.get(&self.paths.source).filter(|_| alive) {
+            self.paths = dijkstra(&self.graph, source);
+        }
+        mapping
+    }
+    fn clear(&mut self) {
+        self.graph.clear();
+        self.paths.distance = PropertyMap::new();
+        self.paths.parent = PropertyMap::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compact_renames_the_source() {
+        let mut graph = GraphAdj::directed();
+        graph.add_nodes(4);
+        graph.add_edge(3, 2, 1);
+        let mut dynamic = DynamicShortestPaths::new(graph, 3);
+        dynamic.remove_node(0);
+        dynamic.remove_node(1);
+        let mapping = dynamic.compact();
+        assert_eq!(mapping[&3], 1);
+        assert_eq!(dynamic.paths().distance(0), Some(1));
+        assert_eq!(dynamic.paths().distance(1), Some(0));
+    }
+}
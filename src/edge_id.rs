@@ -0,0 +1,200 @@
+use core::ops::Range;
+
+use crate::prelude::*;
+use crate::{Edge, GraphAdj, GraphMut, GraphRef, Map, Node, Num};
+
+/// Identificador estável de uma aresta de [`EdgeIds`].
+///
+/// Identificadores nunca são reutilizados: depois que uma aresta é removida, o seu `EdgeId` deixa
+/// de ser válido para sempre, mesmo que uma nova aresta seja criada entre os mesmos nós.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EdgeId(usize);
+
+impl EdgeId {
+    /// Retorna o número do identificador. Arestas criadas depois têm números maiores.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Grafo cujas arestas são identificadas por um [`EdgeId`], retornado por
+/// [`EdgeIds::add_edge`].
+///
+/// Com o `EdgeId` em mãos, é possível consultar, atualizar ou remover aquela aresta
+/// específica, sem precisar guardar os seus nós. Isso prepara o terreno para multigrafos, em que
+/// o par de nós não basta para identificar uma aresta.
+///
+/// Assim como [`crate::GenerationalGraph`], as modificações são feitas pelos métodos próprios do
+/// `EdgeIds`, que mantêm os identificadores sincronizados, e as consultas pelos métodos de
+/// [`GraphRef`].
+#[derive(Debug, Clone)]
+pub struct EdgeIds<G = GraphAdj> {
+    graph: G,
+    /// Identificador de cada aresta, indexado pela chave retornada por `key`.
+    ids: Map<(Node, Node), EdgeId>,
+    /// Chave de cada aresta, indexada pelo seu identificador.
+    edges: Map<EdgeId, (Node, Node)>,
+    next_id: usize,
+}
+
+impl<G: Default> Default for EdgeIds<G> {
+    fn default() -> Self {
+        Self {
+            graph: G::default(),
+            ids: Map::new(),
+            edges: Map::new(),
+            next_id: 0,
+        }
+    }
+}
+
+impl<G> EdgeIds<G> {
+    /// Retorna uma referência ao grafo envolvido.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+    /// Descarta os identificadores e retorna o grafo envolvido.
+    pub fn into_graph(self) -> G {
+        self.graph
+    }
+}
+
+// Assim como em `EdgeData`, os métodos são genéricos sobre o tipo de peso `W`, que é inferido a
+// partir do grafo envolvido.
+impl<G> EdgeIds<G> {
+    /// Envolve o grafo `graph`, atribuindo um identificador a cada aresta já existente.
+    pub fn new<W: Num>(graph: G) -> Self
+    where
+        G: GraphRef<W>,
+    {
+        let mut this = Self {
+            graph,
+            ids: Map::new(),
+            edges: Map::new(),
+            next_id: 0,
+        };
+        let keys: Vec<_> = this
+            .graph
+            .edges_iter()
+            .map(|(a, b, _)| this.key(a, b))
+            .collect();
+        for key in keys {
+            this.assign(key);
+        }
+        this
+    }
+    /// Retorna o identificador da aresta entre `a` e `b`, caso ela exista.
+    pub fn edge_id<W: Num>(&self, a: Node, b: Node) -> Option<EdgeId>
+    where
+        G: GraphRef<W>,
+    {
+        self.ids.get(&self.key(a, b)).copied()
+    }
+    /// Retorna os nós e o peso da aresta `id`, ou `None` caso ela tenha sido removida.
+    pub fn edge<W: Num>(&self, id: EdgeId) -> Option<Edge<W>>
+    where
+        G: GraphRef<W>,
+    {
+        let &(a, b) = self.edges.get(&id)?;
+        let weight = self.graph.get_edge_weight(a, b)?;
+        Some((a, b, weight))
+    }
+    /// Adiciona um nó. Veja [`GraphMut::add_node`].
+    pub fn add_node<W: Num>(&mut self) -> Node
+    where
+        G: GraphMut<W>,
+    {
+        self.graph.add_node()
+    }
+    /// Adiciona `count` nós. Veja [`GraphMut::add_nodes`].
+    pub fn add_nodes<W: Num>(&mut self, count: usize) -> Range<Node>
+    where
+        G: GraphMut<W>,
+    {
+        self.graph.add_nodes(count)
+    }
+    /// Adiciona uma aresta entre `a` e `b`, retornando o seu identificador.
+    ///
+    /// Caso a aresta já exista, seu peso é substituído e o identificador existente é retornado.
+    pub fn add_edge<W: Num>(&mut self, a: Node, b: Node, weight: W) -> EdgeId
+    where
+        G: GraphMut<W>,
+    {
+        self.graph.add_edge(a, b, weight);
+        let key = self.key(a, b);
+        match self.ids.get(&key) {
+            Some(&id) => id,
+            None => self.assign(key),
+        }
+    }
+    /// Substitui o peso da aresta `id`, retornando o peso anterior, ou `None` caso a aresta tenha
+    /// sido removida.
+    pub fn update_weight<W: Num>(&mut self, id: EdgeId, weight: W) -> Option<W>
+    where
+        G: GraphMut<W>,
+    {
+        let (a, b, old) = self.edge(id)?;
+        self.graph.add_edge(a, b, weight);
+        Some(old)
+    }
+    /// Remove a aresta `id`, retornando o seu peso, ou `None` caso ela já tenha sido removida.
+    pub fn remove<W: Num>(&mut self, id: EdgeId) -> Option<W>
+    where
+        G: GraphMut<W>,
+    {
+        let (a, b) = self.edges.remove(&id)?;
+        self.ids.remove(&(a, b));
+        self.graph.remove_edge(a, b)
+    }
+    /// Remove a aresta entre `a` e `b`, retornando o seu peso caso ela existisse.
+    pub fn remove_edge<W: Num>(&mut self, a: Node, b: Node) -> Option<W>
+    where
+        G: GraphMut<W>,
+    {
+        let id = self.edge_id(a, b)?;
+        self.remove(id)
+    }
+    /// Remove o nó `n` e as arestas que incidem nele, invalidando os seus identificadores.
+    pub fn remove_node<W: Num>(&mut self, n: Node)
+    where
+        G: GraphMut<W>,
+    {
+        self.graph.remove_node(n);
+        self.ids.retain(|&(a, b), id| {
+            let keep = a != n && b != n;
+            if !keep {
+                self.edges.remove(id);
+            }
+            keep
+        });
+    }
+
+    /// Retorna a chave da aresta entre `a` e `b`. Assim como em `EdgeData`, o par é ordenado em
+    /// grafos não direcionados.
+    fn key<W: Num>(&self, a: Node, b: Node) -> (Node, Node)
+    where
+        G: GraphRef<W>,
+    {
+        if self.graph.is_directed() || a <= b {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+    /// Cria um novo identificador para a aresta `key`. Em grafos não direcionados, cada aresta
+    /// aparece duas vezes em `edges_iter`, então chaves repetidas são ignoradas.
+    fn assign(&mut self, key: (Node, Node)) -> EdgeId {
+        if let Some(&id) = self.ids.get(&key) {
+            return id;
+        }
+        let id = EdgeId(self.next_id);
+        self.next_id += 1;
+        self.ids.insert(key, id);
+        self.edges.insert(id, key);
+        id
+    }
+}
+
+impl<W: Num, G: GraphRef<W>> GraphRef<W> for EdgeIds<G> {
+    delegate_graph_queries!();
+}
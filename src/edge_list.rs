@@ -0,0 +1,182 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::prelude::*;
+use crate::{sort_last_wins_in, Edge, Num};
+
+/// Quantidade de arestas guardadas dentro da própria lista, sem alocar memória na heap.
+const INLINE: usize = 4;
+
+/// Lista de arestas de um nó de [`crate::GraphAdj`], que pode ser usada como um `&[Edge<W>]`.
+///
+/// A maioria dos nós de grafos esparsos tem poucos vizinhos, e um `Vec` para cada um deles
+/// significaria uma alocação por nó, com as arestas espalhadas pela memória. Aqui, as primeiras
+/// [`INLINE`] arestas ficam em um vetor de tamanho fixo dentro da própria lista (que fica dentro
+/// da `BTreeMap`), e só listas maiores passam a usar um `Vec`. Essa técnica é conhecida como
+/// _small vector optimization_.
+#[derive(Debug, Clone)]
+pub(crate) enum EdgeList<W> {
+    /// As `len` primeiras posições de `edges` são as arestas; as demais não são usadas.
+    Inline {
+        len: u8,
+        edges: [Edge<W>; INLINE],
+    },
+    Heap(Vec<Edge<W>>),
+}
+
+impl<W: Num> Default for EdgeList<W> {
+    fn default() -> Self {
+        // As posições não usadas precisam de algum valor, que nunca é lido.
+        Self::Inline {
+            len: 0,
+            edges: [(0, 0, W::ONE); INLINE],
+        }
+    }
+}
+
+impl<W: Num> EdgeList<W> {
+    /// Retorna o `Vec` da lista, movendo as arestas para a heap caso ainda estejam dentro dela.
+    ///
+    /// A capacidade reservada é o dobro de [`INLINE`], para que as próximas inserções não
+    /// precisem realocar o vetor logo em seguida.
+    fn spill(&mut self) -> &mut Vec<Edge<W>> {
+        if let Self::Inline { len, edges } = self {
+            let mut vec = Vec::with_capacity(2 * INLINE);
+            vec.extend_from_slice(&edges[..*len as usize]);
+            *self = Self::Heap(vec);
+        }
+        match self {
+            Self::Heap(vec) => vec,
+            Self::Inline { .. } => unreachable!(),
+        }
+    }
+    pub(crate) fn push(&mut self, edge: Edge<W>) {
+        let len = self.len();
+        self.insert(len, edge);
+    }
+    /// Insere `edge` na posição `pos`, deslocando as seguintes, como `Vec::insert`.
+    pub(crate) fn insert(&mut self, pos: usize, edge: Edge<W>) {
+        match self {
+            Self::Inline { len, edges } if (*len as usize) < INLINE => {
+                let end = *len as usize;
+                assert!(pos <= end, "insertion index {pos} is out of bounds");
+                edges.copy_within(pos..end, pos + 1);
+                edges[pos] = edge;
+                *len += 1;
+            }
+            _ => self.spill().insert(pos, edge),
+        }
+    }
+    /// Remove e retorna a aresta na posição `pos`, deslocando as seguintes, como `Vec::remove`.
+    pub(crate) fn remove(&mut self, pos: usize) -> Edge<W> {
+        match self {
+            Self::Inline { len, edges } => {
+                let end = *len as usize;
+                assert!(pos < end, "removal index {pos} is out of bounds");
+                let edge = edges[pos];
+                edges.copy_within(pos + 1..end, pos);
+                *len -= 1;
+                edge
+            }
+            Self::Heap(vec) => vec.remove(pos),
+        }
+    }
+    /// Mantém apenas as arestas para as quais `keep` retorna `true`, na mesma ordem.
+    pub(crate) fn retain(&mut self, mut keep: impl FnMut(&Edge<W>) -> bool) {
+        match self {
+            Self::Inline { .. } => {
+                let mut kept = 0;
+                for i in 0..self.len() {
+                    let edge = self[i];
+                    if keep(&edge) {
+                        self[kept] = edge;
+                        kept += 1;
+                    }
+                }
+                self.truncate(kept);
+            }
+            Self::Heap(vec) => vec.retain(keep),
+        }
+    }
+    pub(crate) fn extend_from_slice(&mut self, new: &[Edge<W>]) {
+        match self {
+            Self::Inline { len, edges } if *len as usize + new.len() <= INLINE => {
+                let end = *len as usize;
+                edges[end..end + new.len()].copy_from_slice(new);
+                *len += new.len() as u8;
+            }
+            _ => self.spill().extend_from_slice(new),
+        }
+    }
+    /// Mantém apenas as `new_len` primeiras arestas.
+    pub(crate) fn truncate(&mut self, new_len: usize) {
+        match self {
+            Self::Inline { len, .. } if new_len < *len as usize => *len = new_len as u8,
+            Self::Inline { .. } => {}
+            Self::Heap(vec) => vec.truncate(new_len),
+        }
+    }
+    /// Ordena a lista como [`crate::sort_last_wins`], sem alocar memória.
+    pub(crate) fn sort_last_wins(&mut self) {
+        let len = sort_last_wins_in(self);
+        self.truncate(len);
+    }
+    /// Retorna quantos bytes a lista ocupa na heap, que é zero enquanto as arestas couberem
+    /// dentro dela.
+    pub(crate) fn heap_bytes(&self) -> usize {
+        match self {
+            Self::Inline { .. } => 0,
+            Self::Heap(vec) => crate::vec_bytes(vec),
+        }
+    }
+}
+
+impl<W: Num> From<Vec<Edge<W>>> for EdgeList<W> {
+    fn from(vec: Vec<Edge<W>>) -> Self {
+        if vec.len() > INLINE {
+            return Self::Heap(vec);
+        }
+        let mut list = Self::default();
+        list.extend_from_slice(&vec);
+        list
+    }
+}
+
+impl<W: Num> FromIterator<Edge<W>> for EdgeList<W> {
+    fn from_iter<I: IntoIterator<Item = Edge<W>>>(iter: I) -> Self {
+        let mut list = Self::default();
+        for edge in iter {
+            list.push(edge);
+        }
+        list
+    }
+}
+
+/// Permite percorrer a lista com `for` e usá-la com `flatten`, como um `&Vec`.
+impl<'a, W> IntoIterator for &'a EdgeList<W> {
+    type Item = &'a Edge<W>;
+    type IntoIter = core::slice::Iter<'a, Edge<W>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<W> Deref for EdgeList<W> {
+    type Target = [Edge<W>];
+
+    fn deref(&self) -> &[Edge<W>] {
+        match self {
+            Self::Inline { len, edges } => &edges[..*len as usize],
+            Self::Heap(vec) => vec,
+        }
+    }
+}
+
+impl<W> DerefMut for EdgeList<W> {
+    fn deref_mut(&mut self) -> &mut [Edge<W>] {
+        match self {
+            Self::Inline { len, edges } => &mut edges[..*len as usize],
+            Self::Heap(vec) => vec,
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use core::fmt;
+
+use crate::prelude::*;
+use crate::Node;
+
+/// Erros encontrados ao verificar um grafo com [`crate::GraphRef::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    /// A aresta `a -> b` incide em um nó que não existe.
+    DanglingEdge { a: Node, b: Node },
+    /// Em um grafo não direcionado, a aresta `a -> b` existe, mas `b -> a` não existe ou tem
+    /// outro peso.
+    AsymmetricEdge { a: Node, b: Node },
+    /// A aresta `a -> b` aparece mais de uma vez.
+    DuplicateEdge { a: Node, b: Node },
+    /// Existe um laço no nó `n`, mas o grafo rejeita laços.
+    RejectedSelfLoop { n: Node },
+    /// A estrutura interna do grafo está inconsistente, por exemplo um vetor com o tamanho
+    /// errado. A mensagem descreve o problema.
+    InvalidStorage(String),
+}
+
+// Implementar `Display` e `Error` permite usar `GraphError` com o operador `?` em funções que
+// retornam `Box<dyn std::error::Error>`.
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::DanglingEdge { a, b } => {
+                write!(f, "edge {a} {b} references an inexistent node")
+            }
+            GraphError::AsymmetricEdge { a, b } => {
+                write!(f, "undirected edge {a} {b} has no matching edge {b} {a}")
+            }
+            GraphError::DuplicateEdge { a, b } => {
+                write!(f, "edge {a} {b} is stored more than once")
+            }
+            GraphError::RejectedSelfLoop { n } => {
+                write!(f, "node {n} has a self-loop, but the graph rejects them")
+            }
+            GraphError::InvalidStorage(message) => write!(f, "invalid graph storage: {message}"),
+        }
+    }
+}
+
+impl core::error::Error for GraphError {}
+
+/// Erros encontrados ao ler o formato de entrada com [`crate::fill_graph`] ou
+/// [`crate::fill_graph_from_reader`].
+///
+/// As linhas começam em `1`, como nos editores de texto, e os vértices são os da entrada, também
+/// começando em `1`.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum ParseGraphError {
+    /// A entrada está vazia, ou a primeira linha não tem exatamente dois valores (`n m`).
+    MissingHeader { line: usize },
+    /// `text` não é um número inteiro positivo.
+    InvalidNumber { line: usize, text: String },
+    /// A linha de aresta tem `found` valores, em vez de dois (`a b`) ou três (`a b w`).
+    WrongFieldCount { line: usize, found: usize },
+    /// A aresta usa o vértice `0`, mas os vértices começam em `1`.
+    ZeroVertex { line: usize },
+    /// A aresta usa o vértice `node`, que não está entre os `vertex_count` vértices do grafo.
+    EndpointOutOfRange {
+        line: usize,
+        node: u32,
+        vertex_count: u32,
+    },
+    /// A aresta é um laço no vértice `node`, mas o grafo rejeita laços.
+    RejectedSelfLoop { line: usize, node: u32 },
+    /// A primeira linha promete `expected` arestas, mas a entrada só tem `found`.
+    MissingEdges { expected: usize, found: usize },
+    /// Falha ao ler a entrada.
+    Io(std::io::Error),
+}
+
+#[cfg(feature = "std")]
+impl ParseGraphError {
+    /// Retorna a linha em que o erro foi encontrado, caso ele se refira a uma linha específica.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            ParseGraphError::MissingHeader { line }
+            | ParseGraphError::InvalidNumber { line, .. }
+            | ParseGraphError::WrongFieldCount { line, .. }
+            | ParseGraphError::ZeroVertex { line }
+            | ParseGraphError::EndpointOutOfRange { line, .. }
+            | ParseGraphError::RejectedSelfLoop { line, .. } => Some(*line),
+            ParseGraphError::MissingEdges { .. } | ParseGraphError::Io(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ParseGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGraphError::MissingHeader { line } => {
+                write!(f, "line {line}: expected the vertex and edge counts")
+            }
+            ParseGraphError::InvalidNumber { line, text } => {
+                write!(f, "line {line}: invalid number {text:?}")
+            }
+            ParseGraphError::WrongFieldCount { line, found } => {
+                write!(f, "line {line}: expected 2 or 3 values, found {found}")
+            }
+            ParseGraphError::ZeroVertex { line } => {
+                write!(
+                    f,
+                    "line {line}: vertex 0 does not exist, vertices start at 1"
+                )
+            }
+            ParseGraphError::EndpointOutOfRange {
+                line,
+                node,
+                vertex_count,
+            } => write!(
+                f,
+                "line {line}: vertex {node} is out of range for {vertex_count} vertices"
+            ),
+            ParseGraphError::RejectedSelfLoop { line, node } => {
+                write!(
+                    f,
+                    "line {line}: self-loop on vertex {node}, but the graph rejects them"
+                )
+            }
+            ParseGraphError::MissingEdges { expected, found } => {
+                write!(
+                    f,
+                    "expected {expected} edges, but the input only has {found}"
+                )
+            }
+            ParseGraphError::Io(e) => write!(f, "failed to read the input: {e}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseGraphError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseGraphError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// Permite usar o operador `?` em erros de leitura dentro das funções que retornam
+// `ParseGraphError`.
+#[cfg(feature = "std")]
+impl From<std::io::Error> for ParseGraphError {
+    fn from(e: std::io::Error) -> Self {
+        ParseGraphError::Io(e)
+    }
+}
+
+/// Problema encontrado por [`crate::validate_input`].
+///
+/// Além dos erros que impedem a leitura, inclui situações que a leitura aceita, mas que costumam
+/// indicar um arquivo gerado incorretamente.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum InputProblem {
+    /// Erro que faria [`crate::fill_graph_from_reader`] falhar.
+    Error(ParseGraphError),
+    /// A aresta entre os vértices `a` e `b` já apareceu na linha `first`. Na leitura, o último
+    /// peso prevalece.
+    DuplicateEdge {
+        line: usize,
+        first: usize,
+        a: u32,
+        b: u32,
+    },
+    /// A aresta é um laço no vértice `node`.
+    SelfLoop { line: usize, node: u32 },
+    /// A linha tem dados depois das `expected` arestas prometidas pela primeira linha. Na leitura,
+    /// ela e as seguintes são ignoradas.
+    ExtraEdges { line: usize, expected: usize },
+}
+
+#[cfg(feature = "std")]
+impl InputProblem {
+    /// Retorna a linha em que o problema foi encontrado, caso ele se refira a uma linha
+    /// específica.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            InputProblem::Error(e) => e.line(),
+            InputProblem::DuplicateEdge { line, .. }
+            | InputProblem::SelfLoop { line, .. }
+            | InputProblem::ExtraEdges { line, .. } => Some(*line),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for InputProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputProblem::Error(e) => e.fmt(f),
+            InputProblem::DuplicateEdge { line, first, a, b } => write!(
+                f,
+                "line {line}: edge {a} {b} was already declared on line {first}"
+            ),
+            InputProblem::SelfLoop { line, node } => {
+                write!(f, "line {line}: self-loop on vertex {node}")
+            }
+            InputProblem::ExtraEdges { line, expected } => write!(
+                f,
+                "line {line}: data after the {expected} edges declared in the header"
+            ),
+        }
+    }
+}
@@ -0,0 +1,329 @@
+//! Interface em C, com a feature `ffi`, para chamar o crate a partir de C ou C++.
+//!
+//! O grafo é um ponteiro opaco, [`Ex1Graph`], criado por [`ex1_graph_new`] ou [`ex1_graph_read`]
+//! e liberado por [`ex1_graph_free`]. As funções que podem falhar retornam um [`Ex1Status`], e
+//! os resultados são escritos em ponteiros recebidos como parâmetros. O cabeçalho `include/ex1.h`
+//! é gerado a partir deste arquivo pelo `cbindgen` (veja o README).
+//!
+//! Um `panic` não pode atravessar uma função `extern "C"`, então os nós são verificados antes de
+//! chamar a biblioteca, e qualquer `panic` restante é capturado e vira [`Ex1Status::Panic`].
+
+use std::ffi::{c_char, CStr};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::{fill_graph_from_reader, open_input, shortest_path, GraphAdj, GraphMat, GraphMut};
+use crate::{Node, ParseGraphError, Weight};
+
+/// Representação por lista de adjacência ([`GraphAdj`]), para [`ex1_graph_new`].
+pub const EX1_ADJ: u32 = 0;
+/// Representação por matriz de adjacência ([`GraphMat`]), para [`ex1_graph_new`].
+pub const EX1_MAT: u32 = 1;
+
+/// Resultado das funções que podem falhar.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ex1Status {
+    Ok = 0,
+    /// Um dos ponteiros recebidos é nulo.
+    NullPointer,
+    /// A representação não é [`EX1_ADJ`] nem [`EX1_MAT`].
+    InvalidRepresentation,
+    /// Um dos nós não existe no grafo.
+    InvalidNode,
+    /// A aresta ou o caminho procurado não existe.
+    NotFound,
+    /// O arquivo de entrada não está no formato do exercício.
+    ParseError,
+    /// Falha ao abrir ou ler o arquivo de entrada.
+    IoError,
+    /// Um erro inesperado dentro da biblioteca.
+    Panic,
+}
+
+/// Grafo opaco, acessível apenas pelas funções deste módulo.
+pub struct Ex1Graph {
+    graph: Box<dyn GraphMut>,
+}
+
+/// Executa `f`, convertendo um `panic` em [`Ex1Status::Panic`].
+fn guard(f: impl FnOnce() -> Result<(), Ex1Status>) -> Ex1Status {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => Ex1Status::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => Ex1Status::Panic,
+    }
+}
+
+/// Converte um ponteiro recebido do C em uma referência, ou em [`Ex1Status::NullPointer`].
+///
+/// # Safety
+///
+/// Caso não seja nulo, `ptr` precisa ser válido e não pode estar sendo usado por outra referência.
+unsafe fn non_null<'a, T>(ptr: *mut T) -> Result<&'a mut T, Ex1Status> {
+    ptr.as_mut().ok_or(Ex1Status::NullPointer)
+}
+
+/// Igual a [`non_null`], mas para ponteiros `const`, que podem ser compartilhados entre várias
+/// leituras ao mesmo tempo.
+///
+/// # Safety
+///
+/// Caso não seja nulo, `ptr` precisa ser válido e não pode estar sendo modificado.
+unsafe fn non_null_ref<'a, T>(ptr: *const T) -> Result<&'a T, Ex1Status> {
+    ptr.as_ref().ok_or(Ex1Status::NullPointer)
+}
+
+fn check_nodes(graph: &Ex1Graph, nodes: &[Node]) -> Result<(), Ex1Status> {
+    match nodes.iter().all(|&n| graph.graph.has_node(n)) {
+        true => Ok(()),
+        false => Err(Ex1Status::InvalidNode),
+    }
+}
+
+fn new_graph(directed: bool, representation: u32) -> Option<Box<dyn GraphMut>> {
+    Some(match (representation, directed) {
+        (EX1_ADJ, false) => Box::new(GraphAdj::default()),
+        (EX1_ADJ, true) => Box::new(GraphAdj::directed()),
+        (EX1_MAT, false) => Box::new(GraphMat::default()),
+        (EX1_MAT, true) => Box::new(GraphMat::directed()),
+        _ => return None,
+    })
+}
+
+/// Cria um grafo vazio, com a representação [`EX1_ADJ`] ou [`EX1_MAT`]. Retorna um ponteiro
+/// nulo caso a representação seja inválida.
+#[no_mangle]
+pub extern "C" fn ex1_graph_new(directed: bool, representation: u32) -> *mut Ex1Graph {
+    match new_graph(directed, representation) {
+        Some(graph) => Box::into_raw(Box::new(Ex1Graph { graph })),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Lê um grafo de `path`, no formato de entrada do exercício, escrevendo-o em `out`.
+///
+/// # Safety
+///
+/// `path` precisa ser uma string terminada em `\0`, e `out` um ponteiro válido para escrita.
+#[no_mangle]
+pub unsafe extern "C" fn ex1_graph_read(
+    path: *const c_char,
+    directed: bool,
+    representation: u32,
+    out: *mut *mut Ex1Graph,
+) -> Ex1Status {
+    guard(|| {
+        let out = non_null(out)?;
+        if path.is_null() {
+            return Err(Ex1Status::NullPointer);
+        }
+        let path = CStr::from_ptr(path)
+            .to_str()
+            .map_err(|_| Ex1Status::IoError)?;
+        let mut graph =
+            new_graph(directed, representation).ok_or(Ex1Status::InvalidRepresentation)?;
+        let reader = open_input(path).map_err(|_| Ex1Status::IoError)?;
+        fill_graph_from_reader(reader, graph.as_mut()).map_err(|e| match e {
+            ParseGraphError::Io(_) => Ex1Status::IoError,
+            _ => Ex1Status::ParseError,
+        })?;
+        *out = Box::into_raw(Box::new(Ex1Graph { graph }));
+        Ok(())
+    })
+}
+
+/// Libera um grafo criado por [`ex1_graph_new`] ou [`ex1_graph_read`]. Não faz nada com um
+/// ponteiro nulo.
+///
+/// # Safety
+///
+/// `graph` precisa ter sido criado por este módulo, e não pode ser usado depois.
+#[no_mangle]
+pub unsafe extern "C" fn ex1_graph_free(graph: *mut Ex1Graph) {
+    if !graph.is_null() {
+        drop(Box::from_raw(graph));
+    }
+}
+
+/// Cria um nó, escrevendo o seu identificador em `out`.
+///
+/// # Safety
+///
+/// `graph` e `out` precisam ser ponteiros válidos.
+#[no_mangle]
+pub unsafe extern "C" fn ex1_graph_add_node(graph: *mut Ex1Graph, out: *mut Node) -> Ex1Status {
+    guard(|| {
+        let graph = non_null(graph)?;
+        *non_null(out)? = graph.graph.add_node();
+        Ok(())
+    })
+}
+
+/// Adiciona a aresta `a - b`, substituindo o peso caso ela já exista.
+///
+/// # Safety
+///
+/// `graph` precisa ser um ponteiro válido.
+#[no_mangle]
+pub unsafe extern "C" fn ex1_graph_add_edge(
+    graph: *mut Ex1Graph,
+    a: Node,
+    b: Node,
+    weight: Weight,
+) -> Ex1Status {
+    guard(|| {
+        let graph = non_null(graph)?;
+        check_nodes(graph, &[a, b])?;
+        graph.graph.add_edge(a, b, weight);
+        Ok(())
+    })
+}
+
+/// Remove a aresta `a - b`, ou retorna [`Ex1Status::NotFound`] caso ela não exista.
+///
+/// # Safety
+///
+/// `graph` precisa ser um ponteiro válido.
+#[no_mangle]
+pub unsafe extern "C" fn ex1_graph_remove_edge(
+    graph: *mut Ex1Graph,
+    a: Node,
+    b: Node,
+) -> Ex1Status {
+    guard(|| {
+        let graph = non_null(graph)?;
+        graph.graph.remove_edge(a, b).ok_or(Ex1Status::NotFound)?;
+        Ok(())
+    })
+}
+
+/// Retorna a quantidade de nós, ou zero com um ponteiro nulo.
+///
+/// # Safety
+///
+/// `graph` precisa ser nulo ou um ponteiro válido.
+#[no_mangle]
+pub unsafe extern "C" fn ex1_graph_node_count(graph: *const Ex1Graph) -> usize {
+    graph.as_ref().map_or(0, |graph| graph.graph.node_count())
+}
+
+/// Retorna a quantidade de arestas, ou zero com um ponteiro nulo.
+///
+/// # Safety
+///
+/// `graph` precisa ser nulo ou um ponteiro válido.
+#[no_mangle]
+pub unsafe extern "C" fn ex1_graph_edge_count(graph: *const Ex1Graph) -> usize {
+    graph.as_ref().map_or(0, |graph| graph.graph.edge_count())
+}
+
+/// Indica se o nó `n` existe, retornando `false` com um ponteiro nulo.
+///
+/// # Safety
+///
+/// `graph` precisa ser nulo ou um ponteiro válido.
+#[no_mangle]
+pub unsafe extern "C" fn ex1_graph_has_node(graph: *const Ex1Graph, n: Node) -> bool {
+    graph.as_ref().is_some_and(|graph| graph.graph.has_node(n))
+}
+
+/// Escreve em `out` o peso da aresta `a - b`, ou retorna [`Ex1Status::NotFound`] caso ela não
+/// exista.
+///
+/// # Safety
+///
+/// `graph` e `out` precisam ser ponteiros válidos.
+#[no_mangle]
+pub unsafe extern "C" fn ex1_graph_edge_weight(
+    graph: *const Ex1Graph,
+    a: Node,
+    b: Node,
+    out: *mut Weight,
+) -> Ex1Status {
+    guard(|| {
+        let graph = non_null_ref(graph)?;
+        let weight = graph
+            .graph
+            .get_edge_weight(a, b)
+            .ok_or(Ex1Status::NotFound)?;
+        *non_null(out)? = weight;
+        Ok(())
+    })
+}
+
+/// Escreve os vizinhos de `n` em `buffer`, que tem espaço para `capacity` nós, e a quantidade
+/// total de vizinhos em `len`.
+///
+/// Caso `len` seja maior que `capacity`, só os primeiros vizinhos foram escritos. Chamar a função
+/// com `capacity` zero (e `buffer` nulo) permite descobrir o tamanho necessário.
+///
+/// # Safety
+///
+/// `graph` e `len` precisam ser ponteiros válidos, e `buffer` precisa ter espaço para `capacity`
+/// nós, ou ser nulo caso `capacity` seja zero.
+#[no_mangle]
+pub unsafe extern "C" fn ex1_graph_neighbors(
+    graph: *const Ex1Graph,
+    n: Node,
+    buffer: *mut Node,
+    capacity: usize,
+    len: *mut usize,
+) -> Ex1Status {
+    guard(|| {
+        let graph = non_null_ref(graph)?;
+        let len = non_null(len)?;
+        check_nodes(graph, &[n])?;
+        if buffer.is_null() && capacity > 0 {
+            return Err(Ex1Status::NullPointer);
+        }
+        let mut count = 0;
+        for neighbor in graph.graph.neighbors(n) {
+            if count < capacity {
+                *buffer.add(count) = neighbor;
+            }
+            count += 1;
+        }
+        *len = count;
+        Ok(())
+    })
+}
+
+/// Escreve em `out` o peso de um caminho mínimo de `source` até `target`, ou retorna
+/// [`Ex1Status::NotFound`] caso `target` não seja alcançável.
+///
+/// # Safety
+///
+/// `graph` e `out` precisam ser ponteiros válidos.
+#[no_mangle]
+pub unsafe extern "C" fn ex1_shortest_path_length(
+    graph: *const Ex1Graph,
+    source: Node,
+    target: Node,
+    out: *mut Weight,
+) -> Ex1Status {
+    guard(|| {
+        let graph = non_null_ref(graph)?;
+        check_nodes(graph, &[source, target])?;
+        let (_, weight) =
+            shortest_path(graph.graph.as_ref(), source, target).ok_or(Ex1Status::NotFound)?;
+        *non_null(out)? = weight;
+        Ok(())
+    })
+}
+
+/// Retorna uma descrição de `status`, em uma string estática terminada em `\0`, que não deve ser
+/// liberada.
+#[no_mangle]
+pub extern "C" fn ex1_status_message(status: Ex1Status) -> *const c_char {
+    let message: &CStr = match status {
+        Ex1Status::Ok => c"success",
+        Ex1Status::NullPointer => c"a required pointer is null",
+        Ex1Status::InvalidRepresentation => c"invalid graph representation",
+        Ex1Status::InvalidNode => c"node does not exist",
+        Ex1Status::NotFound => c"edge or path not found",
+        Ex1Status::ParseError => c"input is not in the expected format",
+        Ex1Status::IoError => c"failed to read the input file",
+        Ex1Status::Panic => c"unexpected internal error",
+    };
+    message.as_ptr()
+}
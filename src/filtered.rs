@@ -0,0 +1,89 @@
+use crate::prelude::*;
+use crate::{node_ids, Edge, GraphRef, Node, Num, SelfLoops, Weight};
+
+/// Visão de um grafo que esconde os nós e arestas rejeitados por predicados, sem copiar o grafo.
+///
+/// Por exemplo, `FilteredGraph::new(&graph).with_edge_filter(|e| e.2 >= k)` se comporta como um
+/// grafo com apenas as arestas de peso maior ou igual a `k`. Esconder um nó também esconde as
+/// arestas que incidem nele.
+///
+/// Como `FilteredGraph` guarda apenas uma referência, o grafo original não pode ser modificado
+/// enquanto a visão existir. Por isso, a visão implementa apenas [`GraphRef`].
+///
+/// Em grafos não direcionados, o filtro de arestas deve tratar `(a, b, w)` e `(b, a, w)` da mesma
+/// forma, já que cada aresta aparece nos dois sentidos.
+pub struct FilteredGraph<'a, W = Weight> {
+    graph: &'a dyn GraphRef<W>,
+    node_filter: NodeFilter<'a>,
+    edge_filter: EdgeFilter<'a, W>,
+}
+
+// Os predicados são guardados em `Box`es para que qualquer closure possa ser usada sem que o seu
+// tipo faça parte do tipo de `FilteredGraph`.
+type NodeFilter<'a> = Box<dyn Fn(Node) -> bool + 'a>;
+type EdgeFilter<'a, W> = Box<dyn Fn(&Edge<W>) -> bool + 'a>;
+
+impl<'a, W: Num> FilteredGraph<'a, W> {
+    /// Cria uma visão de `graph` que, inicialmente, não esconde nada.
+    pub fn new(graph: &'a dyn GraphRef<W>) -> Self {
+        Self {
+            graph,
+            node_filter: Box::new(|_| true),
+            edge_filter: Box::new(|_| true),
+        }
+    }
+    /// Mantém apenas os nós para os quais `filter` retorna `true`.
+    pub fn with_node_filter(mut self, filter: impl Fn(Node) -> bool + 'a) -> Self {
+        self.node_filter = Box::new(filter);
+        self
+    }
+    /// Mantém apenas as arestas para as quais `filter` retorna `true`.
+    pub fn with_edge_filter(mut self, filter: impl Fn(&Edge<W>) -> bool + 'a) -> Self {
+        self.edge_filter = Box::new(filter);
+        self
+    }
+
+    /// Indica se a aresta `e` do grafo original aparece na visão.
+    fn keeps(&self, e: &Edge<W>) -> bool {
+        self.has_node(e.0) && self.has_node(e.1) && (self.edge_filter)(e)
+    }
+    fn filter(&self, edges: Vec<Edge<W>>) -> Vec<Edge<W>> {
+        edges.into_iter().filter(|e| self.keeps(e)).collect()
+    }
+}
+
+impl<W: Num> GraphRef<W> for FilteredGraph<'_, W> {
+    fn edges(&self) -> Vec<Edge<W>> {
+        self.filter(self.graph.edges())
+    }
+    fn edges_iter(&self) -> Box<dyn Iterator<Item = Edge<W>> + '_> {
+        Box::new(self.graph.edges_iter().filter(|e| self.keeps(e)))
+    }
+    fn node_count(&self) -> usize {
+        node_ids(self.graph)
+            .filter(|&n| (self.node_filter)(n))
+            .count()
+    }
+    fn has_node(&self, n: Node) -> bool {
+        self.graph.has_node(n) && (self.node_filter)(n)
+    }
+    fn is_directed(&self) -> bool {
+        self.graph.is_directed()
+    }
+    fn self_loops(&self) -> SelfLoops {
+        self.graph.self_loops()
+    }
+    fn out_edges(&self, a: Node) -> Vec<Edge<W>> {
+        self.filter(self.graph.out_edges(a))
+    }
+    fn in_edges(&self, a: Node) -> Vec<Edge<W>> {
+        self.filter(self.graph.in_edges(a))
+    }
+    fn get_node_edges(&self, a: Node) -> Vec<Edge<W>> {
+        self.filter(self.graph.get_node_edges(a))
+    }
+    fn get_edge_weight(&self, a: Node, b: Node) -> Option<W> {
+        let weight = self.graph.get_edge_weight(a, b)?;
+        self.keeps(&(a, b, weight)).then_some(weight)
+    }
+}
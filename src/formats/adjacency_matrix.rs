@@ -0,0 +1,27 @@
+use std::fmt::Write;
+
+use crate::{node_ids, GraphRef, Num};
+
+/// Gera a matriz de adjacência completa de `graph`, com uma linha de números por nó.
+///
+/// A entrada da linha `i` e coluna `j` é o peso da aresta entre o `i`-ésimo e o `j`-ésimo nó, ou
+/// `0` caso ela não exista. Os valores são separados por um espaço, então o texto pode ser colado
+/// em uma planilha ou lido com `numpy.loadtxt`. Em grafos não direcionados, a matriz é simétrica.
+///
+/// As linhas e colunas seguem a ordem dos identificadores dos nós; nós removidos não aparecem.
+/// Como só usa [`GraphRef`], funciona com qualquer implementação, inclusive [`crate::GraphAdj`].
+pub fn to_adjacency_matrix<W: Num>(graph: &dyn GraphRef<W>) -> String {
+    let nodes: Vec<_> = node_ids(graph).collect();
+    // `write!` em uma `String` nunca falha, por isso os `unwrap`s.
+    let mut out = String::new();
+    for &a in &nodes {
+        for (i, &b) in nodes.iter().enumerate() {
+            if i > 0 {
+                out.push(' ');
+            }
+            write!(out, "{}", graph.get_edge_weight(a, b).unwrap_or(W::ZERO)).unwrap();
+        }
+        out.push('\n');
+    }
+    out
+}
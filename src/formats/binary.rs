@@ -0,0 +1,197 @@
+use std::io;
+use std::path::Path;
+
+use crate::{node_ids, GraphMut, GraphRef, Node, Num};
+
+/// Identifica o início de um arquivo gerado por [`to_binary`].
+const MAGIC: &[u8; 4] = b"HMHG";
+const VERSION: u8 = 1;
+
+/// Tipos de peso que podem ser gravados por [`to_binary`].
+///
+/// Cada tipo tem um código próprio, que é gravado no arquivo. Assim, ler um arquivo com um tipo de
+/// peso diferente do usado para gravá-lo resulta em um erro, e não em pesos sem sentido.
+pub trait BinaryWeight: Num {
+    /// Código que identifica o tipo no arquivo.
+    const TAG: u8;
+    /// Quantos bytes o peso ocupa no arquivo.
+    const SIZE: usize;
+    /// Adiciona os bytes do peso ao final de `out`, em little-endian.
+    fn write_bytes(self, out: &mut Vec<u8>);
+    /// Lê o peso de `bytes`, que tem exatamente [`BinaryWeight::SIZE`] bytes.
+    fn read_bytes(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_binary_weight {
+    ($($t:ty => $tag:expr),*) => {
+        $(
+            impl BinaryWeight for $t {
+                const TAG: u8 = $tag;
+                const SIZE: usize = std::mem::size_of::<$t>();
+                fn write_bytes(self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+                fn read_bytes(bytes: &[u8]) -> Self {
+                    <$t>::from_le_bytes(bytes.try_into().unwrap())
+                }
+            }
+        )*
+    };
+}
+
+// `usize` e `isize` mudam de tamanho conforme a plataforma, e por isso o código deles inclui o
+// tamanho: um arquivo gravado com `usize` de 8 bytes não é lido como um de 4.
+impl_binary_weight!(
+    u8 => 1, u16 => 2, u32 => 3, u64 => 4, i8 => 5, i16 => 6, i32 => 7, i64 => 8,
+    f32 => 9, f64 => 10,
+    usize => 16 + std::mem::size_of::<usize>() as u8,
+    isize => 32 + std::mem::size_of::<isize>() as u8
+);
+
+/// Grava `graph` em um formato binário compacto, para ser lido de volta com [`from_binary`].
+///
+/// Ler um grafo assim é muito mais rápido que interpretar um arquivo de texto, já que não é
+/// preciso separar palavras nem converter números. O formato é:
+///
+/// - o cabeçalho `HMHG`, a versão do formato, se o grafo é direcionado e o código do tipo de peso
+///   (veja [`BinaryWeight`]), um byte cada;
+/// - a quantidade de nós (`u64`), seguida do identificador de cada nó (`u32`);
+/// - a quantidade de arestas (`u64`), seguida de cada aresta como origem, destino e peso.
+///
+/// Todos os números são little-endian. Em grafos não direcionados, cada aresta é gravada uma
+/// única vez.
+pub fn to_binary<W: BinaryWeight>(graph: &dyn GraphRef<W>) -> Vec<u8> {
+    let directed = graph.is_directed();
+    let edges: Vec<_> = graph
+        .edges()
+        .into_iter()
+        .filter(|e| directed || e.0 <= e.1)
+        .collect();
+
+    let node_count = graph.node_count();
+    let mut out = Vec::with_capacity(24 + node_count * 4 + edges.len() * (8 + W::SIZE));
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&[VERSION, directed as u8, W::TAG]);
+    out.extend_from_slice(&(node_count as u64).to_le_bytes());
+    for n in node_ids(graph) {
+        out.extend_from_slice(&n.to_le_bytes());
+    }
+    out.extend_from_slice(&(edges.len() as u64).to_le_bytes());
+    for (a, b, weight) in edges {
+        out.extend_from_slice(&a.to_le_bytes());
+        out.extend_from_slice(&b.to_le_bytes());
+        weight.write_bytes(&mut out);
+    }
+    out
+}
+
+/// Lê um grafo gravado por [`to_binary`], adicionando os seus nós e arestas a `graph`.
+///
+/// Assim como em [`crate::from_json`], cada nó gravado vira um novo nó de `graph`, e o vetor
+/// retornado guarda o identificador original de cada nó criado. As arestas seguem a direção de
+/// `graph`. Caso os bytes sejam inválidos, o erro tem o tipo [`io::ErrorKind::InvalidData`], e
+/// nada é adicionado a `graph`.
+pub fn from_binary<W: BinaryWeight>(
+    bytes: &[u8],
+    graph: &mut dyn GraphMut<W>,
+) -> io::Result<Vec<Node>> {
+    let mut reader = Reader { bytes };
+    if reader.take(4)? != MAGIC {
+        return Err(invalid("not a binary graph file"));
+    }
+    let [version, directed, tag] = reader.take(3)? else {
+        unreachable!()
+    };
+    if *version != VERSION {
+        return Err(invalid(format!("unsupported format version {version}")));
+    }
+    if *tag != W::TAG {
+        return Err(invalid("the file was saved with a different weight type"));
+    }
+    let directed = *directed != 0;
+
+    let node_count = reader.len(4)?;
+    let mut ids = Vec::with_capacity(node_count);
+    for _ in 0..node_count {
+        ids.push(reader.node()?);
+    }
+    // Como os identificadores são gravados em ordem crescente, a posição de cada um pode ser
+    // encontrada com uma busca binária.
+    if ids.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(invalid("node ids are not in increasing order"));
+    }
+    let index = |id: Node| {
+        ids.binary_search(&id)
+            .map(|i| i as Node)
+            .map_err(|_| invalid(format!("edge references an inexistent node {id}")))
+    };
+
+    let edge_count = reader.len(8 + W::SIZE)?;
+    let mut edges = Vec::with_capacity(edge_count);
+    for _ in 0..edge_count {
+        let (a, b) = (index(reader.node()?)?, index(reader.node()?)?);
+        edges.push((a, b, W::read_bytes(reader.take(W::SIZE)?)));
+    }
+    if !reader.bytes.is_empty() {
+        return Err(invalid("unexpected bytes after the end of the graph"));
+    }
+
+    let start = graph.add_nodes(ids.len()).start;
+    for (a, b, weight) in edges {
+        graph.add_edge(a + start, b + start, weight);
+        if !directed && graph.is_directed() && a != b {
+            graph.add_edge(b + start, a + start, weight);
+        }
+    }
+    Ok(ids)
+}
+
+/// Grava `graph` no arquivo `path` com [`to_binary`].
+pub fn save_binary<W: BinaryWeight>(
+    graph: &dyn GraphRef<W>,
+    path: impl AsRef<Path>,
+) -> io::Result<()> {
+    std::fs::write(path, to_binary(graph))
+}
+
+/// Lê o arquivo `path` com [`from_binary`].
+pub fn load_binary<W: BinaryWeight>(
+    path: impl AsRef<Path>,
+    graph: &mut dyn GraphMut<W>,
+) -> io::Result<Vec<Node>> {
+    from_binary(&std::fs::read(path)?, graph)
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+/// Lê os bytes do início para o fim.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> io::Result<&'a [u8]> {
+        if self.bytes.len() < count {
+            return Err(invalid("unexpected end of the file"));
+        }
+        let (taken, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Ok(taken)
+    }
+    fn node(&mut self) -> io::Result<Node> {
+        Ok(Node::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+    /// Lê a quantidade de itens de um vetor, em que cada um ocupa `item_size` bytes.
+    ///
+    /// A quantidade é comparada com o tamanho do restante do arquivo antes de ser usada, para que
+    /// um arquivo corrompido não cause uma alocação gigante.
+    fn len(&mut self, item_size: usize) -> io::Result<usize> {
+        let len = u64::from_le_bytes(self.take(8)?.try_into().unwrap());
+        match usize::try_from(len) {
+            Ok(len) if len.saturating_mul(item_size) <= self.bytes.len() => Ok(len),
+            _ => Err(invalid("unexpected end of the file")),
+        }
+    }
+}
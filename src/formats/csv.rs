@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use super::FormatError;
+use crate::{GraphMut, GraphRef, Node, Num};
+
+/// Opções de [`to_csv`] e [`read_csv`].
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    /// Caractere que separa as colunas. Planilhas em português costumam usar `;`.
+    pub delimiter: char,
+    /// Indica se a primeira linha é o cabeçalho (`source,target,weight`). Ao ler, a primeira
+    /// linha é simplesmente ignorada.
+    pub header: bool,
+    /// Ao ler, pula as linhas inválidas em vez de retornar um erro. Quantas linhas foram puladas
+    /// fica em [`CsvSummary::skipped`].
+    pub skip_malformed: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            header: true,
+            skip_malformed: false,
+        }
+    }
+}
+
+/// Resultado de [`read_csv`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CsvSummary {
+    /// Nome de cada nó criado, na ordem em que apareceu pela primeira vez.
+    pub names: Vec<String>,
+    /// Quantas linhas inválidas foram puladas. Só é diferente de `0` com
+    /// [`CsvOptions::skip_malformed`].
+    pub skipped: usize,
+}
+
+/// Gera a lista de arestas de `graph` em CSV, com uma aresta `origem,destino,peso` por linha.
+///
+/// Assim como em [`crate::print_edges`], os nós são numerados a partir de `1`. Em grafos não
+/// direcionados, cada aresta aparece uma única vez. Nós sem arestas não aparecem na lista.
+pub fn to_csv<W: Num>(graph: &dyn GraphRef<W>, options: &CsvOptions) -> String {
+    let d = options.delimiter;
+    // `write!` em uma `String` nunca falha, por isso os `unwrap`s.
+    let mut out = String::new();
+    if options.header {
+        writeln!(out, "source{d}target{d}weight").unwrap();
+    }
+    for (a, b, weight) in graph.edges() {
+        if graph.is_directed() || a <= b {
+            writeln!(out, "{}{d}{}{d}{weight}", a + 1, b + 1).unwrap();
+        }
+    }
+    out
+}
+
+/// Lê uma lista de arestas em CSV, adicionando os seus nós e arestas a `graph`.
+///
+/// Cada linha tem a origem, o destino e, opcionalmente, o peso da aresta; sem peso, ele vale `1`.
+/// Linhas vazias são ignoradas. Campos podem estar entre aspas, como as planilhas escrevem textos
+/// que contêm o separador, e espaços em volta dos campos são removidos.
+///
+/// Assim como em [`crate::read_dot`], os nós são identificados por nomes: cada nome vira um novo nó
+/// de `graph`, na ordem em que aparece pela primeira vez. As arestas são adicionadas com
+/// `add_edge`, então seguem a direção de `graph`. Nada é adicionado a `graph` caso o texto tenha
+/// algum erro.
+pub fn read_csv<W: Num + FromStr>(
+    input: &str,
+    graph: &mut dyn GraphMut<W>,
+    options: &CsvOptions,
+) -> Result<CsvSummary, FormatError> {
+    let mut summary = CsvSummary::default();
+    let mut nodes: HashMap<String, Node> = HashMap::new();
+    let mut edges = Vec::new();
+    let skip = usize::from(options.header);
+    for (i, text) in input.lines().enumerate().skip(skip) {
+        let line = i + 1;
+        if text.trim().is_empty() {
+            continue;
+        }
+        let row = split_row(text, options.delimiter).and_then(|fields| parse_row::<W>(&fields));
+        let (a, b, weight) = match row {
+            Ok(row) => row,
+            Err(_) if options.skip_malformed => {
+                summary.skipped += 1;
+                continue;
+            }
+            Err(message) => return Err(FormatError::new(line, message)),
+        };
+        let mut node = |name: String| {
+            *nodes.entry(name).or_insert_with_key(|name| {
+                summary.names.push(name.clone());
+                (summary.names.len() - 1) as Node
+            })
+        };
+        let (a, b) = (node(a), node(b));
+        edges.push((a, b, weight));
+    }
+
+    let start = graph.add_nodes(summary.names.len()).start;
+    for (a, b, weight) in edges {
+        graph.add_edge(a + start, b + start, weight);
+    }
+    Ok(summary)
+}
+
+/// Separa uma linha em campos, tratando aspas como as planilhas: `"a,b"` é um só campo, e `""`
+/// dentro de aspas é uma aspa.
+fn split_row(text: &str, delimiter: char) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = text.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.next_if_eq(&'"').is_some() => field.push('"'),
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted field".to_string());
+    }
+    fields.push(field);
+    Ok(fields.into_iter().map(|f| f.trim().to_string()).collect())
+}
+
+fn parse_row<W: FromStr + Num>(fields: &[String]) -> Result<(String, String, W), String> {
+    let (a, b, weight) = match fields {
+        [a, b] => (a, b, None),
+        [a, b, weight] => (a, b, Some(weight)),
+        _ => return Err(format!("expected 2 or 3 fields, found {}", fields.len())),
+    };
+    if a.is_empty() || b.is_empty() {
+        return Err("empty node name".to_string());
+    }
+    let weight = match weight {
+        Some(w) => w.parse().map_err(|_| format!("invalid weight {w:?}"))?,
+        None => W::ONE,
+    };
+    Ok((a.clone(), b.clone(), weight))
+}
@@ -0,0 +1,125 @@
+use std::fmt::Write;
+use std::ops::Range;
+use std::str::FromStr;
+
+use super::FormatError;
+use crate::{node_ids, GraphMut, GraphRef, Node, Num};
+
+/// Gera a descrição de `graph` no formato DIMACS.
+///
+/// Grafos direcionados usam o formato de caminhos mínimos, com a linha `p sp <nós> <arestas>` e
+/// uma linha `a <origem> <destino> <peso>` por aresta. Grafos não direcionados usam o formato de
+/// arestas, com `p edge <nós> <arestas>` e uma linha `e <a> <b>` por aresta, escrita uma única
+/// vez. Como `e` normalmente não tem peso, o peso só é escrito (`e <a> <b> <peso>`) caso alguma
+/// aresta tenha peso diferente de `1`.
+///
+/// No DIMACS, os nós vão de `1` a `n`, sem buracos. Por isso, os nós são renumerados na ordem dos
+/// seus identificadores; em um grafo sem nós removidos, o nó `n` vira o nó `n + 1`.
+pub fn to_dimacs<W: Num>(graph: &dyn GraphRef<W>) -> String {
+    let directed = graph.is_directed();
+    // Posição de cada identificador, começando em `1`.
+    let mut index = vec![0; node_ids(graph).last().map_or(0, |n| n as usize + 1)];
+    for (i, n) in node_ids(graph).enumerate() {
+        index[n as usize] = i + 1;
+    }
+    let edges: Vec<_> = graph
+        .edges()
+        .into_iter()
+        .filter(|e| directed || e.0 <= e.1)
+        .collect();
+
+    // `write!` em uma `String` nunca falha, por isso os `unwrap`s.
+    let mut out = String::new();
+    let (problem, line) = if directed { ("sp", 'a') } else { ("edge", 'e') };
+    let weighted = directed || edges.iter().any(|e| e.2 != W::ONE);
+    writeln!(out, "p {problem} {} {}", graph.node_count(), edges.len()).unwrap();
+    for (a, b, weight) in edges {
+        write!(out, "{line} {} {}", index[a as usize], index[b as usize]).unwrap();
+        if weighted {
+            write!(out, " {weight}").unwrap();
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Lê um grafo no formato DIMACS, adicionando os seus nós e arestas a `graph`.
+///
+/// Aceita os formatos de caminhos mínimos (`a <origem> <destino> <peso>`) e de arestas
+/// (`e <a> <b>`, com um peso opcional no final). Linhas `c` são comentários, e a linha
+/// `p <problema> <nós> <arestas>` precisa vir antes de qualquer aresta; o tipo do problema e a
+/// quantidade de arestas não são verificados. Linhas `n`, usadas por problemas de fluxo para
+/// marcar a origem e o destino, são ignoradas.
+///
+/// São criados os `<nós>` nós da linha `p`, e o intervalo retornado guarda os seus
+/// identificadores: o nó `i` do arquivo vira o nó `start + i - 1`. Arestas `a` são adicionadas com
+/// `add_edge`, então seguem a direção de `graph`; arestas `e` são adicionadas nos dois sentidos em
+/// um grafo direcionado. Nada é adicionado a `graph` caso o texto tenha algum erro.
+pub fn read_dimacs<W: Num + FromStr>(
+    input: &str,
+    graph: &mut dyn GraphMut<W>,
+) -> Result<Range<Node>, FormatError> {
+    let mut node_count = None;
+    let mut edges = Vec::new();
+    for (i, text) in input.lines().enumerate() {
+        let line = i + 1;
+        let mut words = text.split_whitespace();
+        let Some(kind) = words.next() else {
+            continue;
+        };
+        match kind {
+            "c" | "n" => {}
+            "p" => {
+                if node_count.is_some() {
+                    return Err(FormatError::new(line, "more than one problem line"));
+                }
+                let count = words
+                    .nth(1)
+                    .and_then(|w| w.parse::<usize>().ok())
+                    .ok_or_else(|| {
+                        FormatError::new(line, "expected `p <problem> <nodes> <edges>`")
+                    })?;
+                node_count = Some(count);
+            }
+            "a" | "e" => {
+                let count = node_count
+                    .ok_or_else(|| FormatError::new(line, "edge before the problem line"))?;
+                let mut endpoint = || {
+                    words
+                        .next()
+                        .and_then(|w| w.parse::<usize>().ok())
+                        .filter(|&n| (1..=count).contains(&n))
+                        .map(|n| (n - 1) as Node)
+                        .ok_or_else(|| {
+                            FormatError::new(line, format!("expected nodes between 1 and {count}"))
+                        })
+                };
+                let (a, b) = (endpoint()?, endpoint()?);
+                let weight = match words.next() {
+                    Some(w) => w
+                        .parse()
+                        .map_err(|_| FormatError::new(line, format!("invalid weight {w:?}")))?,
+                    None if kind == "e" => W::ONE,
+                    None => return Err(FormatError::new(line, "arc without a weight")),
+                };
+                edges.push((a, b, weight, kind == "e"));
+            }
+            _ => {
+                return Err(FormatError::new(
+                    line,
+                    format!("unknown line type {kind:?}"),
+                ))
+            }
+        }
+    }
+
+    let nodes = graph.add_nodes(node_count.unwrap_or(0));
+    let start = nodes.start;
+    for (a, b, weight, undirected) in edges {
+        graph.add_edge(a + start, b + start, weight);
+        if undirected && graph.is_directed() && a != b {
+            graph.add_edge(b + start, a + start, weight);
+        }
+    }
+    Ok(nodes)
+}
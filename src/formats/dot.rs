@@ -0,0 +1,351 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use super::FormatError;
+use crate::{node_ids, Edge, GraphMut, GraphRef, Node, Num};
+
+/// Opções de [`to_dot`].
+#[derive(Debug, Clone)]
+pub struct DotOptions {
+    /// Nome do grafo no arquivo gerado.
+    pub name: String,
+    /// Força a saída a ser um `digraph` (`Some(true)`) ou um `graph` (`Some(false)`). Com `None`,
+    /// segue a direção do próprio grafo.
+    pub directed: Option<bool>,
+    /// Mostra o peso de cada aresta como rótulo.
+    pub weights: bool,
+}
+
+impl Default for DotOptions {
+    fn default() -> Self {
+        Self {
+            name: "G".to_string(),
+            directed: None,
+            weights: true,
+        }
+    }
+}
+
+/// Gera a descrição de `graph` na linguagem DOT, do Graphviz.
+///
+/// Assim como em [`crate::print_edges`], os nós são numerados a partir de `1`, para ficarem iguais
+/// aos da entrada. Nós sem arestas também são listados. Por exemplo, o resultado pode ser
+/// convertido em uma imagem com `dot -Tpng grafo.dot -o grafo.png`.
+///
+/// Ao escrever um grafo não direcionado como `graph`, cada aresta aparece uma única vez. Ao
+/// escrevê-lo como `digraph`, aparecem os dois sentidos.
+pub fn to_dot<W: Num>(graph: &dyn GraphRef<W>, options: &DotOptions) -> String {
+    let directed = options.directed.unwrap_or(graph.is_directed());
+    let (keyword, connector) = if directed {
+        ("digraph", "->")
+    } else {
+        ("graph", "--")
+    };
+
+    // `write!` em uma `String` nunca falha, por isso os `unwrap`s.
+    let mut out = String::new();
+    writeln!(out, "{keyword} {} {{", quote(&options.name)).unwrap();
+    for n in node_ids(graph) {
+        writeln!(out, "  {};", n + 1).unwrap();
+    }
+    for (a, b, weight) in graph.edges() {
+        // Em um grafo não direcionado escrito como `graph`, pula o sentido `b -> a`.
+        if !directed && !graph.is_directed() && a > b {
+            continue;
+        }
+        write!(out, "  {} {connector} {}", a + 1, b + 1).unwrap();
+        if options.weights {
+            write!(out, " [label={}]", quote(&weight.to_string())).unwrap();
+        }
+        out.push_str(";\n");
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Coloca `s` entre aspas, escapando as aspas que ele contiver.
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\\\""))
+}
+
+/// Lê um grafo na linguagem DOT, adicionando os seus nós e arestas a `graph`.
+///
+/// Aceita um subconjunto da linguagem: declarações de nós (`a;`), de arestas, inclusive em cadeia
+/// (`a -- b -- c;`), e atributos entre colchetes. O peso de uma aresta é lido do atributo `weight`
+/// ou, na falta dele, de um `label` numérico; sem nenhum dos dois, o peso é `1`. Atributos do
+/// grafo (`rankdir=LR`, `node [shape=box]`) e comentários são ignorados. Subgrafos não são
+/// aceitos.
+///
+/// Os nós de DOT têm nomes, e não números. Cada nome vira um novo nó de `graph`, na ordem em que
+/// aparece pela primeira vez, e o vetor retornado guarda o nome de cada nó criado, em ordem. As
+/// arestas são adicionadas com `add_edge`, então seguem a direção de `graph`; as arestas de um
+/// `graph` (não direcionado) lido em um grafo direcionado são adicionadas nos dois sentidos.
+///
+/// Nada é adicionado a `graph` caso o texto tenha algum erro.
+pub fn read_dot<W: Num + FromStr>(
+    input: &str,
+    graph: &mut dyn GraphMut<W>,
+) -> Result<Vec<String>, FormatError> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        pos: 0,
+        names: Vec::new(),
+        nodes: HashMap::new(),
+        edges: Vec::new(),
+    };
+    let directed = parser.parse_graph()?;
+
+    let start = graph.add_nodes(parser.names.len()).start;
+    for (a, b, weight) in parser.edges {
+        graph.add_edge(a + start, b + start, weight);
+        if !directed && graph.is_directed() && a != b {
+            graph.add_edge(b + start, a + start, weight);
+        }
+    }
+    Ok(parser.names)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Um identificador, número ou texto entre aspas.
+    Id(String),
+    /// `--` ou `->`.
+    EdgeOp,
+    Symbol(char),
+}
+
+/// Separa o texto em tokens, guardando a linha de cada um.
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, FormatError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    let mut line = 1;
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => {}
+            // Comentários de linha, incluindo as linhas de pré-processador (`#`).
+            '#' => while chars.next_if(|&c| c != '\n').is_some() {},
+            '/' if chars.next_if_eq(&'/').is_some() => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+            }
+            '/' if chars.next_if_eq(&'*').is_some() => {
+                let start = line;
+                let mut previous = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if previous == '*' => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            previous = c;
+                        }
+                        None => return Err(FormatError::new(start, "unterminated comment")),
+                    }
+                }
+            }
+            '-' if chars.peek().is_some_and(|&c| c == '-' || c == '>') => {
+                chars.next();
+                tokens.push((Token::EdgeOp, line));
+            }
+            '"' => {
+                let start = line;
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') if chars.peek() == Some(&'"') => {
+                            text.push(chars.next().unwrap())
+                        }
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            text.push(c);
+                        }
+                        None => return Err(FormatError::new(start, "unterminated string")),
+                    }
+                }
+                tokens.push((Token::Id(text), start));
+            }
+            '{' | '}' | '[' | ']' | ';' | ',' | '=' | ':' => tokens.push((Token::Symbol(c), line)),
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let mut text = c.to_string();
+                while let Some(c) = chars.next_if(|&c| c.is_alphanumeric() || c == '_' || c == '.')
+                {
+                    text.push(c);
+                }
+                tokens.push((Token::Id(text), line));
+            }
+            c => {
+                return Err(FormatError::new(
+                    line,
+                    format!("unexpected character {c:?}"),
+                ))
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<W> {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    /// Nome de cada nó encontrado, na ordem em que apareceu.
+    names: Vec<String>,
+    nodes: HashMap<String, Node>,
+    edges: Vec<Edge<W>>,
+}
+
+impl<W: Num + FromStr> Parser<W> {
+    /// Lê o grafo inteiro, retornando se ele é direcionado.
+    fn parse_graph(&mut self) -> Result<bool, FormatError> {
+        if self.peek_keyword("strict") {
+            self.pos += 1;
+        }
+        let directed = if self.peek_keyword("digraph") {
+            true
+        } else if self.peek_keyword("graph") {
+            false
+        } else {
+            return Err(self.error("expected `graph` or `digraph`"));
+        };
+        self.pos += 1;
+        if let Some(Token::Id(_)) = self.peek() {
+            self.pos += 1;
+        }
+        self.expect('{')?;
+        while !self.eat('}') {
+            if self.peek().is_none() {
+                return Err(self.error("expected `}` at the end of the graph"));
+            }
+            self.parse_statement()?;
+            self.eat(';');
+        }
+        if self.peek().is_some() {
+            return Err(self.error("unexpected text after the end of the graph"));
+        }
+        Ok(directed)
+    }
+    fn parse_statement(&mut self) -> Result<(), FormatError> {
+        if self.peek_keyword("subgraph") || self.peek() == Some(&Token::Symbol('{')) {
+            return Err(self.error("subgraphs are not supported"));
+        }
+        if ["graph", "node", "edge"]
+            .iter()
+            .any(|k| self.peek_keyword(k))
+        {
+            // Atributos padrão, que não afetam a estrutura do grafo.
+            self.pos += 1;
+            self.parse_attributes()?;
+            return Ok(());
+        }
+        let first = self.expect_id()?;
+        if self.eat('=') {
+            // Atributo do grafo, como `rankdir = LR`.
+            self.expect_id()?;
+            return Ok(());
+        }
+        let mut chain = vec![self.node(first)];
+        while self.peek() == Some(&Token::EdgeOp) {
+            self.pos += 1;
+            let id = self.expect_id()?;
+            chain.push(self.node(id));
+        }
+        if self.peek() == Some(&Token::Symbol(':')) {
+            return Err(self.error("ports are not supported"));
+        }
+        let attributes = self.parse_attributes()?;
+        if chain.len() > 1 {
+            let weight = self.weight(&attributes)?;
+            for pair in chain.windows(2) {
+                self.edges.push((pair[0], pair[1], weight));
+            }
+        }
+        Ok(())
+    }
+    /// Lê zero ou mais listas de atributos (`[a=1, b=2]`), retornando os pares encontrados junto
+    /// com a linha de cada um.
+    fn parse_attributes(&mut self) -> Result<Vec<(String, String, usize)>, FormatError> {
+        let mut attributes = Vec::new();
+        while self.eat('[') {
+            while !self.eat(']') {
+                let line = self.line();
+                let key = self.expect_id()?;
+                let value = if self.eat('=') {
+                    self.expect_id()?
+                } else {
+                    "true".to_string()
+                };
+                attributes.push((key, value, line));
+                if !self.eat(',') {
+                    self.eat(';');
+                }
+            }
+        }
+        Ok(attributes)
+    }
+    fn weight(&self, attributes: &[(String, String, usize)]) -> Result<W, FormatError> {
+        let find = |key: &str| attributes.iter().rev().find(|a| a.0 == key);
+        if let Some((_, value, line)) = find("weight") {
+            return value
+                .parse()
+                .map_err(|_| FormatError::new(*line, format!("invalid weight {value:?}")));
+        }
+        // Rótulos podem ser qualquer texto, então só são usados como peso quando são números.
+        let label = find("label").and_then(|(_, value, _)| value.parse().ok());
+        Ok(label.unwrap_or(W::ONE))
+    }
+    /// Retorna o nó com o nome `name`, criando-o caso ainda não exista.
+    fn node(&mut self, name: String) -> Node {
+        if let Some(&node) = self.nodes.get(&name) {
+            return node;
+        }
+        let node = self.names.len() as Node;
+        self.names.push(name.clone());
+        self.nodes.insert(name, node);
+        node
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        // Palavras-chave de DOT não diferenciam maiúsculas de minúsculas.
+        matches!(self.peek(), Some(Token::Id(id)) if id.eq_ignore_ascii_case(keyword))
+    }
+    /// Avança caso o próximo token seja o símbolo `c`, indicando se avançou.
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(&Token::Symbol(c));
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+    fn expect(&mut self, c: char) -> Result<(), FormatError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{c}`")))
+        }
+    }
+    fn expect_id(&mut self) -> Result<String, FormatError> {
+        match self.peek() {
+            Some(Token::Id(id)) => {
+                let id = id.clone();
+                self.pos += 1;
+                Ok(id)
+            }
+            _ => Err(self.error("expected an identifier")),
+        }
+    }
+    /// Linha do próximo token, ou do último caso o texto tenha acabado.
+    fn line(&self) -> usize {
+        let index = self.pos.min(self.tokens.len().saturating_sub(1));
+        self.tokens.get(index).map_or(1, |(_, line)| *line)
+    }
+    fn error(&self, message: &str) -> FormatError {
+        FormatError::new(self.line(), message)
+    }
+}
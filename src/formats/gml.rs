@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use super::FormatError;
+use crate::{node_ids, GraphMut, GraphRef, Node, Num};
+
+/// Gera a descrição de `graph` no formato GML, o mesmo de `networkx.write_gml`.
+///
+/// Cada nó vira um bloco `node` com o seu identificador como `id` e como `label`, e cada aresta um
+/// bloco `edge` com `source`, `target` e `weight`. Em grafos não direcionados, cada aresta aparece
+/// uma única vez.
+pub fn to_gml<W: Num>(graph: &dyn GraphRef<W>) -> String {
+    let directed = graph.is_directed();
+    // `write!` em uma `String` nunca falha, por isso os `unwrap`s.
+    let mut out = String::new();
+    writeln!(out, "graph [\n  directed {}", directed as u8).unwrap();
+    for n in node_ids(graph) {
+        writeln!(out, "  node [\n    id {n}\n    label \"{n}\"\n  ]").unwrap();
+    }
+    for (a, b, weight) in graph.edges() {
+        if directed || a <= b {
+            writeln!(
+                out,
+                "  edge [\n    source {a}\n    target {b}\n    weight {weight}\n  ]"
+            )
+            .unwrap();
+        }
+    }
+    out.push_str("]\n");
+    out
+}
+
+/// Lê um grafo no formato GML, adicionando os seus nós e arestas a `graph`.
+///
+/// O texto precisa ter um bloco `graph`, com um bloco `node` (com um `id` inteiro) por nó e um
+/// bloco `edge` (com `source` e `target`) por aresta. O peso de uma aresta é lido do atributo
+/// `weight` ou, na falta dele, de `value`; sem nenhum dos dois, o peso é `1`. Outros atributos,
+/// inclusive blocos aninhados como `graphics`, são ignorados, assim como linhas começando com `#`.
+///
+/// Cada nó vira um novo nó de `graph`, na ordem em que aparece, e o vetor retornado guarda o
+/// rótulo (`label`) de cada nó criado, ou o seu `id` caso ele não tenha rótulo. As arestas de um
+/// grafo sem `directed 1` são adicionadas nos dois sentidos em um grafo direcionado. Nada é
+/// adicionado a `graph` caso o texto tenha algum erro.
+pub fn read_gml<W: Num + FromStr>(
+    input: &str,
+    graph: &mut dyn GraphMut<W>,
+) -> Result<Vec<String>, FormatError> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        pos: 0,
+    };
+    let document = parser.parse_list(false)?;
+    let Some((_, Value::List(body, _))) = document.iter().find(|(key, _)| key == "graph") else {
+        return Err(FormatError::new(1, "missing the `graph` block"));
+    };
+
+    let mut directed = false;
+    let mut names = Vec::new();
+    let mut nodes: HashMap<i64, Node> = HashMap::new();
+    let mut edges = Vec::new();
+    for (key, value) in body {
+        match (key.as_str(), value) {
+            ("directed", Value::Number(text, line)) => {
+                directed = text != "0";
+                if !matches!(text.as_str(), "0" | "1") {
+                    return Err(FormatError::new(*line, "`directed` must be 0 or 1"));
+                }
+            }
+            ("node", Value::List(attributes, line)) => {
+                let (id, line) = integer(attributes, "id", *line)?;
+                let label = match find(attributes, "label") {
+                    Some(Value::String(label, _)) => label.clone(),
+                    _ => id.to_string(),
+                };
+                if nodes.insert(id, names.len() as Node).is_some() {
+                    return Err(FormatError::new(line, format!("node {id} declared twice")));
+                }
+                names.push(label);
+            }
+            ("edge", Value::List(attributes, line)) => edges.push((attributes, *line)),
+            _ => {}
+        }
+    }
+
+    let mut checked = Vec::with_capacity(edges.len());
+    for (attributes, line) in edges {
+        let endpoint = |key: &str| {
+            let (id, line) = integer(attributes, key, line)?;
+            nodes.get(&id).copied().ok_or_else(|| {
+                FormatError::new(line, format!("edge references an undeclared node {id}"))
+            })
+        };
+        let (a, b) = (endpoint("source")?, endpoint("target")?);
+        let weight = match find(attributes, "weight").or_else(|| find(attributes, "value")) {
+            Some(Value::Number(text, line)) => text
+                .parse()
+                .map_err(|_| FormatError::new(*line, format!("invalid weight {text}")))?,
+            Some(Value::String(_, line) | Value::List(_, line)) => {
+                return Err(FormatError::new(*line, "the weight must be a number"))
+            }
+            None => W::ONE,
+        };
+        checked.push((a, b, weight));
+    }
+
+    let start = graph.add_nodes(names.len()).start;
+    for (a, b, weight) in checked {
+        graph.add_edge(a + start, b + start, weight);
+        if !directed && graph.is_directed() && a != b {
+            graph.add_edge(b + start, a + start, weight);
+        }
+    }
+    Ok(names)
+}
+
+/// Retorna o primeiro valor com a chave `key`.
+fn find<'a>(attributes: &'a [(String, Value)], key: &str) -> Option<&'a Value> {
+    attributes.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+/// Retorna o valor inteiro com a chave `key`, junto com a sua linha. `line` é usada no erro caso
+/// a chave não exista.
+fn integer(
+    attributes: &[(String, Value)],
+    key: &str,
+    line: usize,
+) -> Result<(i64, usize), FormatError> {
+    match find(attributes, key) {
+        Some(Value::Number(text, line)) => text
+            .parse()
+            .map(|n| (n, *line))
+            .map_err(|_| FormatError::new(*line, format!("`{key}` must be an integer"))),
+        Some(Value::String(_, line) | Value::List(_, line)) => Err(FormatError::new(
+            *line,
+            format!("`{key}` must be an integer"),
+        )),
+        None => Err(FormatError::new(line, format!("missing `{key}`"))),
+    }
+}
+
+/// Um valor de GML, junto com a linha em que aparece.
+#[derive(Debug)]
+enum Value {
+    /// Números são guardados como texto, para que cada tipo de peso os leia com o seu `FromStr`.
+    Number(String, usize),
+    String(String, usize),
+    List(Vec<(String, Value)>, usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Key(String),
+    Number(String),
+    String(String),
+    Open,
+    Close,
+}
+
+/// Separa o texto em tokens, guardando a linha de cada um.
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, FormatError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    let mut line = 1;
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => {}
+            '#' => while chars.next_if(|&c| c != '\n').is_some() {},
+            '[' => tokens.push((Token::Open, line)),
+            ']' => tokens.push((Token::Close, line)),
+            '"' => {
+                let start = line;
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            text.push(c);
+                        }
+                        None => return Err(FormatError::new(start, "unterminated string")),
+                    }
+                }
+                // O GML não tem escapes: aspas e outros caracteres especiais são escritos como
+                // entidades de HTML, como faz o networkx.
+                let text = text
+                    .replace("&quot;", "\"")
+                    .replace("&lt;", "<")
+                    .replace("&gt;", ">")
+                    .replace("&amp;", "&");
+                tokens.push((Token::String(text), start));
+            }
+            c if c.is_ascii_digit() || matches!(c, '-' | '+' | '.') => {
+                let mut text = c.to_string();
+                while let Some(c) =
+                    chars.next_if(|&c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'))
+                {
+                    text.push(c);
+                }
+                tokens.push((Token::Number(text), line));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut text = c.to_string();
+                while let Some(c) = chars.next_if(|&c| c.is_ascii_alphanumeric() || c == '_') {
+                    text.push(c);
+                }
+                tokens.push((Token::Key(text), line));
+            }
+            c => {
+                return Err(FormatError::new(
+                    line,
+                    format!("unexpected character {c:?}"),
+                ))
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl Parser {
+    /// Lê pares de chave e valor até o `]` que fecha a lista ou, com `nested` falso, até o fim do
+    /// texto.
+    fn parse_list(&mut self, nested: bool) -> Result<Vec<(String, Value)>, FormatError> {
+        let mut list = Vec::new();
+        loop {
+            let Some((token, line)) = self.tokens.get(self.pos).cloned() else {
+                if nested {
+                    return Err(FormatError::new(self.line(), "expected `]`"));
+                }
+                return Ok(list);
+            };
+            self.pos += 1;
+            let key = match token {
+                Token::Close if nested => return Ok(list),
+                Token::Key(key) => key,
+                _ => return Err(FormatError::new(line, "expected a key")),
+            };
+            let Some((token, line)) = self.tokens.get(self.pos).cloned() else {
+                return Err(FormatError::new(line, format!("`{key}` without a value")));
+            };
+            self.pos += 1;
+            let value = match token {
+                Token::Number(text) => Value::Number(text, line),
+                Token::String(text) => Value::String(text, line),
+                Token::Open => Value::List(self.parse_list(true)?, line),
+                Token::Key(_) | Token::Close => {
+                    return Err(FormatError::new(line, format!("`{key}` without a value")))
+                }
+            };
+            list.push((key, value));
+        }
+    }
+    /// Linha do último token, usada nos erros sem uma posição melhor.
+    fn line(&self) -> usize {
+        self.tokens.last().map_or(1, |(_, line)| *line)
+    }
+}
@@ -0,0 +1,364 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use super::FormatError;
+use crate::{node_ids, GraphMut, GraphRef, Node, Num};
+
+/// Gera a descrição de `graph` em GraphML, o formato XML usado por ferramentas como Gephi e yEd.
+///
+/// Cada nó `n` recebe o identificador `n{n}`, mantendo a numeração do grafo (começando em `0`), e
+/// o peso de cada aresta é guardado na chave `weight`. Em grafos não direcionados, cada aresta
+/// aparece uma única vez.
+pub fn to_graphml<W: Num>(graph: &dyn GraphRef<W>) -> String {
+    let directed = graph.is_directed();
+    let edge_default = if directed { "directed" } else { "undirected" };
+
+    // `write!` em uma `String` nunca falha, por isso os `unwrap`s.
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    out.push_str("  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"/>\n");
+    writeln!(out, "  <graph id=\"G\" edgedefault=\"{edge_default}\">").unwrap();
+    for n in node_ids(graph) {
+        writeln!(out, "    <node id=\"n{n}\"/>").unwrap();
+    }
+    for (a, b, weight) in graph.edges() {
+        if !directed && a > b {
+            continue;
+        }
+        writeln!(
+            out,
+            "    <edge source=\"n{a}\" target=\"n{b}\"><data key=\"weight\">{weight}</data></edge>"
+        )
+        .unwrap();
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+/// Lê um grafo em GraphML, adicionando os seus nós e arestas a `graph`.
+///
+/// O peso de cada aresta é lido da chave (`<key>`) de arestas chamada `weight`, caso exista, e é
+/// `1` para arestas sem ela. Os demais dados são ignorados, assim como grafos além do primeiro.
+///
+/// Assim como em [`super::read_dot`], cada nó declarado vira um novo nó de `graph`, na ordem do
+/// arquivo, e o vetor retornado guarda o identificador (`id`) de cada nó criado. As arestas seguem
+/// a direção de `graph`, e as não direcionadas são adicionadas nos dois sentidos em um grafo
+/// direcionado. Nada é adicionado a `graph` caso o texto tenha algum erro.
+pub fn read_graphml<W: Num + FromStr>(
+    input: &str,
+    graph: &mut dyn GraphMut<W>,
+) -> Result<Vec<String>, FormatError> {
+    let events = xml_events(input)?;
+    let mut names = Vec::new();
+    let mut nodes: HashMap<String, Node> = HashMap::new();
+    // Arestas na forma `(origem, destino, peso, direcionada)`.
+    let mut edges = Vec::new();
+    let mut weight_key = None;
+    let mut weight_default = W::ONE;
+    let mut edge_default_directed = false;
+    let mut graphs = 0;
+
+    let mut i = 0;
+    while i < events.len() {
+        let (event, line) = &events[i];
+        let line = *line;
+        i += 1;
+        let Event::Start {
+            name,
+            attributes,
+            empty,
+        } = event
+        else {
+            continue;
+        };
+        let attribute = |key: &str| attributes.iter().find(|a| a.0 == key).map(|a| &a.1);
+        match name.as_str() {
+            "key" => {
+                let is_edge_key =
+                    matches!(attribute("for").map(String::as_str), Some("edge" | "all"));
+                let is_weight = attribute("attr.name")
+                    .or(attribute("id"))
+                    .map(String::as_str)
+                    == Some("weight");
+                if is_edge_key && is_weight {
+                    let id = attribute("id")
+                        .ok_or_else(|| FormatError::new(line, "<key> without an id"))?;
+                    weight_key = Some(id.clone());
+                    // Chaves podem ter um valor padrão, em `<default>`.
+                    if !empty {
+                        if let Some((text, text_line)) = element_text(&events, &mut i, "key") {
+                            weight_default = parse_weight(&text, text_line)?;
+                        }
+                    }
+                }
+            }
+            "graph" => {
+                graphs += 1;
+                if graphs == 1 {
+                    edge_default_directed =
+                        attribute("edgedefault").map(String::as_str) == Some("directed");
+                }
+            }
+            _ if graphs != 1 => {}
+            "node" => {
+                let id = attribute("id")
+                    .ok_or_else(|| FormatError::new(line, "<node> without an id"))?;
+                if nodes.contains_key(id) {
+                    return Err(FormatError::new(
+                        line,
+                        format!("node {id:?} declared twice"),
+                    ));
+                }
+                nodes.insert(id.clone(), names.len() as Node);
+                names.push(id.clone());
+            }
+            "edge" => {
+                let endpoint = |key: &str| {
+                    let id = attribute(key)
+                        .ok_or_else(|| FormatError::new(line, format!("<edge> without a {key}")))?;
+                    nodes.get(id).copied().ok_or_else(|| {
+                        FormatError::new(line, format!("edge references undeclared node {id:?}"))
+                    })
+                };
+                let (a, b) = (endpoint("source")?, endpoint("target")?);
+                let directed = match attribute("directed").map(String::as_str) {
+                    Some("true") => true,
+                    Some("false") => false,
+                    _ => edge_default_directed,
+                };
+                let mut weight = weight_default;
+                if !empty {
+                    weight = edge_weight(&events, &mut i, weight_key.as_deref())?.unwrap_or(weight);
+                }
+                edges.push((a, b, weight, directed));
+            }
+            _ => {}
+        }
+    }
+    if graphs == 0 {
+        return Err(FormatError::new(1, "no <graph> element found"));
+    }
+
+    let start = graph.add_nodes(names.len()).start;
+    for (a, b, weight, directed) in edges {
+        graph.add_edge(a + start, b + start, weight);
+        if !directed && graph.is_directed() && a != b {
+            graph.add_edge(b + start, a + start, weight);
+        }
+    }
+    Ok(names)
+}
+
+fn parse_weight<W: FromStr>(text: &str, line: usize) -> Result<W, FormatError> {
+    text.trim()
+        .parse()
+        .map_err(|_| FormatError::new(line, format!("invalid weight {:?}", text.trim())))
+}
+
+/// Percorre o conteúdo de um `<edge>` até o seu fim, retornando o peso encontrado no `<data>` da
+/// chave `weight_key`.
+fn edge_weight<W: FromStr>(
+    events: &[(Event, usize)],
+    i: &mut usize,
+    weight_key: Option<&str>,
+) -> Result<Option<W>, FormatError> {
+    let mut weight = None;
+    while let Some((event, _)) = events.get(*i) {
+        *i += 1;
+        match event {
+            Event::End(name) if name == "edge" => break,
+            Event::Start {
+                name,
+                attributes,
+                empty: false,
+            } if name == "data" => {
+                let is_weight = attributes
+                    .iter()
+                    .any(|(k, v)| k == "key" && Some(v.as_str()) == weight_key);
+                if let Some((text, line)) = element_text(events, i, "data") {
+                    if is_weight {
+                        weight = Some(parse_weight(&text, line)?);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(weight)
+}
+
+/// Junta o texto encontrado até o fim do elemento `name`, avançando `i` até depois dele.
+fn element_text(events: &[(Event, usize)], i: &mut usize, name: &str) -> Option<(String, usize)> {
+    let mut text: Option<(String, usize)> = None;
+    while let Some((event, line)) = events.get(*i) {
+        *i += 1;
+        match event {
+            Event::End(end) if end == name => break,
+            Event::Text(t) => text.get_or_insert((String::new(), *line)).0.push_str(t),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Um trecho de um documento XML.
+#[derive(Debug)]
+enum Event {
+    /// Uma tag de abertura. `empty` indica uma tag que se fecha sozinha, como `<node id="a"/>`.
+    Start {
+        name: String,
+        attributes: Vec<(String, String)>,
+        empty: bool,
+    },
+    End(String),
+    Text(String),
+}
+
+/// Separa um documento XML em tags e textos, guardando a linha de cada um.
+///
+/// É apenas o necessário para GraphML: comentários, declarações (`<?xml ... ?>`) e `<!DOCTYPE>`
+/// são ignorados, e _namespaces_ não são tratados, ou seja, `y:ShapeNode` é só um nome.
+fn xml_events(input: &str) -> Result<Vec<(Event, usize)>, FormatError> {
+    let mut events = Vec::new();
+    let mut rest = input;
+    let mut line = 1;
+    // Avança `n` bytes, contando as quebras de linha.
+    let advance = |rest: &mut &str, line: &mut usize, n: usize| {
+        *line += rest[..n].matches('\n').count();
+        *rest = &rest[n..];
+    };
+    let find = |rest: &str, pattern: &str, line: usize| {
+        rest.find(pattern)
+            .ok_or_else(|| FormatError::new(line, format!("expected `{pattern}`")))
+    };
+    while !rest.is_empty() {
+        if rest.starts_with("<!--") {
+            let end = find(rest, "-->", line)?;
+            advance(&mut rest, &mut line, end + 3);
+        } else if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = find(cdata, "]]>", line)?;
+            events.push((Event::Text(cdata[..end].to_string()), line));
+            advance(&mut rest, &mut line, "<![CDATA[".len() + end + 3);
+        } else if rest.starts_with("<?") || rest.starts_with("<!") {
+            let end = find(rest, ">", line)?;
+            advance(&mut rest, &mut line, end + 1);
+        } else if let Some(tag) = rest.strip_prefix("</") {
+            let end = find(tag, ">", line)?;
+            events.push((Event::End(tag[..end].trim().to_string()), line));
+            advance(&mut rest, &mut line, end + 3);
+        } else if rest.starts_with('<') {
+            let start_line = line;
+            let (event, len) = parse_tag(rest, line)?;
+            events.push((event, start_line));
+            advance(&mut rest, &mut line, len);
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = &rest[..end];
+            if !text.trim().is_empty() {
+                events.push((Event::Text(unescape(text, line)?), line));
+            }
+            advance(&mut rest, &mut line, end);
+        }
+    }
+    Ok(events)
+}
+
+/// Lê a tag de abertura no início de `rest`, retornando-a junto com o seu tamanho em bytes.
+fn parse_tag(rest: &str, line: usize) -> Result<(Event, usize), FormatError> {
+    let error = |message: &str| FormatError::new(line, message);
+    let mut chars = rest.char_indices().skip(1).peekable();
+    let read_while = |chars: &mut std::iter::Peekable<_>, keep: fn(char) -> bool| {
+        let mut s = String::new();
+        while let Some((_, c)) = chars.next_if(|&(_, c): &(usize, char)| keep(c)) {
+            s.push(c);
+        }
+        s
+    };
+    let is_name = |c: char| !c.is_whitespace() && !matches!(c, '/' | '>' | '=');
+    let name = read_while(&mut chars, is_name);
+    if name.is_empty() {
+        return Err(error("expected a tag name"));
+    }
+    let mut attributes = Vec::new();
+    loop {
+        read_while(&mut chars, char::is_whitespace);
+        match chars.next() {
+            Some((i, '>')) => {
+                let event = Event::Start {
+                    name,
+                    attributes,
+                    empty: false,
+                };
+                return Ok((event, i + 1));
+            }
+            Some((_, '/')) => match chars.next() {
+                Some((i, '>')) => {
+                    let event = Event::Start {
+                        name,
+                        attributes,
+                        empty: true,
+                    };
+                    return Ok((event, i + 1));
+                }
+                _ => return Err(error("expected `>` after `/`")),
+            },
+            Some((_, c)) => {
+                let mut key = c.to_string();
+                key.push_str(&read_while(&mut chars, is_name));
+                read_while(&mut chars, char::is_whitespace);
+                if chars.next().map(|(_, c)| c) != Some('=') {
+                    return Err(error(&format!("expected `=` after attribute {key}")));
+                }
+                read_while(&mut chars, char::is_whitespace);
+                let quote = match chars.next() {
+                    Some((_, q @ ('"' | '\''))) => q,
+                    _ => return Err(error(&format!("expected a quoted value for {key}"))),
+                };
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, c)) if c == quote => break,
+                        Some((_, c)) => value.push(c),
+                        None => return Err(error("unterminated attribute value")),
+                    }
+                }
+                attributes.push((key, unescape(&value, line)?));
+            }
+            None => return Err(error("unterminated tag")),
+        }
+    }
+}
+
+/// Substitui as entidades de XML (`&amp;`, `&lt;`, `&#65;`, etc.) pelos caracteres que
+/// representam.
+fn unescape(text: &str, line: usize) -> Result<String, FormatError> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find(';')
+            .ok_or_else(|| FormatError::new(line, "unterminated entity"))?;
+        let entity = &rest[start + 1..start + end];
+        let c = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        let c = c.ok_or_else(|| FormatError::new(line, format!("unknown entity &{entity};")))?;
+        out.push(c);
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use super::FormatError;
+use crate::{node_ids, GraphMut, GraphRef, Node, Num};
+
+/// Gera a descrição de `graph` em JSON.
+///
+/// O formato é um objeto com três campos:
+///
+/// ```text
+/// {
+///   "directed": false,
+///   "nodes": [0, 1, 3],
+///   "edges": [
+///     {"source": 0, "target": 1, "weight": 4}
+///   ]
+/// }
+/// ```
+///
+/// `nodes` lista os identificadores dos nós, começando em `0` e mantendo os buracos deixados por
+/// nós removidos. Em grafos não direcionados, cada aresta aparece uma única vez, com
+/// `source <= target`.
+pub fn to_json<W: Num>(graph: &dyn GraphRef<W>) -> String {
+    let directed = graph.is_directed();
+    let nodes: Vec<_> = node_ids(graph).map(|n| n.to_string()).collect();
+    let edges: Vec<_> = graph
+        .edges()
+        .into_iter()
+        .filter(|e| directed || e.0 <= e.1)
+        .map(|(a, b, weight)| {
+            format!("    {{\"source\": {a}, \"target\": {b}, \"weight\": {weight}}}")
+        })
+        .collect();
+
+    // `write!` em uma `String` nunca falha, por isso os `unwrap`s.
+    let mut out = String::new();
+    writeln!(out, "{{\n  \"directed\": {directed},").unwrap();
+    writeln!(out, "  \"nodes\": [{}],", nodes.join(", ")).unwrap();
+    if edges.is_empty() {
+        out.push_str("  \"edges\": []\n}\n");
+    } else {
+        writeln!(out, "  \"edges\": [\n{}\n  ]\n}}", edges.join(",\n")).unwrap();
+    }
+    out
+}
+
+/// Lê um grafo no formato de [`to_json`], adicionando os seus nós e arestas a `graph`.
+///
+/// `directed` é opcional e vale `false` por padrão, assim como o `weight` de cada aresta, que vale
+/// `1`. Outros campos são ignorados.
+///
+/// Cada nó listado vira um novo nó de `graph`, na ordem de `nodes`, e o vetor retornado guarda o
+/// identificador original de cada nó criado. Em um grafo vazio sem buracos, os identificadores são
+/// os mesmos. As arestas seguem a direção de `graph`, e as de um grafo não direcionado são
+/// adicionadas nos dois sentidos em um grafo direcionado. Nada é adicionado a `graph` caso o texto
+/// tenha algum erro.
+pub fn from_json<W: Num + FromStr>(
+    input: &str,
+    graph: &mut dyn GraphMut<W>,
+) -> Result<Vec<Node>, FormatError> {
+    let mut parser = Parser {
+        chars: input.chars().peekable(),
+        line: 1,
+    };
+    let root = parser.parse_document()?;
+    let Value::Object(fields) = &root.value else {
+        return Err(FormatError::new(root.line, "expected an object"));
+    };
+    let field = |name: &str| fields.iter().find(|(key, _)| key == name).map(|(_, v)| v);
+
+    let directed = match field("directed") {
+        None => false,
+        Some(Json {
+            value: Value::Bool(b),
+            ..
+        }) => *b,
+        Some(other) => return Err(FormatError::new(other.line, "`directed` must be a boolean")),
+    };
+
+    let mut ids = Vec::new();
+    let mut nodes: HashMap<Node, Node> = HashMap::new();
+    for node in array(field("nodes"), "nodes", root.line)? {
+        let id = node_id(node)?;
+        if nodes.insert(id, ids.len() as Node).is_some() {
+            return Err(FormatError::new(
+                node.line,
+                format!("node {id} listed twice"),
+            ));
+        }
+        ids.push(id);
+    }
+
+    let mut edges = Vec::new();
+    for edge in array(field("edges"), "edges", root.line)? {
+        let Value::Object(edge_fields) = &edge.value else {
+            return Err(FormatError::new(edge.line, "expected an edge object"));
+        };
+        let edge_field = |name: &str| edge_fields.iter().find(|(k, _)| k == name).map(|(_, v)| v);
+        let endpoint = |name: &str| {
+            let value = edge_field(name)
+                .ok_or_else(|| FormatError::new(edge.line, format!("edge without `{name}`")))?;
+            let id = node_id(value)?;
+            nodes.get(&id).copied().ok_or_else(|| {
+                FormatError::new(value.line, format!("edge references unlisted node {id}"))
+            })
+        };
+        let (a, b) = (endpoint("source")?, endpoint("target")?);
+        let weight = match edge_field("weight") {
+            None => W::ONE,
+            Some(Json {
+                value: Value::Number(text),
+                line,
+            }) => text
+                .parse()
+                .map_err(|_| FormatError::new(*line, format!("invalid weight {text}")))?,
+            Some(other) => return Err(FormatError::new(other.line, "`weight` must be a number")),
+        };
+        edges.push((a, b, weight));
+    }
+
+    let start = graph.add_nodes(ids.len()).start;
+    for (a, b, weight) in edges {
+        graph.add_edge(a + start, b + start, weight);
+        if !directed && graph.is_directed() && a != b {
+            graph.add_edge(b + start, a + start, weight);
+        }
+    }
+    Ok(ids)
+}
+
+/// Retorna os elementos do campo `name`, que precisa ser um vetor.
+fn array<'a>(value: Option<&'a Json>, name: &str, line: usize) -> Result<&'a [Json], FormatError> {
+    match value {
+        Some(Json {
+            value: Value::Array(items),
+            ..
+        }) => Ok(items),
+        Some(other) => Err(FormatError::new(
+            other.line,
+            format!("`{name}` must be an array"),
+        )),
+        None => Err(FormatError::new(line, format!("missing field `{name}`"))),
+    }
+}
+
+fn node_id(value: &Json) -> Result<Node, FormatError> {
+    match &value.value {
+        Value::Number(text) => text.parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| FormatError::new(value.line, "node ids must be non-negative integers"))
+}
+
+/// Um valor JSON, junto com a linha em que começa.
+#[derive(Debug)]
+struct Json {
+    value: Value,
+    line: usize,
+}
+
+#[derive(Debug)]
+enum Value {
+    Null,
+    Bool(bool),
+    /// Números são guardados como texto, para que cada tipo de peso os leia com o seu `FromStr`.
+    Number(String),
+    /// O conteúdo dos textos não é usado pelo formato, apenas o dos nomes de campos.
+    String,
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+}
+
+impl Parser<'_> {
+    fn parse_document(&mut self) -> Result<Json, FormatError> {
+        let value = self.parse_value()?;
+        self.skip_whitespace();
+        if self.chars.peek().is_some() {
+            return Err(self.error("unexpected text after the end of the document"));
+        }
+        Ok(value)
+    }
+    fn parse_value(&mut self) -> Result<Json, FormatError> {
+        self.skip_whitespace();
+        let line = self.line;
+        let value = match self.chars.peek() {
+            Some('{') => {
+                self.chars.next();
+                let mut fields = Vec::new();
+                if !self.eat('}') {
+                    loop {
+                        self.skip_whitespace();
+                        if self.chars.next() != Some('"') {
+                            return Err(self.error("expected a field name"));
+                        }
+                        let key = self.parse_string()?;
+                        if !self.eat(':') {
+                            return Err(self.error("expected `:`"));
+                        }
+                        fields.push((key, self.parse_value()?));
+                        if self.eat('}') {
+                            break;
+                        }
+                        if !self.eat(',') {
+                            return Err(self.error("expected `,` or `}`"));
+                        }
+                    }
+                }
+                Value::Object(fields)
+            }
+            Some('[') => {
+                self.chars.next();
+                let mut items = Vec::new();
+                if !self.eat(']') {
+                    loop {
+                        items.push(self.parse_value()?);
+                        if self.eat(']') {
+                            break;
+                        }
+                        if !self.eat(',') {
+                            return Err(self.error("expected `,` or `]`"));
+                        }
+                    }
+                }
+                Value::Array(items)
+            }
+            Some('"') => {
+                self.chars.next();
+                self.parse_string()?;
+                Value::String
+            }
+            Some(c) if *c == '-' || c.is_ascii_digit() => {
+                let mut text = String::new();
+                while let Some(c) = self
+                    .chars
+                    .next_if(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+                {
+                    text.push(c);
+                }
+                Value::Number(text)
+            }
+            Some(c) if c.is_alphabetic() => {
+                let mut word = String::new();
+                while let Some(c) = self.chars.next_if(|c| c.is_alphabetic()) {
+                    word.push(c);
+                }
+                match word.as_str() {
+                    "true" => Value::Bool(true),
+                    "false" => Value::Bool(false),
+                    "null" => Value::Null,
+                    _ => return Err(self.error(&format!("unexpected word {word:?}"))),
+                }
+            }
+            _ => return Err(self.error("expected a value")),
+        };
+        Ok(Json { value, line })
+    }
+    /// Lê o restante de um texto, cujas aspas de abertura já foram lidas.
+    fn parse_string(&mut self) -> Result<String, FormatError> {
+        let mut text = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(text),
+                Some('\\') => {
+                    let c = match self.chars.next() {
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('u') => {
+                            let hex: String = (0..4).filter_map(|_| self.chars.next()).collect();
+                            u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| self.error("invalid \\u escape"))?
+                        }
+                        Some(c @ ('"' | '\\' | '/')) => c,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    text.push(c);
+                }
+                Some('\n') | None => return Err(self.error("unterminated string")),
+                Some(c) => text.push(c),
+            }
+        }
+    }
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.chars.next_if(|c| c.is_whitespace()) {
+            if c == '\n' {
+                self.line += 1;
+            }
+        }
+    }
+    /// Pula os espaços e avança caso o próximo caractere seja `c`, indicando se avançou.
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        self.chars.next_if_eq(&c).is_some()
+    }
+    fn error(&self, message: &str) -> FormatError {
+        FormatError::new(self.line, message)
+    }
+}
@@ -0,0 +1,165 @@
+use std::fmt::Write;
+use std::ops::Range;
+use std::str::FromStr;
+
+use super::FormatError;
+use crate::{node_ids, GraphMut, GraphRef, Node, Num};
+
+/// Gera a matriz de adjacência de `graph` no formato Matrix Market (`.mtx`), usado por SciPy
+/// (`scipy.io.mmread`) e pelo Matlab.
+///
+/// A matriz é esparsa (`coordinate`): cada aresta `a -> b` vira a entrada da linha `a` e coluna
+/// `b`, com o peso como valor. Grafos não direcionados são escritos como matrizes `symmetric`, das
+/// quais só é guardada a metade de baixo (`linha >= coluna`), e grafos direcionados como
+/// `general`. O campo é `integer` caso todos os pesos sejam inteiros, e `real` caso contrário.
+///
+/// Assim como no DIMACS, as linhas e colunas vão de `1` a `n`, e os nós são renumerados na ordem
+/// dos seus identificadores.
+pub fn to_matrix_market<W: Num>(graph: &dyn GraphRef<W>) -> String {
+    let directed = graph.is_directed();
+    let mut index = vec![0; node_ids(graph).last().map_or(0, |n| n as usize + 1)];
+    for (i, n) in node_ids(graph).enumerate() {
+        index[n as usize] = i + 1;
+    }
+    let entries: Vec<_> = graph
+        .edges()
+        .into_iter()
+        .filter(|e| directed || e.0 >= e.1)
+        .map(|(a, b, weight)| (index[a as usize], index[b as usize], weight.to_string()))
+        .collect();
+    let integer = entries.iter().all(|e| e.2.parse::<i64>().is_ok());
+
+    // `write!` em uma `String` nunca falha, por isso os `unwrap`s.
+    let mut out = String::new();
+    let field = if integer { "integer" } else { "real" };
+    let symmetry = if directed { "general" } else { "symmetric" };
+    writeln!(out, "%%MatrixMarket matrix coordinate {field} {symmetry}").unwrap();
+    let n = graph.node_count();
+    writeln!(out, "{n} {n} {}", entries.len()).unwrap();
+    for (row, column, value) in entries {
+        writeln!(out, "{row} {column} {value}").unwrap();
+    }
+    out
+}
+
+/// Lê uma matriz de adjacência no formato Matrix Market, adicionando os seus nós e arestas a
+/// `graph`.
+///
+/// A matriz precisa ser quadrada e esparsa (`coordinate`), com o campo `integer`, `real` ou
+/// `pattern` (sem valores, caso em que o peso é `1`) e simetria `general` ou `symmetric`. Em uma
+/// matriz `symmetric`, a entrada `(i, j)` também representa `(j, i)`, então ela é lida como um
+/// grafo não direcionado: em um grafo direcionado, cada entrada é adicionada nos dois sentidos.
+///
+/// São criados `n` nós, um por linha da matriz, e o intervalo retornado guarda os seus
+/// identificadores: a linha `i` vira o nó `start + i - 1`. Nada é adicionado a `graph` caso o
+/// texto tenha algum erro.
+pub fn read_matrix_market<W: Num + FromStr>(
+    input: &str,
+    graph: &mut dyn GraphMut<W>,
+) -> Result<Range<Node>, FormatError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(i, text)| (i + 1, text.trim()));
+    let header = lines
+        .next()
+        .map_or("", |(_, text)| text)
+        .to_ascii_lowercase();
+    let header: Vec<_> = header.split_whitespace().collect();
+    let ["%%matrixmarket", "matrix", format, field, symmetry] = header[..] else {
+        return Err(FormatError::new(
+            1,
+            "expected a `%%MatrixMarket matrix` header",
+        ));
+    };
+    if format != "coordinate" {
+        return Err(FormatError::new(
+            1,
+            "only the `coordinate` format is supported",
+        ));
+    }
+    let pattern = match field {
+        "integer" | "real" => false,
+        "pattern" => true,
+        _ => return Err(FormatError::new(1, format!("unsupported field {field:?}"))),
+    };
+    let symmetric = match symmetry {
+        "general" => false,
+        "symmetric" => true,
+        _ => {
+            return Err(FormatError::new(
+                1,
+                format!("unsupported symmetry {symmetry:?}"),
+            ))
+        }
+    };
+
+    // Pula linhas vazias e comentários, que começam com `%`.
+    let mut lines = lines.filter(|(_, text)| !text.is_empty() && !text.starts_with('%'));
+    let (line, size) = lines
+        .next()
+        .ok_or_else(|| FormatError::new(1, "missing the matrix size"))?;
+    let size: Vec<usize> = size
+        .split_whitespace()
+        .map(|w| {
+            w.parse()
+                .map_err(|_| FormatError::new(line, "expected `<rows> <columns> <entries>`"))
+        })
+        .collect::<Result<_, _>>()?;
+    let [rows, columns, count] = size[..] else {
+        return Err(FormatError::new(
+            line,
+            "expected `<rows> <columns> <entries>`",
+        ));
+    };
+    if rows != columns {
+        return Err(FormatError::new(line, "an adjacency matrix must be square"));
+    }
+
+    // A quantidade vem do arquivo, então só uma parte é reservada, para que um cabeçalho
+    // corrompido não cause uma alocação gigante. O vetor cresce caso as entradas existam.
+    let mut edges = Vec::with_capacity(count.min(4096));
+    let mut last = line;
+    for (line, text) in lines {
+        last = line;
+        let mut words = text.split_whitespace();
+        let mut index = || {
+            words
+                .next()
+                .and_then(|w| w.parse::<usize>().ok())
+                .filter(|&i| (1..=rows).contains(&i))
+                .map(|i| (i - 1) as Node)
+                .ok_or_else(|| {
+                    FormatError::new(line, format!("expected indices between 1 and {rows}"))
+                })
+        };
+        let (a, b) = (index()?, index()?);
+        let weight = if pattern {
+            W::ONE
+        } else {
+            let value = words
+                .next()
+                .ok_or_else(|| FormatError::new(line, "entry without a value"))?;
+            value
+                .parse()
+                .map_err(|_| FormatError::new(line, format!("invalid weight {value:?}")))?
+        };
+        edges.push((a, b, weight));
+    }
+    if edges.len() != count {
+        return Err(FormatError::new(
+            last,
+            format!("expected {count} entries, found {}", edges.len()),
+        ));
+    }
+
+    let nodes = graph.add_nodes(rows);
+    let start = nodes.start;
+    for (a, b, weight) in edges {
+        graph.add_edge(a + start, b + start, weight);
+        if symmetric && graph.is_directed() && a != b {
+            graph.add_edge(b + start, a + start, weight);
+        }
+    }
+    Ok(nodes)
+}
@@ -0,0 +1,64 @@
+//! Leitura e escrita de grafos em formatos usados por outras ferramentas.
+//!
+//! Cada formato fica em um submódulo. As funções de escrita recebem um `&dyn GraphRef`, e as de
+//! leitura preenchem um `&mut dyn GraphMut`, então funcionam com qualquer implementação.
+
+use std::fmt;
+
+mod adjacency_matrix;
+mod binary;
+mod csv;
+mod dimacs;
+mod dot;
+mod gml;
+mod graphml;
+mod json;
+mod matrix_market;
+mod pajek;
+mod svg;
+
+pub use adjacency_matrix::to_adjacency_matrix;
+pub use binary::{from_binary, load_binary, save_binary, to_binary, BinaryWeight};
+pub use csv::{read_csv, to_csv, CsvOptions, CsvSummary};
+pub use dimacs::{read_dimacs, to_dimacs};
+pub use dot::{read_dot, to_dot, DotOptions};
+pub use gml::{read_gml, to_gml};
+pub use graphml::{read_graphml, to_graphml};
+pub use json::{from_json, to_json};
+pub use matrix_market::{read_matrix_market, to_matrix_market};
+pub use pajek::{read_pajek, to_pajek};
+pub use svg::{render_svg, to_svg};
+
+/// Erro encontrado ao ler um grafo de um dos formatos deste módulo.
+///
+/// Indica a linha do texto em que o problema foi encontrado, começando em `1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatError {
+    line: usize,
+    message: String,
+}
+
+impl FormatError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+    /// Retorna a linha em que o erro foi encontrado, começando em `1`.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+    /// Retorna a descrição do erro.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for FormatError {}
@@ -0,0 +1,164 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use super::FormatError;
+use crate::{node_ids, GraphMut, GraphRef, Node, Num};
+
+/// Gera a descrição de `graph` no formato `.net`, do Pajek.
+///
+/// O arquivo começa com a seção `*Vertices <n>`, com um nó por linha, seguida de `*Arcs`
+/// (direcionados) ou `*Edges` (não direcionados), com uma aresta `origem destino peso` por linha.
+/// Em grafos não direcionados, cada aresta aparece uma única vez.
+///
+/// No Pajek, os nós vão de `1` a `n`, então eles são renumerados na ordem dos seus
+/// identificadores. O rótulo de cada nó é o seu identificador original.
+pub fn to_pajek<W: Num>(graph: &dyn GraphRef<W>) -> String {
+    let directed = graph.is_directed();
+    let mut index = vec![0; node_ids(graph).last().map_or(0, |n| n as usize + 1)];
+    // `write!` em uma `String` nunca falha, por isso os `unwrap`s.
+    let mut out = String::new();
+    writeln!(out, "*Vertices {}", graph.node_count()).unwrap();
+    for (i, n) in node_ids(graph).enumerate() {
+        index[n as usize] = i + 1;
+        writeln!(out, "{} \"{n}\"", i + 1).unwrap();
+    }
+    out.push_str(if directed { "*Arcs\n" } else { "*Edges\n" });
+    for (a, b, weight) in graph.edges() {
+        if directed || a <= b {
+            writeln!(out, "{} {} {weight}", index[a as usize], index[b as usize]).unwrap();
+        }
+    }
+    out
+}
+
+/// Seção do arquivo sendo lida.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Vertices,
+    Arcs,
+    Edges,
+    /// Listas de vizinhos (`*Arcslist` e `*Edgeslist`): `a b c` significa `a -> b` e `a -> c`.
+    ArcsList,
+    EdgesList,
+    /// Seções que não descrevem o grafo, como `*Network` ou `*Partition`, ou o início do arquivo.
+    Other,
+}
+
+/// Lê um grafo no formato `.net`, do Pajek, adicionando os seus nós e arestas a `graph`.
+///
+/// Aceita as seções `*Vertices <n>`, `*Arcs`, `*Edges`, `*Arcslist` e `*Edgeslist`, sem diferenciar
+/// maiúsculas de minúsculas. Linhas começando com `%` são comentários, e outras seções, como
+/// `*Network`, são ignoradas. Na seção de nós, cada linha tem o número do nó e, opcionalmente, o
+/// seu rótulo, entre aspas caso tenha espaços; as coordenadas e cores que venham depois são
+/// ignoradas. Arestas sem peso valem `1`.
+///
+/// São criados os `<n>` nós de `*Vertices`, e o vetor retornado guarda o rótulo de cada um, ou o
+/// seu número caso ele não tenha rótulo: o nó `i` do arquivo vira o nó `start + i - 1`. As arestas
+/// de `*Arcs` são adicionadas com `add_edge`, então seguem a direção de `graph`; as de `*Edges` são
+/// adicionadas nos dois sentidos em um grafo direcionado. Nada é adicionado a `graph` caso o texto
+/// tenha algum erro.
+pub fn read_pajek<W: Num + FromStr>(
+    input: &str,
+    graph: &mut dyn GraphMut<W>,
+) -> Result<Vec<String>, FormatError> {
+    let mut names: Option<Vec<String>> = None;
+    let mut section = Section::Other;
+    let mut edges = Vec::new();
+    for (i, text) in input.lines().enumerate() {
+        let line = i + 1;
+        let text = text.trim();
+        if text.is_empty() || text.starts_with('%') {
+            continue;
+        }
+        if let Some(header) = text.strip_prefix('*') {
+            let mut words = header.split_whitespace();
+            let name = words.next().unwrap_or("").to_ascii_lowercase();
+            section = match name.as_str() {
+                "vertices" => {
+                    if names.is_some() {
+                        return Err(FormatError::new(line, "more than one `*Vertices` section"));
+                    }
+                    let count: usize = words
+                        .next()
+                        .and_then(|w| w.parse().ok())
+                        .ok_or_else(|| FormatError::new(line, "expected `*Vertices <count>`"))?;
+                    names = Some((1..=count).map(|n| n.to_string()).collect());
+                    Section::Vertices
+                }
+                "arcs" => Section::Arcs,
+                "edges" => Section::Edges,
+                "arcslist" => Section::ArcsList,
+                "edgeslist" => Section::EdgesList,
+                _ => Section::Other,
+            };
+            if section != Section::Vertices && section != Section::Other && names.is_none() {
+                return Err(FormatError::new(
+                    line,
+                    "edges before the `*Vertices` section",
+                ));
+            }
+            continue;
+        }
+
+        let count = names.as_ref().map_or(0, Vec::len);
+        let node = |word: &str| {
+            word.parse::<usize>()
+                .ok()
+                .filter(|&n| (1..=count).contains(&n))
+                .map(|n| (n - 1) as Node)
+                .ok_or_else(|| {
+                    FormatError::new(line, format!("expected vertices between 1 and {count}"))
+                })
+        };
+        let (number, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+        let rest = rest.trim_start();
+        match section {
+            Section::Vertices => {
+                let n = node(number)?;
+                if let Some(label) = label(rest, line)? {
+                    names.as_mut().unwrap()[n as usize] = label;
+                }
+            }
+            Section::Arcs | Section::Edges => {
+                let mut words = rest.split_whitespace();
+                let a = node(number)?;
+                let b = node(words.next().unwrap_or(""))?;
+                let weight = match words.next() {
+                    Some(w) => w
+                        .parse()
+                        .map_err(|_| FormatError::new(line, format!("invalid weight {w:?}")))?,
+                    None => W::ONE,
+                };
+                edges.push((a, b, weight, section == Section::Edges));
+            }
+            Section::ArcsList | Section::EdgesList => {
+                let a = node(number)?;
+                for word in rest.split_whitespace() {
+                    edges.push((a, node(word)?, W::ONE, section == Section::EdgesList));
+                }
+            }
+            Section::Other => {}
+        }
+    }
+
+    let names = names.unwrap_or_default();
+    let start = graph.add_nodes(names.len()).start;
+    for (a, b, weight, undirected) in edges {
+        graph.add_edge(a + start, b + start, weight);
+        if undirected && graph.is_directed() && a != b {
+            graph.add_edge(b + start, a + start, weight);
+        }
+    }
+    Ok(names)
+}
+
+/// Lê o rótulo no início de `text`, que pode estar entre aspas.
+fn label(text: &str, line: usize) -> Result<Option<String>, FormatError> {
+    if let Some(quoted) = text.strip_prefix('"') {
+        let (label, _) = quoted
+            .split_once('"')
+            .ok_or_else(|| FormatError::new(line, "unterminated label"))?;
+        return Ok(Some(label.to_string()));
+    }
+    Ok(text.split_whitespace().next().map(str::to_string))
+}
@@ -0,0 +1,158 @@
+use std::fmt::Write;
+use std::io;
+use std::path::Path;
+
+use crate::{node_ids, GraphRef, Layout, Num};
+
+/// Largura e altura da imagem, em pixels.
+const SIZE: f64 = 600.0;
+/// Espaço entre os nós mais afastados e a borda da imagem.
+const MARGIN: f64 = 40.0;
+const RADIUS: f64 = 16.0;
+
+/// Desenha `graph` em SVG, com os nós nas posições de `layout`.
+///
+/// Cada nó é um círculo com o seu número, começando em `1` como em [`crate::print_edges`], e cada
+/// aresta uma linha com o peso no meio. Em grafos direcionados, as arestas terminam em uma seta;
+/// nos não direcionados, cada aresta é desenhada uma única vez. Laços são desenhados como um
+/// pequeno círculo acima do nó.
+///
+/// As posições são ajustadas para ocupar toda a imagem, então a escala de `layout` não importa.
+/// Nós sem posição em `layout` não são desenhados, assim como as suas arestas.
+pub fn to_svg<W: Num>(graph: &dyn GraphRef<W>, layout: &Layout) -> String {
+    let directed = graph.is_directed();
+    // Ajusta as posições para que o retângulo que contém todos os nós ocupe a imagem.
+    let points: Vec<_> = node_ids(graph).filter_map(|n| layout.get(n)).collect();
+    let (min_x, max_x) = bounds(points.iter().map(|p| p.0));
+    let (min_y, max_y) = bounds(points.iter().map(|p| p.1));
+    let scale = (SIZE - 2.0 * MARGIN) / (max_x - min_x).max(max_y - min_y).max(f64::EPSILON);
+    let position = |n| {
+        layout.get(n).map(|&(x, y)| {
+            // Centraliza o desenho no eixo com menos variação.
+            let x = MARGIN
+                + (x - min_x) * scale
+                + (SIZE - 2.0 * MARGIN - (max_x - min_x) * scale) / 2.0;
+            let y = MARGIN
+                + (y - min_y) * scale
+                + (SIZE - 2.0 * MARGIN - (max_y - min_y) * scale) / 2.0;
+            (x, y)
+        })
+    };
+
+    // `write!` em uma `String` nunca falha, por isso os `unwrap`s.
+    let mut out = String::new();
+    writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{SIZE}\" height=\"{SIZE}\" \
+         viewBox=\"0 0 {SIZE} {SIZE}\" font-family=\"sans-serif\" font-size=\"14\">"
+    )
+    .unwrap();
+    if directed {
+        out.push_str(
+            "  <defs>\n    <marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+             markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\">\n      \
+             <path d=\"M 0 0 L 10 5 L 0 10 z\"/>\n    </marker>\n  </defs>\n",
+        );
+    }
+    let arrow = if directed {
+        " marker-end=\"url(#arrow)\""
+    } else {
+        ""
+    };
+
+    for (a, b, weight) in graph.edges() {
+        if !directed && a > b {
+            continue;
+        }
+        let (Some((x1, y1)), Some((x2, y2))) = (position(a), position(b)) else {
+            continue;
+        };
+        if a == b {
+            let (cx, cy) = (x1, y1 - RADIUS * 1.5);
+            writeln!(
+                out,
+                "  <circle cx=\"{cx:.1}\" cy=\"{cy:.1}\" r=\"{RADIUS}\" fill=\"none\" \
+                 stroke=\"black\"/>"
+            )
+            .unwrap();
+            label(&mut out, cx, cy - RADIUS - 4.0, &weight.to_string());
+            continue;
+        }
+        // A linha começa e termina na borda dos círculos, para que a seta fique visível.
+        let length = (x2 - x1).hypot(y2 - y1).max(f64::EPSILON);
+        let (dx, dy) = ((x2 - x1) / length * RADIUS, (y2 - y1) / length * RADIUS);
+        writeln!(
+            out,
+            "  <line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"black\"{arrow}/>",
+            x1 + dx,
+            y1 + dy,
+            x2 - dx,
+            y2 - dy
+        )
+        .unwrap();
+        // Em grafos direcionados, `a -> b` e `b -> a` ficam sobrepostas; o rótulo é deslocado
+        // para o lado de cada sentido para que os dois pesos continuem legíveis.
+        let (ox, oy) = if directed {
+            (-dy * 0.6, dx * 0.6)
+        } else {
+            (0.0, 0.0)
+        };
+        label(
+            &mut out,
+            (x1 + x2) / 2.0 + ox,
+            (y1 + y2) / 2.0 + oy - 4.0,
+            &weight.to_string(),
+        );
+    }
+
+    for n in node_ids(graph) {
+        let Some((x, y)) = position(n) else {
+            continue;
+        };
+        writeln!(
+            out,
+            "  <circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"{RADIUS}\" fill=\"white\" \
+             stroke=\"black\"/>"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "  <text x=\"{x:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>",
+            y + 5.0,
+            n + 1
+        )
+        .unwrap();
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Desenha `graph` com [`to_svg`] e salva a imagem no arquivo `path`.
+pub fn render_svg<W: Num>(
+    graph: &dyn GraphRef<W>,
+    layout: &Layout,
+    path: impl AsRef<Path>,
+) -> io::Result<()> {
+    std::fs::write(path, to_svg(graph, layout))
+}
+
+/// Retorna o menor e o maior valor de `values`, ou `(0, 0)` caso esteja vazio.
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values
+        .fold(None, |acc: Option<(f64, f64)>, v| {
+            Some(acc.map_or((v, v), |(min, max)| (min.min(v), max.max(v))))
+        })
+        .unwrap_or((0.0, 0.0))
+}
+
+/// Escreve um texto centralizado em `(x, y)`, com um contorno branco para ficar legível sobre as
+/// linhas.
+fn label(out: &mut String, x: f64, y: f64, text: &str) {
+    let text = text.replace('&', "&amp;").replace('<', "&lt;");
+    writeln!(
+        out,
+        "  <text x=\"{x:.1}\" y=\"{y:.1}\" text-anchor=\"middle\" stroke=\"white\" \
+         stroke-width=\"3\" paint-order=\"stroke\">{text}</text>"
+    )
+    .unwrap();
+}
@@ -0,0 +1,194 @@
+use crate::prelude::*;
+use crate::{node_ids, vec_bytes, Edge, GraphRef, Node, Num, SelfLoops, Weight};
+
+/// Listas de adjacência no formato CSR, como em [`crate::GraphCsr`]: os vizinhos de `n` ficam em
+/// `targets[offsets[n]..offsets[n + 1]]`, em ordem crescente.
+#[derive(Debug, Clone)]
+struct Adjacency<W> {
+    offsets: Vec<usize>,
+    targets: Vec<Node>,
+    weights: Vec<W>,
+}
+
+impl<W: Num> Adjacency<W> {
+    /// Constrói as listas a partir de `entries`, na forma `(origem, destino, peso)`, sem
+    /// repetições.
+    ///
+    /// Usa uma ordenação por contagem: conta as arestas de cada origem, acumula as contagens para
+    /// obter `offsets`, e então coloca cada aresta na próxima posição livre da sua origem. Se
+    /// `entries` estiver ordenado por destino, os destinos de cada origem também ficam ordenados.
+    fn build(slots: usize, entries: &[Edge<W>]) -> Self {
+        let mut offsets = vec![0; slots + 1];
+        for e in entries {
+            offsets[e.0 as usize + 1] += 1;
+        }
+        for n in 0..slots {
+            offsets[n + 1] += offsets[n];
+        }
+        let mut next = offsets.clone();
+        let mut targets = vec![0; entries.len()];
+        let mut weights = vec![W::ONE; entries.len()];
+        for &(a, b, weight) in entries {
+            let pos = &mut next[a as usize];
+            targets[*pos] = b;
+            weights[*pos] = weight;
+            *pos += 1;
+        }
+        Self {
+            offsets,
+            targets,
+            weights,
+        }
+    }
+    fn memory_usage(&self) -> usize {
+        vec_bytes(&self.offsets) + vec_bytes(&self.targets) + vec_bytes(&self.weights)
+    }
+    fn range(&self, n: Node) -> core::ops::Range<usize> {
+        let n = n as usize;
+        if n + 1 >= self.offsets.len() {
+            return 0..0;
+        }
+        self.offsets[n]..self.offsets[n + 1]
+    }
+    fn targets(&self, n: Node) -> &[Node] {
+        &self.targets[self.range(n)]
+    }
+    fn weights(&self, n: Node) -> &[W] {
+        &self.weights[self.range(n)]
+    }
+}
+
+/// Grafo imutável, otimizado para consultas. Criado por [`crate::GraphAdj::freeze`] ou
+/// [`FrozenGraph::new`].
+///
+/// Em troca de não poder ser modificado (implementa apenas [`GraphRef`]), guarda as arestas de
+/// forma compacta e contígua, como [`crate::GraphCsr`]. Em grafos direcionados, guarda também as
+/// arestas de entrada de cada nó, de forma que `in_neighbors`, `in_edges` e `in_degree` sejam tão
+/// rápidos quanto as versões de saída. Os graus são obtidos em O(1) a partir dos `offsets`.
+///
+/// Como todos os campos são vetores comuns, `FrozenGraph` é `Send` e `Sync` automaticamente.
+/// Assim, um mesmo grafo pode ser consultado por várias threads ao mesmo tempo, por exemplo
+/// dentro de um `Arc<FrozenGraph>`.
+#[derive(Debug, Clone)]
+pub struct FrozenGraph<W = Weight> {
+    outgoing: Adjacency<W>,
+    /// Arestas de entrada, agrupadas pelo destino. Só existe em grafos direcionados, já que nos
+    /// não direcionados as arestas de entrada são as mesmas de saída.
+    incoming: Option<Adjacency<W>>,
+    /// Indica, para cada identificador, se o nó existe. Nós removidos do grafo original continuam
+    /// ocupando uma posição.
+    exists: Vec<bool>,
+    node_count: usize,
+    directed: bool,
+    self_loops: SelfLoops,
+}
+
+impl<W: Num> FrozenGraph<W> {
+    /// Copia `graph` para um `FrozenGraph`, mantendo os identificadores dos nós.
+    pub fn new(graph: &dyn GraphRef<W>) -> Self {
+        let slots = node_ids(graph).last().map_or(0, |n| n as usize + 1);
+        let mut exists = vec![false; slots];
+        for n in node_ids(graph) {
+            exists[n as usize] = true;
+        }
+        let entries = graph.edges();
+        let outgoing = Adjacency::build(slots, &entries);
+        // Como `entries` está ordenado por origem, as origens de cada destino também ficam
+        // ordenadas.
+        let incoming = graph.is_directed().then(|| {
+            let reversed: Vec<_> = entries.iter().map(|&(a, b, w)| (b, a, w)).collect();
+            Adjacency::build(slots, &reversed)
+        });
+        Self {
+            outgoing,
+            incoming,
+            exists,
+            node_count: graph.node_count(),
+            directed: graph.is_directed(),
+            self_loops: graph.self_loops(),
+        }
+    }
+    /// Retorna os destinos das arestas que saem de `n`, em ordem crescente.
+    pub fn neighbor_slice(&self, n: Node) -> &[Node] {
+        self.outgoing.targets(n)
+    }
+    /// Retorna as origens das arestas que chegam em `n`, em ordem crescente.
+    pub fn in_neighbor_slice(&self, n: Node) -> &[Node] {
+        self.incoming().targets(n)
+    }
+    /// Retorna quantos bytes o grafo ocupa na heap, sem contar o próprio `FrozenGraph`.
+    pub fn memory_usage(&self) -> usize {
+        self.outgoing.memory_usage()
+            + self.incoming.as_ref().map_or(0, Adjacency::memory_usage)
+            + vec_bytes(&self.exists)
+    }
+
+    fn incoming(&self) -> &Adjacency<W> {
+        self.incoming.as_ref().unwrap_or(&self.outgoing)
+    }
+}
+
+impl<W: Num> GraphRef<W> for FrozenGraph<W> {
+    fn edges(&self) -> Vec<Edge<W>> {
+        self.edges_iter().collect()
+    }
+    fn edges_iter(&self) -> Box<dyn Iterator<Item = Edge<W>> + '_> {
+        Box::new((0..self.exists.len() as Node).flat_map(move |a| {
+            self.outgoing
+                .targets(a)
+                .iter()
+                .zip(self.outgoing.weights(a))
+                .map(move |(&b, &weight)| (a, b, weight))
+        }))
+    }
+    fn node_count(&self) -> usize {
+        self.node_count
+    }
+    fn has_node(&self, n: Node) -> bool {
+        self.exists.get(n as usize).copied().unwrap_or(false)
+    }
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+    fn self_loops(&self) -> SelfLoops {
+        self.self_loops
+    }
+    fn out_edges(&self, a: Node) -> Vec<Edge<W>> {
+        let (targets, weights) = (self.outgoing.targets(a), self.outgoing.weights(a));
+        targets
+            .iter()
+            .zip(weights)
+            .map(|(&b, &weight)| (a, b, weight))
+            .collect()
+    }
+    fn in_edges(&self, b: Node) -> Vec<Edge<W>> {
+        let incoming = self.incoming();
+        incoming
+            .targets(b)
+            .iter()
+            .zip(incoming.weights(b))
+            .map(|(&a, &weight)| (a, b, weight))
+            .collect()
+    }
+    fn get_edge_weight(&self, a: Node, b: Node) -> Option<W> {
+        let pos = self.outgoing.targets(a).binary_search(&b).ok()?;
+        Some(self.outgoing.weights(a)[pos])
+    }
+    fn neighbors(&self, n: Node) -> Box<dyn Iterator<Item = Node> + '_> {
+        Box::new(self.neighbor_slice(n).iter().copied())
+    }
+    fn in_neighbors(&self, n: Node) -> Box<dyn Iterator<Item = Node> + '_> {
+        Box::new(self.in_neighbor_slice(n).iter().copied())
+    }
+    fn for_each_neighbor(&self, n: Node, f: &mut dyn FnMut(Node)) {
+        for &b in self.neighbor_slice(n) {
+            f(b);
+        }
+    }
+    fn out_degree(&self, n: Node) -> usize {
+        self.outgoing.range(n).len()
+    }
+    fn in_degree(&self, n: Node) -> usize {
+        self.incoming().range(n).len()
+    }
+}
@@ -0,0 +1,121 @@
+use crate::prelude::*;
+use crate::{GraphAdj, GraphMut, GraphRef, Node, Num, Weight};
+
+/// Identificador de um nó de [`GenerationalGraph`], que guarda também a geração do nó.
+///
+/// A geração de uma posição aumenta toda vez que o nó nela é removido. Assim, caso o identificador
+/// seja reutilizado por um nó novo, os `NodeHandle`s antigos deixam de ser válidos, em vez de
+/// passarem a se referir ao nó novo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeHandle {
+    node: Node,
+    generation: u32,
+}
+
+impl NodeHandle {
+    /// Retorna o identificador do nó, sem verificar se ele ainda existe.
+    pub fn node(self) -> Node {
+        self.node
+    }
+    /// Retorna a geração do nó no momento em que o `NodeHandle` foi criado.
+    pub fn generation(self) -> u32 {
+        self.generation
+    }
+}
+
+/// Grafo que reutiliza os identificadores de nós removidos, mas detecta o uso de identificadores
+/// antigos.
+///
+/// Os nós são referenciados por [`NodeHandle`]s. Usar um `NodeHandle` de um nó removido causa um
+/// `panic` com uma mensagem clara, mesmo que o identificador já tenha sido reutilizado. Para
+/// verificar antes, use [`GenerationalGraph::resolve`].
+///
+/// Internamente, é um [`GraphAdj`] com [`GraphAdj::with_id_reuse`]. Consultas podem ser feitas
+/// com os métodos de [`GraphRef`], que usam os identificadores diretamente.
+#[derive(Debug, Clone)]
+pub struct GenerationalGraph<W = Weight> {
+    graph: GraphAdj<W>,
+    /// Geração atual de cada posição, indexada pelo identificador do nó.
+    generations: Vec<u32>,
+}
+
+impl<W: Num> Default for GenerationalGraph<W> {
+    fn default() -> Self {
+        Self {
+            graph: GraphAdj::default().with_id_reuse(),
+            generations: Vec::new(),
+        }
+    }
+}
+
+impl<W: Num> GenerationalGraph<W> {
+    /// Cria um grafo direcionado vazio.
+    pub fn directed() -> Self {
+        Self {
+            graph: GraphAdj::directed().with_id_reuse(),
+            generations: Vec::new(),
+        }
+    }
+    /// Retorna uma referência ao grafo envolvido.
+    pub fn graph(&self) -> &GraphAdj<W> {
+        &self.graph
+    }
+    /// Retorna o identificador do nó de `handle`, ou `None` caso ele tenha sido removido.
+    pub fn resolve(&self, handle: NodeHandle) -> Option<Node> {
+        let current = self.generations.get(handle.node as usize)?;
+        (*current == handle.generation && self.graph.has_node(handle.node)).then_some(handle.node)
+    }
+    /// Retorna o `NodeHandle` atual do nó `n`, caso ele exista.
+    pub fn handle(&self, n: Node) -> Option<NodeHandle> {
+        self.graph.has_node(n).then(|| NodeHandle {
+            node: n,
+            generation: self.generations[n as usize],
+        })
+    }
+    /// Adiciona um nó, possivelmente reutilizando o identificador de um nó removido.
+    pub fn add_node(&mut self) -> NodeHandle {
+        let node = self.graph.add_node();
+        if node as usize == self.generations.len() {
+            self.generations.push(0);
+        }
+        NodeHandle {
+            node,
+            generation: self.generations[node as usize],
+        }
+    }
+    /// Remove o nó de `handle` e as arestas que incidem nele.
+    ///
+    /// Causa um `panic` caso o nó já tenha sido removido.
+    pub fn remove_node(&mut self, handle: NodeHandle) {
+        let n = self.check(handle);
+        self.graph.remove_node(n);
+        self.generations[n as usize] += 1;
+    }
+    /// Adiciona uma aresta entre `a` e `b`. Veja [`GraphMut::add_edge`].
+    ///
+    /// Causa um `panic` caso algum dos nós tenha sido removido.
+    pub fn add_edge(&mut self, a: NodeHandle, b: NodeHandle, weight: W) {
+        let (a, b) = (self.check(a), self.check(b));
+        self.graph.add_edge(a, b, weight);
+    }
+    /// Remove a aresta entre `a` e `b`, retornando o seu peso caso ela existisse.
+    ///
+    /// Causa um `panic` caso algum dos nós tenha sido removido.
+    pub fn remove_edge(&mut self, a: NodeHandle, b: NodeHandle) -> Option<W> {
+        let (a, b) = (self.check(a), self.check(b));
+        self.graph.remove_edge(a, b)
+    }
+
+    fn check(&self, handle: NodeHandle) -> Node {
+        self.resolve(handle).unwrap_or_else(|| {
+            panic!(
+                "Tried to use stale handle to node {} (generation {}), which was removed",
+                handle.node, handle.generation
+            )
+        })
+    }
+}
+
+impl<W: Num> GraphRef<W> for GenerationalGraph<W> {
+    delegate_graph_queries!();
+}
@@ -0,0 +1,541 @@
+//! Geração de grafos, usados como entradas sintéticas para testes e comparações.
+//!
+//! Os geradores aleatórios recebem uma semente: a mesma semente sempre gera o mesmo grafo, o que
+//! torna os experimentos reproduzíveis. Os grafos clássicos, como [`complete`] e [`cycle`], não
+//! têm nada de aleatório, e servem como casos de teste com respostas conhecidas.
+
+use std::ops::Range;
+
+use crate::rng::Rng;
+use crate::{connected_components, node_ids, Edge, GraphMut, GraphRef, Node, Num, Set};
+
+/// Gera um grafo aleatório de Erdős–Rényi `G(n, p)`: cada um dos pares de nós distintos é ligado,
+/// de forma independente, com probabilidade `p`. Todas as arestas têm peso `1`.
+///
+/// O grafo pode ser de qualquer tipo que implemente [`GraphMut`], criado com `G::default()`, que é
+/// não direcionado em todas as representações deste crate. Por exemplo,
+/// `let graph: GraphCsr = gnp(1000, 0.01, 42);`. A mesma semente gera as mesmas arestas em todas
+/// as representações, então elas podem ser comparadas com a mesma entrada.
+///
+/// Em vez de sortear cada um dos n(n - 1)/2 pares, o algoritmo de Batagelj e Brandes sorteia
+/// quantos pares são pulados até a próxima aresta, que segue uma distribuição geométrica. Assim,
+/// o custo é O(n + m), onde m é a quantidade de arestas geradas, e grafos grandes e esparsos são
+/// gerados rapidamente.
+pub fn gnp<G, W>(n: usize, p: f64, seed: u64) -> G
+where
+    G: GraphMut<W> + Default,
+    W: Num,
+{
+    let mut graph = G::default();
+    graph.add_nodes(n);
+    if p <= 0.0 || n < 2 {
+        return graph;
+    }
+    if p >= 1.0 {
+        graph.add_edges_unweighted((0..n as Node).flat_map(|a| (0..a).map(move |b| (a, b))));
+        return graph;
+    }
+
+    let total = n as u64 * (n as u64 - 1) / 2;
+    graph.add_edges_unweighted(skip_sample(total, p, seed).map(pair_at));
+    graph
+}
+
+/// Sorteia cada posição de `0..total`, de forma independente, com probabilidade `p` entre 0 e 1
+/// (exclusive), retornando as sorteadas em ordem. Cada pulo até a próxima posição sorteada segue
+/// uma distribuição geométrica, e é sorteado de uma vez, como em [`gnp`].
+fn skip_sample(total: u64, p: f64, seed: u64) -> impl Iterator<Item = u64> {
+    let mut rng = Rng::new(seed);
+    let log_q = (1.0 - p).ln();
+    // A posição começa em -1 para que o primeiro pulo possa parar na posição 0.
+    let mut k = -1i128;
+    std::iter::from_fn(move || {
+        // Limita o pulo, que pode ser enorme com `p` muito pequeno, a mais que o total.
+        let skip = ((1.0 - rng.next_f64()).ln() / log_q).floor();
+        k += 1 + skip.min(total as f64) as i128;
+        (k < total as i128).then_some(k as u64)
+    })
+}
+
+/// Gera um grafo aleatório de Erdős–Rényi `G(n, m)`: são escolhidos, com a mesma probabilidade,
+/// `m` pares de nós distintos, que são ligados com peso `1`. Diferente de [`gnp`], a quantidade de
+/// arestas é exata.
+///
+/// Assim como em [`gnp`], o grafo é criado com `G::default()`.
+///
+/// Os pares são sorteados até que `m` pares diferentes sejam encontrados. Quando `m` é mais que
+/// a metade dos pares, os repetidos tornariam o sorteio lento, então são sorteados os pares que
+/// ficam de fora. O custo é O(m log m).
+///
+/// Causa um `panic` caso `m` seja maior que a quantidade de pares, n(n - 1)/2.
+pub fn gnm<G, W>(n: usize, m: usize, seed: u64) -> G
+where
+    G: GraphMut<W> + Default,
+    W: Num,
+{
+    let total = n as u64 * n.saturating_sub(1) as u64 / 2;
+    if m as u64 > total {
+        panic!("Tried to generate {m} edges, but {n} nodes only have {total} pairs");
+    }
+    let mut graph = G::default();
+    graph.add_nodes(n);
+
+    let mut rng = Rng::new(seed);
+    let complement = m as u64 > total / 2;
+    let wanted = if complement {
+        total - m as u64
+    } else {
+        m as u64
+    };
+    // Cada par é identificado pela sua posição na ordem de `pair_at`.
+    let mut chosen = Set::new();
+    while (chosen.len() as u64) < wanted {
+        chosen.insert(rng.next_u64() % total);
+    }
+    let pairs: Box<dyn Iterator<Item = u64>> = if complement {
+        Box::new((0..total).filter(|k| !chosen.contains(k)))
+    } else {
+        Box::new(chosen.iter().copied())
+    };
+    graph.add_edges_unweighted(pairs.map(pair_at));
+    graph
+}
+
+/// Retorna o par `(a, b)`, com `b < a`, na posição `k` da ordem `(1, 0)`, `(2, 0)`, `(2, 1)`,
+/// `(3, 0)`, usada por [`gnp`] e [`gnm`]. Antes dos pares de `a` vêm a(a - 1)/2 pares, então
+/// `a` é a raiz da equação do segundo grau, corrigida caso o arredondamento do `f64` erre por um.
+fn pair_at(k: u64) -> (Node, Node) {
+    let mut a = ((1.0 + (1.0 + 8.0 * k as f64).sqrt()) / 2.0) as u64;
+    while a * (a - 1) / 2 > k {
+        a -= 1;
+    }
+    while (a + 1) * a / 2 <= k {
+        a += 1;
+    }
+    (a as Node, (k - a * (a - 1) / 2) as Node)
+}
+
+/// Gera um grafo bipartido aleatório: os nós são divididos em um lado esquerdo, com `n_left` nós,
+/// e um direito, com `n_right`, e cada par com um nó de cada lado é ligado, de forma
+/// independente, com probabilidade `p`. Nenhuma aresta liga dois nós do mesmo lado, e todas têm
+/// peso `1`.
+///
+/// Retorna o grafo, criado com `G::default()`, e os nós de cada lado: os `n_left` primeiros nós
+/// formam o lado esquerdo, e os seguintes, o direito. Assim como em [`gnp`], os pares que não são
+/// ligados são pulados em vez de sorteados um a um, então o custo é O(V + E).
+pub fn bipartite<G, W>(
+    n_left: usize,
+    n_right: usize,
+    p: f64,
+    seed: u64,
+) -> (G, Range<Node>, Range<Node>)
+where
+    G: GraphMut<W> + Default,
+    W: Num,
+{
+    let mut graph = G::default();
+    let left = graph.add_nodes(n_left);
+    let right = graph.add_nodes(n_right);
+    let total = n_left as u64 * n_right as u64;
+    // As posições `k` percorrem os pares linha a linha: o par `k` liga o `k / n_right`-ésimo nó
+    // da esquerda ao `k % n_right`-ésimo da direita.
+    let pair = |k: u64| {
+        let (a, b) = (k / n_right as u64, k % n_right as u64);
+        (left.start + a as Node, right.start + b as Node)
+    };
+    if p >= 1.0 {
+        graph.add_edges_unweighted((0..total).map(pair));
+    } else if p > 0.0 {
+        graph.add_edges_unweighted(skip_sample(total, p, seed).map(pair));
+    }
+    (graph, left, right)
+}
+
+/// Adiciona a `graph` um grafo acíclico direcionado (DAG) aleatório com `n` nós, retornando os
+/// nós criados em uma ordem topológica. Todas as arestas têm peso `1`.
+///
+/// Os novos nós são embaralhados para formar uma ordem aleatória, e cada par de nós é ligado, com
+/// probabilidade `p`, por uma aresta do que vem antes para o que vem depois na ordem. Como nenhuma
+/// aresta volta na ordem, não há ciclos, e cada DAG com `n` nós e `m` arestas pode ser gerado. A
+/// ordem embaralhada evita que os nós menores sejam sempre as fontes, o que facilitaria demais a
+/// vida de algoritmos que percorrem os nós em ordem.
+///
+/// Diferente dos outros geradores, o grafo é recebido em vez de criado com `G::default()`, que é
+/// não direcionado. Por exemplo, `random_dag(&mut GraphAdj::directed(), 1000, 0.01, 42)`. Assim
+/// como em [`gnp`], o custo é O(V + E).
+///
+/// Causa um `panic` caso `graph` não seja direcionado.
+pub fn random_dag<W: Num>(graph: &mut impl GraphMut<W>, n: usize, p: f64, seed: u64) -> Vec<Node> {
+    if !graph.is_directed() {
+        panic!("Tried to generate a DAG in an undirected graph");
+    }
+    let mut order: Vec<_> = graph.add_nodes(n).collect();
+    let mut rng = Rng::new(seed);
+    rng.shuffle(&mut order);
+    // O par `(a, b)` de `pair_at`, com `b < a`, liga a posição `b` da ordem à posição `a`.
+    let total = n as u64 * n.saturating_sub(1) as u64 / 2;
+    let forward = |k: u64| {
+        let (a, b) = pair_at(k);
+        (order[b as usize], order[a as usize])
+    };
+    if p >= 1.0 {
+        graph.add_edges_unweighted((0..total).map(forward));
+    } else if p > 0.0 {
+        graph.add_edges_unweighted(skip_sample(total, p, rng.next_u64()).map(forward));
+    }
+    order
+}
+
+/// Define o que [`configuration_model`] faz quando o sorteio forma laços ou arestas repetidas, que
+/// os grafos deste crate não conseguem representar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Collisions {
+    /// Descarta os laços e as repetições. O grafo é gerado de uma vez, mas os nós envolvidos
+    /// ficam com grau menor que o pedido.
+    Simplify,
+    /// Sorteia tudo de novo, até `attempts` vezes, até que não haja laços nem repetições. Os graus
+    /// são exatamente os pedidos, mas sequências com graus muito altos podem nunca dar certo.
+    Retry { attempts: usize },
+}
+
+/// Gera um grafo aleatório com a sequência de graus `degrees`, pelo modelo de configuração: o nó
+/// `i` recebe `degrees[i]` "pontas soltas", que são embaralhadas e ligadas duas a duas. Todas as
+/// arestas têm peso `1`, e o grafo é criado com `G::default()`.
+///
+/// O resultado é um grafo com os mesmos graus de uma rede real, mas sem nenhuma outra estrutura,
+/// o que o torna um modelo nulo para comparações estatísticas: o que a rede real tiver de diferente
+/// não é explicado só pelos graus. O embaralhamento pode ligar um nó a si mesmo ou dois nós mais de
+/// uma vez, o que é tratado de acordo com `collisions`. Cada tentativa custa O(V + E log E).
+///
+/// Retorna `None` caso, com [`Collisions::Retry`], nenhuma tentativa tenha gerado um grafo sem
+/// colisões. Causa um `panic` caso a soma dos graus seja ímpar, já que cada aresta usa duas pontas.
+pub fn configuration_model<G, W>(degrees: &[usize], collisions: Collisions, seed: u64) -> Option<G>
+where
+    G: GraphMut<W> + Default,
+    W: Num,
+{
+    let total: usize = degrees.iter().sum();
+    if !total.is_multiple_of(2) {
+        panic!("Tried to generate a graph whose degrees sum to {total}, which is odd");
+    }
+    let mut stubs: Vec<Node> = (0..degrees.len() as Node)
+        .flat_map(|n| std::iter::repeat_n(n, degrees[n as usize]))
+        .collect();
+    let mut rng = Rng::new(seed);
+    let attempts = match collisions {
+        Collisions::Simplify => 1,
+        Collisions::Retry { attempts } => attempts,
+    };
+    for _ in 0..attempts {
+        rng.shuffle(&mut stubs);
+        let mut edges: Vec<_> = stubs
+            .chunks_exact(2)
+            .map(|pair| (pair[0].min(pair[1]), pair[0].max(pair[1])))
+            .collect();
+        edges.sort_unstable();
+        let len = edges.len();
+        edges.dedup();
+        edges.retain(|&(a, b)| a != b);
+        if collisions == Collisions::Simplify || edges.len() == len {
+            let mut graph = G::default();
+            graph.add_nodes(degrees.len());
+            graph.add_edges_unweighted(edges);
+            return Some(graph);
+        }
+    }
+    None
+}
+
+/// Gera o grafo completo com `n` nós, em que cada par de nós distintos é ligado por uma aresta de
+/// peso `1`.
+///
+/// Assim como nos geradores aleatórios, o grafo é criado com `G::default()`, e os nós são
+/// numerados de `0` a `n - 1` nos grafos a seguir.
+pub fn complete<G, W>(n: usize) -> G
+where
+    G: GraphMut<W> + Default,
+    W: Num,
+{
+    gnp(n, 1.0, 0)
+}
+
+/// Gera o caminho `0 - 1 - ... - (n - 1)`, com arestas de peso `1`.
+pub fn path<G, W>(n: usize) -> G
+where
+    G: GraphMut<W> + Default,
+    W: Num,
+{
+    let mut graph = G::default();
+    let nodes = graph.add_nodes(n);
+    graph.add_edges_unweighted(nodes.clone().zip(nodes.skip(1)));
+    graph
+}
+
+/// Gera o ciclo `0 - 1 - ... - (n - 1) - 0`, com arestas de peso `1`.
+///
+/// Com menos de três nós não há ciclo sem laços ou arestas repetidas, então o resultado é o mesmo
+/// de [`path`].
+pub fn cycle<G, W>(n: usize) -> G
+where
+    G: GraphMut<W> + Default,
+    W: Num,
+{
+    let mut graph: G = path(n);
+    if n >= 3 {
+        graph.add_edge(n as Node - 1, 0, W::ONE);
+    }
+    graph
+}
+
+/// Gera a estrela com `n` nós: o nó `0`, no centro, é ligado a todos os outros, com arestas de
+/// peso `1`.
+pub fn star<G, W>(n: usize) -> G
+where
+    G: GraphMut<W> + Default,
+    W: Num,
+{
+    let mut graph = G::default();
+    let nodes = graph.add_nodes(n);
+    graph.add_edges_unweighted(nodes.skip(1).map(|leaf| (0, leaf)));
+    graph
+}
+
+/// Gera a roda com `n` nós: uma estrela com centro no nó `0`, cujas pontas `1` a `n - 1` também
+/// formam um ciclo. Todas as arestas têm peso `1`.
+///
+/// Assim como em [`cycle`], as pontas só são fechadas em ciclo quando são pelo menos três.
+pub fn wheel<G, W>(n: usize) -> G
+where
+    G: GraphMut<W> + Default,
+    W: Num,
+{
+    let mut graph: G = star(n);
+    let rim = 1..n as Node;
+    graph.add_edges_unweighted(rim.clone().zip(rim.skip(1)));
+    if n >= 4 {
+        graph.add_edge(n as Node - 1, 1, W::ONE);
+    }
+    graph
+}
+
+/// Distribuição dos pesos sorteados por [`assign_weights`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WeightDistribution<W> {
+    /// Todas as arestas recebem o mesmo peso.
+    Constant(W),
+    /// Pesos no intervalo `[low, high)`, todos com a mesma probabilidade. Com inteiros, são os
+    /// valores de `low` a `high - 1`.
+    Uniform { low: W, high: W },
+    /// Quantidade de tentativas até o primeiro sucesso, quando cada uma tem sucesso com
+    /// probabilidade `p`: o peso `k` tem probabilidade `(1 - p)^(k - 1) p`. Os pesos começam em
+    /// `1`, e pesos pequenos são muito mais comuns que os grandes, com média `1 / p`.
+    Geometric(f64),
+}
+
+impl<W: Num> WeightDistribution<W> {
+    fn sample(&self, rng: &mut Rng) -> W {
+        match *self {
+            Self::Constant(weight) => weight,
+            Self::Uniform { low, high } => loop {
+                let weight = W::from_f64(low.to_f64() + rng.next_f64() * (high - low).to_f64());
+                // Com `next_f64` muito próximo de 1, o arredondamento pode chegar a `high`, e
+                // nesse caso o peso é sorteado de novo.
+                if weight < high {
+                    break weight;
+                }
+            },
+            Self::Geometric(p) => {
+                if p >= 1.0 {
+                    return W::ONE;
+                }
+                let trials = ((1.0 - rng.next_f64()).ln() / (1.0 - p).ln()).floor();
+                W::from_f64(1.0 + trials)
+            }
+        }
+    }
+}
+
+/// Substitui o peso de todas as arestas de `graph` por pesos sorteados de `distribution`.
+///
+/// Os geradores deste módulo criam só a estrutura do grafo, com pesos `1`. Com esta função, os
+/// mesmos grafos servem para comparar algoritmos de caminhos mínimos com pesos de várias
+/// distribuições. Em grafos não direcionados, os dois sentidos de cada aresta recebem o mesmo
+/// peso. As arestas são sorteadas em ordem, então a mesma semente gera os mesmos pesos para o
+/// mesmo grafo, em qualquer representação.
+///
+/// Causa um `panic` caso o intervalo de [`WeightDistribution::Uniform`] seja vazio ou tenha um
+/// limite infinito ou `NaN`, ou caso a probabilidade de [`WeightDistribution::Geometric`] não
+/// esteja em `(0, 1]`.
+pub fn assign_weights<W: Num>(
+    graph: &mut impl GraphMut<W>,
+    distribution: &WeightDistribution<W>,
+    seed: u64,
+) {
+    match *distribution {
+        // Com um limite `NaN` ou infinito, nenhum peso sorteado seria menor que `high`.
+        WeightDistribution::Uniform { low, high }
+            if !(low < high && low.to_f64().is_finite() && high.to_f64().is_finite()) =>
+        {
+            panic!("Tried to sample weights from the invalid range [{low}, {high})")
+        }
+        WeightDistribution::Geometric(p) if !(p > 0.0 && p <= 1.0) => {
+            panic!("Tried to sample weights with probability {p}, which is not in (0, 1]")
+        }
+        _ => {}
+    }
+    let directed = graph.is_directed();
+    let edges: Vec<_> = graph
+        .edges_iter()
+        .filter(|&(a, b, _)| directed || a <= b)
+        .collect();
+    let mut rng = Rng::new(seed);
+    for (a, b, _) in edges {
+        graph.set_edge_weight(a, b, distribution.sample(&mut rng));
+    }
+}
+
+/// Gera uma árvore aleatória com `n` nós, escolhida com a mesma probabilidade entre todas as
+/// n^(n - 2) árvores possíveis (a fórmula de Cayley). Todas as arestas têm peso `1`.
+///
+/// Cada árvore corresponde a exatamente uma _sequência de Prüfer_, de n - 2 nós quaisquer, então
+/// basta sortear a sequência e decodificá-la: cada nó aparece na sequência uma vez a menos que o
+/// seu grau, e a cada passo a menor folha ainda não usada é ligada ao próximo nó da sequência. A
+/// decodificação percorre os nós uma única vez, em O(n).
+pub fn random_tree<G, W>(n: usize, seed: u64) -> G
+where
+    G: GraphMut<W> + Default,
+    W: Num,
+{
+    let mut graph = G::default();
+    graph.add_nodes(n);
+    if n < 2 {
+        return graph;
+    }
+    let mut rng = Rng::new(seed);
+    let sequence: Vec<usize> = (0..n - 2)
+        .map(|_| (rng.next_u64() % n as u64) as usize)
+        .collect();
+    let mut degree = vec![1; n];
+    for &v in &sequence {
+        degree[v] += 1;
+    }
+
+    let mut edges = Vec::with_capacity(n - 1);
+    // `next` é a menor folha ainda não usada que está à frente de `leaf`. Quando `leaf` é ligada,
+    // o nó da sequência pode virar uma folha menor que `next`, e nesse caso é usado em seguida.
+    let mut next = degree.iter().position(|&d| d == 1).unwrap();
+    let mut leaf = next;
+    for &v in &sequence {
+        edges.push((leaf as Node, v as Node));
+        degree[v] -= 1;
+        if degree[v] == 1 && v < next {
+            leaf = v;
+        } else {
+            next += 1;
+            while degree[next] != 1 {
+                next += 1;
+            }
+            leaf = next;
+        }
+    }
+    edges.push((leaf as Node, n as Node - 1));
+    graph.add_edges_unweighted(edges);
+    graph
+}
+
+/// Sorteia uma árvore geradora de `graph`, com a mesma probabilidade entre todas as árvores
+/// geradoras, pelo algoritmo de Wilson. Retorna as arestas da árvore, ordenadas.
+///
+/// A árvore começa com um único nó, e cada um dos outros faz um passeio aleatório até chegar nela.
+/// Sempre que o passeio volta a um nó já visitado, o ciclo formado é apagado, guardando só a última
+/// aresta usada para sair de cada nó; o caminho que sobra entra na árvore. O custo esperado é o
+/// tempo médio que um passeio aleatório leva para chegar a um nó, O(V · E) no pior caso, mas bem
+/// menor na maioria dos grafos.
+///
+/// Assim como em [`crate::minimum_spanning_tree`], a direção das arestas é ignorada, e em grafos
+/// desconexos o resultado é uma floresta, com uma árvore sorteada para cada componente. Os laços
+/// nunca fazem parte da árvore, e as arestas nos dois sentidos entre os mesmos nós de um grafo
+/// direcionado contam como uma só.
+pub fn random_spanning_tree<W: Num>(graph: &dyn GraphRef<W>, seed: u64) -> Vec<Edge<W>> {
+    let mut edges: Vec<_> = graph
+        .edges_iter()
+        .filter(|&(a, b, _)| a != b && (graph.is_directed() || a < b))
+        .collect();
+    // Mantém a primeira aresta de cada par de nós, na ordem de `edges_iter`.
+    edges.sort_by_key(|&(a, b, _)| (a.min(b), a.max(b)));
+    edges.dedup_by_key(|&mut (a, b, _)| (a.min(b), a.max(b)));
+
+    let bound = node_ids(graph).last().map_or(0, |n| n as usize + 1);
+    let mut incident = vec![Vec::new(); bound];
+    for (i, &(a, b, _)) in edges.iter().enumerate() {
+        incident[a as usize].push(i);
+        incident[b as usize].push(i);
+    }
+    let other = |edge: usize, n: usize| {
+        let (a, b, _) = edges[edge];
+        if a as usize == n {
+            b as usize
+        } else {
+            a as usize
+        }
+    };
+
+    let mut rng = Rng::new(seed);
+    let mut in_tree = vec![false; bound];
+    // Índice em `edges` da última aresta usada pelo passeio para sair de cada nó.
+    let mut exit = vec![0; bound];
+    let mut tree = Vec::new();
+    for component in connected_components(graph) {
+        in_tree[component[0] as usize] = true;
+        for &start in &component[1..] {
+            let mut u = start as usize;
+            while !in_tree[u] {
+                let choices = &incident[u];
+                exit[u] = choices[(rng.next_u64() % choices.len() as u64) as usize];
+                u = other(exit[u], u);
+            }
+            // Refaz o passeio seguindo só as últimas saídas, o que já descarta os ciclos.
+            let mut u = start as usize;
+            while !in_tree[u] {
+                in_tree[u] = true;
+                tree.push(edges[exit[u]]);
+                u = other(exit[u], u);
+            }
+        }
+    }
+    tree.sort_by_key(|&(a, b, _)| (a, b));
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GraphAdj;
+
+    fn single_edge() -> GraphAdj<f64> {
+        let mut graph = GraphAdj::default();
+        graph.add_nodes(2);
+        graph.add_edge(0, 1, 1.0);
+        graph
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid range")]
+    fn uniform_rejects_nan_bounds() {
+        let distribution = WeightDistribution::Uniform {
+            low: 0.0,
+            high: f64::NAN,
+        };
+        assign_weights(&mut single_edge(), &distribution, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid range")]
+    fn uniform_rejects_infinite_bounds() {
+        let distribution = WeightDistribution::Uniform {
+            low: 0.0,
+            high: f64::INFINITY,
+        };
+        assign_weights(&mut single_edge(), &distribution, 1);
+    }
+}
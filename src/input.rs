@@ -0,0 +1,646 @@
+//! Leitura e escrita do formato de entrada do exercício.
+//!
+//! A primeira linha tem a quantidade de vértices e de arestas (`n m`), e cada uma das `m` linhas
+//! seguintes tem uma aresta no formato `a b w`, onde `a` e `b` são vértices, numerados a partir de
+//! `1`, e `w` é o peso da aresta. O peso pode ser omitido (`a b`), e nesse caso vale `1`, ou o peso
+//! definido em [`InputOptions::default_weight`].
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use crate::{
+    node_ids, Edge, GraphBuilder, GraphMut, GraphRef, InputProblem, Node, Num, ParseGraphError,
+    SelfLoops, Weight,
+};
+
+/// Opções de [`fill_graph_from_reader_with`].
+#[derive(Debug, Clone)]
+pub struct InputOptions {
+    /// Os vértices são numerados a partir de `0`, em vez de `1`.
+    pub zero_based: bool,
+    /// A entrada não tem a primeira linha (`n m`): todas as linhas são arestas.
+    ///
+    /// A quantidade de vértices é deduzida a partir do maior vértice encontrado, e os nós são
+    /// criados à medida que aparecem. Com os vértices começando em `1`, uma entrada cujo maior
+    /// vértice é `7` cria 7 nós. Vértices maiores que todos os usados nas arestas não têm como
+    /// aparecer, então não são criados.
+    pub headerless: bool,
+    /// Peso das arestas escritas sem peso (`a b`). Uma mesma entrada pode misturar linhas com e
+    /// sem peso.
+    pub default_weight: Weight,
+}
+
+impl Default for InputOptions {
+    fn default() -> Self {
+        Self {
+            zero_based: false,
+            headerless: false,
+            default_weight: 1,
+        }
+    }
+}
+
+/// Dado um vetor de linhas no formato "a b w", onde a e b são vértices e w é o peso da aresta
+/// entre eles, preenche o grafo `graph`. O peso pode ser omitido ("a b"), e nesse caso vale 1.
+///
+/// Linhas vazias são ignoradas, inclusive a que sobra ao separar um arquivo terminado em `\n`.
+///
+/// As arestas são verificadas antes de o grafo ser modificado. Caso a entrada seja inválida, por
+/// exemplo com uma aresta para um vértice que não existe, retorna um [`ParseGraphError`] com a
+/// linha e a descrição do problema, e `graph` não é modificado.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "fill_graph", level = "debug", skip_all)
+)]
+pub fn fill_graph(
+    input_data: &[Vec<u32>],
+    graph: &mut dyn GraphMut,
+) -> Result<(), ParseGraphError> {
+    // Numera as linhas a partir de 1, como nos editores de texto, e pula as vazias.
+    let mut rows = input_data
+        .iter()
+        .enumerate()
+        .map(|(i, row)| (i + 1, row))
+        .filter(|(_, row)| !row.is_empty());
+    let Some((line, head)) = rows.next() else {
+        return Err(ParseGraphError::MissingHeader { line: 1 });
+    };
+    let (vertex_count, edge_count) = parse_header(head, line)?;
+
+    // Cria `vertex_count` nós no construtor.
+    //
+    // O construtor adiciona os nós depois dos que `graph` já possui. Em um grafo vazio, os nós
+    // criados vão de `0` até `vertex_count - 1`. O construtor tem a mesma direção de `graph`, para
+    // que `a b` e `b a` não sejam tratadas como a mesma aresta em um grafo direcionado.
+    let base = if graph.is_directed() {
+        GraphBuilder::directed()
+    } else {
+        GraphBuilder::new()
+    };
+    let mut builder = base.with_self_loops(graph.self_loops());
+    builder.add_nodes(vertex_count as usize);
+
+    // Adiciona `edge_count` arestas ao construtor.
+    let mut found = 0;
+    for (line, edge_data) in rows.take(edge_count) {
+        let options = InputOptions::default();
+        let (a, b, weight) =
+            parse_edge(edge_data, line, vertex_count, graph.self_loops(), &options)?;
+        builder.add_edge(a, b, weight);
+        found += 1;
+    }
+    if found < edge_count {
+        return Err(ParseGraphError::MissingEdges {
+            expected: edge_count,
+            found,
+        });
+    }
+
+    trace_event!("input parsed", nodes = vertex_count, edges = edge_count);
+    // Todas as arestas já foram verificadas, então o construtor não tem como falhar.
+    builder
+        .build_into(graph)
+        .expect("As arestas já foram verificadas");
+    Ok(())
+}
+
+/// Igual a [`fill_graph`], mas lê a entrada diretamente de `reader`, uma linha por vez.
+///
+/// `fill_graph` precisa da entrada inteira já convertida em um `Vec<Vec<u32>>`, que ocupa mais
+/// memória que o próprio arquivo. Aqui, cada linha é lida, convertida e adicionada ao grafo antes
+/// da próxima, então apenas uma linha fica na memória por vez. Isso permite carregar arquivos
+/// maiores que a memória disponível para o texto.
+///
+/// Linhas vazias e comentários são ignorados e não contam como arestas. Comentários são linhas
+/// começando com `#` ou `%`, ou com a palavra `c`, como no formato DIMACS.
+///
+/// O custo é que as arestas não podem ser verificadas todas antes de modificar o grafo: caso a
+/// entrada seja inválida, retorna um [`ParseGraphError`] como `fill_graph`, mas as arestas
+/// anteriores ao erro já terão sido adicionadas. Erros de leitura de `reader` são retornados como
+/// [`ParseGraphError::Io`].
+pub fn fill_graph_from_reader(
+    reader: impl BufRead,
+    graph: &mut dyn GraphMut,
+) -> Result<(), ParseGraphError> {
+    fill_graph_from_reader_with(reader, graph, &InputOptions::default())
+}
+
+/// Igual a [`fill_graph_from_reader`], mas com as opções de [`InputOptions`].
+pub fn fill_graph_from_reader_with(
+    reader: impl BufRead,
+    graph: &mut dyn GraphMut,
+    options: &InputOptions,
+) -> Result<(), ParseGraphError> {
+    let lines = DataLines {
+        reader,
+        text: String::new(),
+        number: 0,
+        numbers: Vec::new(),
+    };
+    fill_lines(lines, graph, options)
+}
+
+/// Igual a [`fill_graph_from_reader_with`], mas lê a entrada de um texto já carregado na memória,
+/// como um arquivo mapeado com [`MappedFile`](crate::MappedFile).
+///
+/// Cada linha é lida diretamente de `input`, sem ser copiada para uma `String`, e os números de
+/// todas as linhas são convertidos para um mesmo vetor. Assim, a leitura não faz nenhuma alocação
+/// por linha, nem monta um `Vec<Vec<u32>>` como [`fill_graph`]. Linhas só com dígitos e espaços,
+/// que são quase todas, são convertidas byte a byte, sem passar por um `&str`. Erros e linhas
+/// ignoradas são os mesmos de `fill_graph_from_reader_with`, inclusive para bytes que não formam
+/// um texto UTF-8 válido, que resultam em um [`ParseGraphError::Io`].
+pub fn fill_graph_from_bytes(
+    input: &[u8],
+    graph: &mut dyn GraphMut,
+    options: &InputOptions,
+) -> Result<(), ParseGraphError> {
+    let lines = ByteLines {
+        rest: input,
+        number: 0,
+        numbers: Vec::new(),
+    };
+    fill_lines(lines, graph, options)
+}
+
+/// Preenche `graph` com as linhas de `lines`, para as funções de leitura acima.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "read_input",
+        level = "debug",
+        skip_all,
+        fields(headerless = options.headerless)
+    )
+)]
+fn fill_lines(
+    mut lines: impl NumberLines,
+    graph: &mut dyn GraphMut,
+    options: &InputOptions,
+) -> Result<(), ParseGraphError> {
+    if options.headerless {
+        return fill_headerless(lines, graph, options);
+    }
+    let Some((line, numbers)) = lines.next_numbers()? else {
+        return Err(ParseGraphError::MissingHeader { line: 1 });
+    };
+    let (vertex_count, edge_count) = parse_header(numbers, line)?;
+
+    let start = graph.add_nodes(vertex_count as usize).start;
+    for read in 0..edge_count {
+        let Some((line, numbers)) = lines.next_numbers()? else {
+            return Err(ParseGraphError::MissingEdges {
+                expected: edge_count,
+                found: read,
+            });
+        };
+        let self_loops = graph.self_loops();
+        let (a, b, weight) = parse_edge(numbers, line, vertex_count, self_loops, options)?;
+        graph.add_edge(a + start, b + start, weight);
+    }
+    trace_event!("input read", nodes = vertex_count, edges = edge_count);
+    Ok(())
+}
+
+/// Verifica toda a entrada, retornando os problemas encontrados, sem montar o grafo.
+///
+/// Diferente de [`fill_graph_from_reader_with`], que para no primeiro erro, a verificação
+/// continua após cada linha inválida, para que todos os problemas sejam mostrados de uma vez.
+/// Além dos erros que impedem a leitura, aponta arestas repetidas, laços, e linhas além das
+/// arestas prometidas na primeira linha, que a leitura aceita, mas costumam indicar um arquivo
+/// gerado incorretamente. As arestas são consideradas não direcionadas, então `a b` e `b a` são a
+/// mesma aresta.
+///
+/// Uma entrada sem problemas resulta em um vetor vazio. Caso a primeira linha seja inválida, as
+/// arestas não são verificadas, já que a quantidade de vértices é desconhecida.
+pub fn validate_input(
+    reader: impl BufRead,
+    options: &InputOptions,
+) -> io::Result<Vec<InputProblem>> {
+    let mut lines = DataLines {
+        reader,
+        text: String::new(),
+        number: 0,
+        numbers: Vec::new(),
+    };
+    let mut problems = Vec::new();
+    // Sem a primeira linha, qualquer vértice e qualquer quantidade de arestas são aceitos.
+    let (vertex_count, edge_count) = if options.headerless {
+        (Node::MAX, None)
+    } else {
+        let Some(line) = lines.next()? else {
+            problems.push(InputProblem::Error(ParseGraphError::MissingHeader {
+                line: 1,
+            }));
+            return Ok(problems);
+        };
+        match parse_numbers(&lines.text, line).and_then(|numbers| parse_header(&numbers, line)) {
+            Ok((vertex_count, edge_count)) => (vertex_count, Some(edge_count)),
+            Err(e) => {
+                problems.push(InputProblem::Error(e));
+                return Ok(problems);
+            }
+        }
+    };
+
+    let first = u32::from(!options.zero_based);
+    // Linha em que cada aresta apareceu pela primeira vez, com o menor vértice primeiro.
+    let mut seen = HashMap::new();
+    let mut read = 0;
+    while let Some(line) = lines.next()? {
+        if edge_count == Some(read) {
+            problems.push(InputProblem::ExtraEdges {
+                line,
+                expected: read,
+            });
+            break;
+        }
+        read += 1;
+        let edge = parse_numbers(&lines.text, line).and_then(|numbers| {
+            parse_edge(&numbers, line, vertex_count, SelfLoops::Allow, options)
+        });
+        let (a, b) = match edge {
+            Ok((a, b, _)) => (a, b),
+            Err(e) => {
+                problems.push(InputProblem::Error(e));
+                continue;
+            }
+        };
+        if a == b {
+            problems.push(InputProblem::SelfLoop {
+                line,
+                node: a + first,
+            });
+        }
+        if let Some(&earlier) = seen.get(&(a.min(b), a.max(b))) {
+            problems.push(InputProblem::DuplicateEdge {
+                line,
+                first: earlier,
+                a: a + first,
+                b: b + first,
+            });
+        } else {
+            seen.insert((a.min(b), a.max(b)), line);
+        }
+    }
+    if let Some(expected) = edge_count.filter(|&expected| read < expected) {
+        problems.push(InputProblem::Error(ParseGraphError::MissingEdges {
+            expected,
+            found: read,
+        }));
+    }
+    Ok(problems)
+}
+
+/// Lê uma entrada sem a primeira linha, para [`InputOptions::headerless`].
+fn fill_headerless(
+    mut lines: impl NumberLines,
+    graph: &mut dyn GraphMut,
+    options: &InputOptions,
+) -> Result<(), ParseGraphError> {
+    // Identificador do primeiro nó criado e quantos nós já foram criados para a entrada. Como os
+    // nós são criados em sequência, o vértice `v` da entrada é o nó `start + v`.
+    let mut start = 0;
+    let mut created = 0;
+    let mut edges = 0usize;
+    while let Some((line, numbers)) = lines.next_numbers()? {
+        let self_loops = graph.self_loops();
+        // Sem a quantidade de vértices, qualquer vértice é aceito.
+        let (a, b, weight) = parse_edge(numbers, line, Node::MAX, self_loops, options)?;
+        let needed = a.max(b) + 1;
+        if needed > created {
+            let nodes = graph.add_nodes((needed - created) as usize);
+            if created == 0 {
+                start = nodes.start;
+            }
+            created = needed;
+        }
+        graph.add_edge(a + start, b + start, weight);
+        edges += 1;
+    }
+    trace_event!("input read", nodes = created, edges = edges);
+    Ok(())
+}
+
+/// Linhas com dados de uma entrada, já convertidas em números.
+trait NumberLines {
+    /// Lê a próxima linha com dados, retornando o seu número e os seus números, ou `None` caso a
+    /// entrada tenha acabado. Os números ficam em um vetor reaproveitado entre as linhas.
+    fn next_numbers(&mut self) -> Result<Option<(usize, &[u32])>, ParseGraphError>;
+}
+
+/// Lê as linhas de `reader` que têm dados, pulando as vazias e os comentários.
+struct DataLines<R> {
+    reader: R,
+    /// Texto da última linha lida. A mesma `String` é reaproveitada para todas as linhas,
+    /// evitando uma alocação por linha.
+    text: String,
+    /// Número da última linha lida, começando em `1`.
+    number: usize,
+    /// Números da última linha lida, reaproveitados da mesma forma que `text`.
+    numbers: Vec<u32>,
+}
+
+impl<R: BufRead> DataLines<R> {
+    /// Lê a próxima linha com dados para `self.text`, retornando o seu número, ou `None` caso a
+    /// entrada tenha acabado.
+    fn next(&mut self) -> io::Result<Option<usize>> {
+        loop {
+            self.text.clear();
+            if self.reader.read_line(&mut self.text)? == 0 {
+                return Ok(None);
+            }
+            self.number += 1;
+            if has_data(&self.text) {
+                return Ok(Some(self.number));
+            }
+        }
+    }
+}
+
+impl<R: BufRead> NumberLines for DataLines<R> {
+    fn next_numbers(&mut self) -> Result<Option<(usize, &[u32])>, ParseGraphError> {
+        let Some(line) = self.next()? else {
+            return Ok(None);
+        };
+        parse_numbers_into(&self.text, line, &mut self.numbers)?;
+        Ok(Some((line, &self.numbers)))
+    }
+}
+
+/// Lê as linhas com dados de um texto na memória, sem copiá-las.
+struct ByteLines<'a> {
+    /// Parte do texto que ainda não foi lida.
+    rest: &'a [u8],
+    /// Número da última linha lida, começando em `1`.
+    number: usize,
+    numbers: Vec<u32>,
+}
+
+impl NumberLines for ByteLines<'_> {
+    fn next_numbers(&mut self) -> Result<Option<(usize, &[u32])>, ParseGraphError> {
+        while !self.rest.is_empty() {
+            let end = self.rest.iter().position(|&b| b == b'\n');
+            let (line, rest) = match end {
+                Some(end) => (&self.rest[..end], &self.rest[end + 1..]),
+                None => (self.rest, &[][..]),
+            };
+            self.rest = rest;
+            self.number += 1;
+            if parse_line_bytes(line, self.number, &mut self.numbers)? {
+                return Ok(Some((self.number, &self.numbers)));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Converte os números de uma linha em `numbers`, substituindo o seu conteúdo, e retorna se a
+/// linha tem dados (veja [`has_data`]).
+///
+/// Os dígitos são convertidos diretamente dos bytes. Qualquer outro caractere, como o de um
+/// comentário, um número inválido ou um byte que não é UTF-8, faz com que a linha seja convertida
+/// de novo por [`parse_numbers_into`], para que o resultado e os erros sejam exatamente os mesmos.
+pub(crate) fn parse_line_bytes(
+    line: &[u8],
+    number: usize,
+    numbers: &mut Vec<u32>,
+) -> Result<bool, ParseGraphError> {
+    numbers.clear();
+    let mut value: Option<u32> = None;
+    for &byte in line {
+        match byte {
+            b'0'..=b'9' => {
+                let digit = u32::from(byte - b'0');
+                value = value
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add(digit));
+                if value.is_none() {
+                    // O número não cabe em um `u32`.
+                    return parse_line_text(line, number, numbers);
+                }
+            }
+            b' ' | b'\t' | b'\r' => numbers.extend(value.take()),
+            _ => return parse_line_text(line, number, numbers),
+        }
+    }
+    numbers.extend(value);
+    Ok(!numbers.is_empty())
+}
+
+/// Caminho lento de [`parse_line_bytes`], que converte a linha como texto.
+fn parse_line_text(
+    line: &[u8],
+    number: usize,
+    numbers: &mut Vec<u32>,
+) -> Result<bool, ParseGraphError> {
+    // Assim como em `read_line`, um texto que não é UTF-8 é um erro de leitura.
+    let text = std::str::from_utf8(line).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "stream did not contain valid UTF-8",
+        )
+    })?;
+    if !has_data(text) {
+        numbers.clear();
+        return Ok(false);
+    }
+    parse_numbers_into(text, number, numbers)?;
+    Ok(true)
+}
+
+/// Indica se a linha tem dados, ou seja, se não está vazia e não é um comentário.
+pub(crate) fn has_data(text: &str) -> bool {
+    match text.split_whitespace().next() {
+        None | Some("c") => false,
+        Some(word) => !word.starts_with(['#', '%']),
+    }
+}
+
+/// Escreve `graph` em `out` no formato de entrada, de forma que possa ser lido de volta com
+/// [`fill_graph_from_reader`] ou pelos outros programas do exercício.
+///
+/// A primeira linha tem a quantidade de vértices e de arestas, e cada aresta é escrita como
+/// `a b w`, com os vértices começando em `1`. Em grafos não direcionados, cada aresta aparece uma
+/// única vez, já que a leitura adiciona os dois sentidos. O formato não tem buracos na numeração,
+/// então os nós são renumerados na ordem dos seus identificadores; em um grafo sem nós removidos,
+/// o nó `n` vira o vértice `n + 1`.
+pub fn write_input_format<W: Num>(graph: &dyn GraphRef<W>, mut out: impl Write) -> io::Result<()> {
+    let mut index = vec![0; node_ids(graph).last().map_or(0, |n| n as usize + 1)];
+    for (i, n) in node_ids(graph).enumerate() {
+        index[n as usize] = i + 1;
+    }
+    writeln!(out, "{} {}", graph.node_count(), graph.edge_count())?;
+    for (a, b, weight) in graph.edges_iter() {
+        if graph.is_directed() || a <= b {
+            writeln!(out, "{} {} {weight}", index[a as usize], index[b as usize])?;
+        }
+    }
+    // Garante que tudo foi escrito, caso `out` seja um `BufWriter`.
+    out.flush()
+}
+
+/// Converte os números de uma linha da entrada, separados por espaços.
+pub(crate) fn parse_numbers(text: &str, line: usize) -> Result<Vec<u32>, ParseGraphError> {
+    let mut numbers = Vec::new();
+    parse_numbers_into(text, line, &mut numbers)?;
+    Ok(numbers)
+}
+
+/// Igual a [`parse_numbers`], mas escreve os números em `numbers`, substituindo o seu conteúdo,
+/// para que o mesmo vetor possa ser reaproveitado entre as linhas.
+fn parse_numbers_into(
+    text: &str,
+    line: usize,
+    numbers: &mut Vec<u32>,
+) -> Result<(), ParseGraphError> {
+    numbers.clear();
+    for num in text.split_whitespace() {
+        numbers.push(num.parse().map_err(|_| ParseGraphError::InvalidNumber {
+            line,
+            text: num.to_string(),
+        })?);
+    }
+    Ok(())
+}
+
+/// Lê a primeira linha, retornando a quantidade de vértices e de arestas.
+pub(crate) fn parse_header(numbers: &[u32], line: usize) -> Result<(u32, usize), ParseGraphError> {
+    // Tenta desestruturar o vetor em dois valores, executando o `else` caso não seja possível.
+    let [vertex_count, edge_count] = numbers[..] else {
+        return Err(ParseGraphError::MissingHeader { line });
+    };
+    // Converte `edge_count` para `usize` para indexação.
+    //
+    // `usize` é um inteiro positivo cujo tamanho é definido pela arquitetura,
+    // comummente utilizado para indexação.
+    Ok((vertex_count, edge_count as usize))
+}
+
+/// Lê uma linha de aresta, retornando-a com os vértices já começando em `0`.
+pub(crate) fn parse_edge(
+    numbers: &[u32],
+    line: usize,
+    vertex_count: u32,
+    self_loops: SelfLoops,
+    options: &InputOptions,
+) -> Result<Edge, ParseGraphError> {
+    // Linhas com apenas dois valores são arestas sem peso, que recebem o peso padrão.
+    let (a, b, weight) = match numbers[..] {
+        [a, b, weight] => (a, b, weight),
+        [a, b] => (a, b, options.default_weight),
+        _ => {
+            return Err(ParseGraphError::WrongFieldCount {
+                line,
+                found: numbers.len(),
+            })
+        }
+    };
+    // Como os nós são crescentes e começam em 0, precisamos subtrair 1 dos identificadores das
+    // entradas, a não ser que elas também comecem em 0.
+    let first = u32::from(!options.zero_based);
+    let node = |node: u32| {
+        let Some(n) = node.checked_sub(first) else {
+            return Err(ParseGraphError::ZeroVertex { line });
+        };
+        if n >= vertex_count {
+            return Err(ParseGraphError::EndpointOutOfRange {
+                line,
+                node,
+                vertex_count,
+            });
+        }
+        Ok(n)
+    };
+    let (a, b) = (node(a)?, node(b)?);
+    if a == b && self_loops == SelfLoops::Reject {
+        return Err(ParseGraphError::RejectedSelfLoop {
+            line,
+            node: a + first,
+        });
+    }
+    Ok((a, b, weight))
+}
+
+/// Abre o arquivo de entrada `path` para ser lido com [`fill_graph_from_reader`].
+///
+/// O nome `-` representa a entrada padrão (stdin), como em muitos programas de linha de comando.
+/// Assim, a entrada pode vir de outro programa: `zcat grafo.gz | ex1 -`.
+///
+/// Com as features `gzip` e `zstd`, entradas comprimidas nesses formatos são descomprimidas
+/// durante a leitura, sem precisar de um arquivo temporário. O formato é detectado pelos primeiros
+/// bytes da entrada, e não pela extensão, então também funciona com a entrada padrão. Sem a
+/// feature correspondente, uma entrada comprimida resulta em um erro.
+pub fn open_input(path: impl AsRef<Path>) -> io::Result<Box<dyn BufRead>> {
+    let path = path.as_ref();
+    let mut input: Box<dyn BufRead> = if path == Path::new("-") {
+        // `lock` dá acesso exclusivo à entrada padrão, que já tem o seu próprio buffer.
+        Box::new(io::stdin().lock())
+    } else {
+        Box::new(BufReader::new(File::open(path)?))
+    };
+    // `fill_buf` permite olhar os primeiros bytes sem consumi-los.
+    let magic = input.fill_buf()?;
+    if magic.starts_with(GZIP_MAGIC) {
+        return decompress_gzip(input);
+    }
+    if magic.starts_with(ZSTD_MAGIC) {
+        return decompress_zstd(input);
+    }
+    Ok(input)
+}
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// Indica se `input` começa com a assinatura de um dos formatos comprimidos que [`open_input`]
+/// reconhece. Útil para entradas que não passam por ela, como as lidas com
+/// [`fill_graph_from_bytes`], que não são descomprimidas.
+pub fn is_compressed(input: &[u8]) -> bool {
+    input.starts_with(GZIP_MAGIC) || input.starts_with(ZSTD_MAGIC)
+}
+
+#[cfg(feature = "gzip")]
+fn decompress_gzip(input: Box<dyn BufRead>) -> io::Result<Box<dyn BufRead>> {
+    // `MultiGzDecoder` também aceita arquivos com vários membros, como os gerados por `pigz`.
+    let decoder = flate2::bufread::MultiGzDecoder::new(input);
+    Ok(Box::new(BufReader::new(decoder)))
+}
+
+#[cfg(not(feature = "gzip"))]
+fn decompress_gzip(_: Box<dyn BufRead>) -> io::Result<Box<dyn BufRead>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "the input is gzip-compressed, but the `gzip` feature is disabled",
+    ))
+}
+
+#[cfg(feature = "zstd")]
+fn decompress_zstd(input: Box<dyn BufRead>) -> io::Result<Box<dyn BufRead>> {
+    let decoder = zstd::stream::read::Decoder::with_buffer(input)?;
+    Ok(Box::new(BufReader::new(decoder)))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn decompress_zstd(_: Box<dyn BufRead>) -> io::Result<Box<dyn BufRead>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "the input is zstd-compressed, but the `zstd` feature is disabled",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GraphAdj;
+
+    #[test]
+    fn fill_graph_keeps_both_directions() {
+        let input = [vec![2, 2], vec![1, 2, 5], vec![2, 1, 7]];
+        let mut graph = GraphAdj::directed();
+        fill_graph(&input, &mut graph).unwrap();
+        assert_eq!(graph.edges(), [(0, 1, 5), (1, 0, 7)]);
+    }
+}
@@ -0,0 +1,214 @@
+use core::ops::Range;
+
+use crate::prelude::*;
+use crate::{Edge, GraphAdj, GraphMut, GraphRef, Map, Node, Num, Weight};
+
+/// Posição no histórico de um [`JournaledGraph`], criada por [`JournaledGraph::checkpoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checkpoint(usize);
+
+/// Uma modificação feita no grafo, com o que é preciso para desfazê-la.
+#[derive(Debug, Clone)]
+enum Change<W> {
+    /// Um nó foi criado. `reused` indica se o seu identificador veio da lista de identificadores
+    /// livres.
+    AddNode {
+        node: Node,
+        reused: bool,
+    },
+    AddNodes(Range<Node>),
+    /// O peso da aresta `a -> b` foi definido. `previous` é o peso anterior, caso ela já existisse.
+    SetEdge {
+        a: Node,
+        b: Node,
+        previous: Option<W>,
+    },
+    RemoveEdge(Edge<W>),
+    /// Um nó foi removido. `edges` são todas as posições em que ele aparecia nas listas: a sua
+    /// própria lista e as arestas dos outros nós que apontavam para ele.
+    RemoveNode {
+        node: Node,
+        edges: Vec<Edge<W>>,
+    },
+    Reverse,
+    /// `compact` e `clear` reorganizam o grafo inteiro, então guardamos uma cópia dele.
+    Replace(GraphAdj<W>),
+}
+
+/// Grafo que registra as suas modificações, permitindo desfazê-las.
+///
+/// Algoritmos de busca, como coloração por _branch and bound_ ou a procura de um ciclo
+/// hamiltoniano, costumam modificar o grafo de forma provisória e voltar atrás caso o caminho não
+/// dê certo. Clonar o grafo inteiro a cada tentativa custa O(V + E); com um `JournaledGraph`, basta
+/// guardar um [`Checkpoint`] antes e chamar [`JournaledGraph::rollback`] depois, pagando apenas
+/// pelas modificações feitas no meio.
+///
+/// Envolve um [`GraphAdj`] e implementa [`GraphMut`], então pode ser usado no lugar dele. Desfazer
+/// as modificações restaura o grafo exatamente, inclusive os identificadores dos nós.
+#[derive(Debug, Clone)]
+pub struct JournaledGraph<W = Weight> {
+    graph: GraphAdj<W>,
+    journal: Vec<Change<W>>,
+}
+
+impl<W: Num> Default for JournaledGraph<W> {
+    fn default() -> Self {
+        Self::new(GraphAdj::default())
+    }
+}
+
+impl<W: Num> JournaledGraph<W> {
+    /// Envolve o grafo `graph`, com o histórico vazio.
+    pub fn new(graph: GraphAdj<W>) -> Self {
+        Self {
+            graph,
+            journal: Vec::new(),
+        }
+    }
+    /// Retorna uma referência ao grafo envolvido.
+    pub fn graph(&self) -> &GraphAdj<W> {
+        &self.graph
+    }
+    /// Descarta o histórico e retorna o grafo envolvido.
+    pub fn into_graph(self) -> GraphAdj<W> {
+        self.graph
+    }
+    /// Retorna um [`Checkpoint`] para o estado atual do grafo.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.journal.len())
+    }
+    /// Desfaz todas as modificações feitas depois de `checkpoint`, da mais recente para a mais
+    /// antiga.
+    ///
+    /// Os checkpoints criados depois de `checkpoint` deixam de ser válidos. Causa um `panic` caso
+    /// `checkpoint` já não seja válido.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        if checkpoint.0 > self.journal.len() {
+            panic!(
+                "Tried to roll back to checkpoint {}, but the journal only has {} changes",
+                checkpoint.0,
+                self.journal.len()
+            );
+        }
+        while self.journal.len() > checkpoint.0 {
+            let change = self.journal.pop().unwrap();
+            self.undo(change);
+        }
+    }
+    /// Descarta o histórico, tornando as modificações feitas até agora definitivas e liberando a
+    /// memória usada por ele. Todos os checkpoints deixam de ser válidos.
+    pub fn commit(&mut self) {
+        self.journal.clear();
+    }
+
+    fn undo(&mut self, change: Change<W>) {
+        let graph = &mut self.graph;
+        match change {
+            Change::AddNode { node, reused } => {
+                graph.node_edges.remove(&node);
+                if reused {
+                    graph.free_ids.insert(node);
+                } else {
+                    graph.next_node = node;
+                }
+            }
+            Change::AddNodes(nodes) => {
+                for node in nodes.clone() {
+                    graph.node_edges.remove(&node);
+                }
+                graph.next_node = nodes.start;
+            }
+            Change::SetEdge { a, b, previous } => match previous {
+                Some(weight) => graph.add_edge(a, b, weight),
+                None => {
+                    graph.remove_edge(a, b);
+                }
+            },
+            Change::RemoveEdge((a, b, weight)) => graph.add_edge(a, b, weight),
+            Change::RemoveNode { node, edges } => {
+                graph.free_ids.remove(&node);
+                graph.node_edges.insert(node, Default::default());
+                // Reinsere cada posição diretamente, em vez de usar `add_edge`, para não espelhar
+                // de novo as arestas de grafos não direcionados.
+                for edge in edges {
+                    graph.insert_entry(edge);
+                }
+            }
+            Change::Reverse => graph.reverse(),
+            Change::Replace(previous) => *graph = previous,
+        }
+    }
+}
+
+impl<W: Num> GraphRef<W> for JournaledGraph<W> {
+    delegate_graph_queries!();
+}
+
+impl<W: Num> GraphMut<W> for JournaledGraph<W> {
+    fn add_node(&mut self) -> Node {
+        let reused = self.graph.reuse_ids && !self.graph.free_ids.is_empty();
+        let node = self.graph.add_node();
+        self.journal.push(Change::AddNode { node, reused });
+        node
+    }
+    fn add_nodes(&mut self, count: usize) -> Range<Node> {
+        let nodes = self.graph.add_nodes(count);
+        self.journal.push(Change::AddNodes(nodes.clone()));
+        nodes
+    }
+    fn add_edge(&mut self, a: Node, b: Node, weight: W) {
+        let previous = self.graph.get_edge_weight(a, b);
+        self.graph.add_edge(a, b, weight);
+        self.journal.push(Change::SetEdge { a, b, previous });
+    }
+    fn add_edges<I>(&mut self, edges: I)
+    where
+        I: IntoIterator<Item = Edge<W>>,
+    {
+        // Os pesos anteriores são lidos antes de qualquer inserção. Mesmo que uma aresta apareça
+        // mais de uma vez, desfazer as mudanças em ordem inversa termina no peso original.
+        let edges: Vec<_> = edges.into_iter().collect();
+        let changes: Vec<_> = edges
+            .iter()
+            .map(|&(a, b, _)| Change::SetEdge {
+                a,
+                b,
+                previous: self.graph.get_edge_weight(a, b),
+            })
+            .collect();
+        self.graph.add_edges(edges);
+        self.journal.extend(changes);
+    }
+    fn remove_node(&mut self, n: Node) {
+        let mut edges = self.graph.out_edges(n);
+        for (&a, list) in &self.graph.node_edges {
+            if a != n {
+                edges.extend(list.iter().filter(|e| e.1 == n));
+            }
+        }
+        self.graph.remove_node(n);
+        self.journal.push(Change::RemoveNode { node: n, edges });
+    }
+    fn remove_edge(&mut self, a: Node, b: Node) -> Option<W> {
+        let weight = self.graph.remove_edge(a, b)?;
+        self.journal.push(Change::RemoveEdge((a, b, weight)));
+        Some(weight)
+    }
+    fn reverse(&mut self) {
+        if self.graph.is_directed() {
+            self.graph.reverse();
+            self.journal.push(Change::Reverse);
+        }
+    }
+    fn compact(&mut self) -> Map<Node, Node> {
+        let previous = self.graph.clone();
+        let mapping = self.graph.compact();
+        self.journal.push(Change::Replace(previous));
+        mapping
+    }
+    fn clear(&mut self) {
+        let previous = self.graph.clone();
+        self.graph.clear();
+        self.journal.push(Change::Replace(previous));
+    }
+}
@@ -0,0 +1,145 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Range;
+
+use crate::{Edge, GraphAdj, GraphMut, GraphRef, Map, Node, Num};
+
+/// Grafo cujos nós são identificados por chaves arbitrárias, como `&str` ou ids vindos de um banco
+/// de dados, em vez de inteiros sequenciais.
+///
+/// Internamente, cada chave é associada a um [`Node`] do grafo envolvido (por padrão,
+/// [`GraphAdj`]) na primeira vez em que aparece. Esse processo é chamado de _interning_. Assim, os
+/// algoritmos continuam trabalhando com nós densos, e as chaves só são usadas na entrada e saída.
+///
+/// `KeyedGraph` também implementa [`GraphMut`]. Nós criados diretamente por `add_node` não possuem
+/// chave.
+#[derive(Debug, Clone)]
+pub struct KeyedGraph<K, G = GraphAdj> {
+    graph: G,
+    nodes: HashMap<K, Node>,
+    keys: Map<Node, K>,
+}
+
+impl<K, G: Default> Default for KeyedGraph<K, G> {
+    fn default() -> Self {
+        Self::new(G::default())
+    }
+}
+
+impl<K, G> KeyedGraph<K, G> {
+    /// Envolve o grafo `graph`. Os nós já existentes começam sem chave.
+    pub fn new(graph: G) -> Self {
+        Self {
+            graph,
+            nodes: HashMap::new(),
+            keys: Map::new(),
+        }
+    }
+    /// Retorna uma referência ao grafo envolvido.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+    /// Descarta as chaves e retorna o grafo envolvido.
+    pub fn into_graph(self) -> G {
+        self.graph
+    }
+    /// Retorna a chave do nó `n`, caso ele tenha uma.
+    pub fn key_of(&self, n: Node) -> Option<&K> {
+        self.keys.get(&n)
+    }
+}
+
+// `Borrow` permite buscar por um tipo "emprestado" da chave. Por exemplo, em um
+// `KeyedGraph<String>`, podemos buscar usando um `&str`, sem precisar criar uma `String`.
+impl<K: Hash + Eq + Clone, G> KeyedGraph<K, G> {
+    /// Retorna o nó associado à chave `key`, caso exista.
+    pub fn node_of<Q>(&self, key: &Q) -> Option<Node>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.nodes.get(key).copied()
+    }
+    /// Retorna o nó associado à chave `key`, criando um novo nó caso ela ainda não exista.
+    pub fn intern<W: Num>(&mut self, key: K) -> Node
+    where
+        G: GraphMut<W>,
+    {
+        if let Some(&node) = self.nodes.get(&key) {
+            return node;
+        }
+        let node = self.graph.add_node();
+        self.nodes.insert(key.clone(), node);
+        self.keys.insert(node, key);
+        node
+    }
+    /// Adiciona uma aresta entre as chaves `a` e `b`, criando os nós que ainda não existirem.
+    pub fn add_edge_keys<W: Num>(&mut self, a: K, b: K, weight: W)
+    where
+        G: GraphMut<W>,
+    {
+        let a = self.intern(a);
+        let b = self.intern(b);
+        self.graph.add_edge(a, b, weight);
+    }
+    /// Remove o nó associado à chave `key`, retornando-o caso existisse.
+    pub fn remove_key<W: Num, Q>(&mut self, key: &Q) -> Option<Node>
+    where
+        G: GraphMut<W>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let node = self.node_of(key)?;
+        self.remove_node(node);
+        Some(node)
+    }
+}
+
+impl<K: Hash + Eq, W: Num, G: GraphRef<W>> GraphRef<W> for KeyedGraph<K, G> {
+    delegate_graph_queries!();
+}
+
+impl<K: Hash + Eq, W: Num, G: GraphMut<W>> GraphMut<W> for KeyedGraph<K, G> {
+    fn add_node(&mut self) -> Node {
+        self.graph.add_node()
+    }
+    fn add_nodes(&mut self, count: usize) -> Range<Node> {
+        self.graph.add_nodes(count)
+    }
+    fn add_edge(&mut self, a: Node, b: Node, weight: W) {
+        self.graph.add_edge(a, b, weight)
+    }
+    fn add_edges<I>(&mut self, edges: I)
+    where
+        I: IntoIterator<Item = Edge<W>>,
+    {
+        self.graph.add_edges(edges)
+    }
+    fn remove_node(&mut self, n: Node) {
+        self.graph.remove_node(n);
+        if let Some(key) = self.keys.remove(&n) {
+            self.nodes.remove(&key);
+        }
+    }
+    fn clear(&mut self) {
+        self.graph.clear();
+        self.nodes.clear();
+        self.keys.clear();
+    }
+    fn compact(&mut self) -> Map<Node, Node> {
+        let mapping = self.graph.compact();
+        for node in self.nodes.values_mut() {
+            *node = mapping[node];
+        }
+        let keys = std::mem::take(&mut self.keys);
+        self.keys = keys.into_iter().map(|(n, k)| (mapping[&n], k)).collect();
+        mapping
+    }
+    fn remove_edge(&mut self, a: Node, b: Node) -> Option<W> {
+        self.graph.remove_edge(a, b)
+    }
+    fn reverse(&mut self) {
+        self.graph.reverse()
+    }
+}
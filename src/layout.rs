@@ -0,0 +1,100 @@
+//! Posicionamento dos nós de um grafo no plano, usado para desenhá-lo com [`crate::to_svg`].
+
+use std::f64::consts::TAU;
+
+use crate::rng::Rng;
+use crate::{node_ids, GraphRef, Map, Node, Num, PropertyMap};
+
+/// Posição `(x, y)` de cada nó. As coordenadas podem estar em qualquer escala, já que o desenho é
+/// ajustado para caber na imagem.
+///
+/// Como é um [`PropertyMap`], as posições calculadas podem ser ajustadas à mão com
+/// [`PropertyMap::insert`] antes de desenhar o grafo.
+pub type Layout = PropertyMap<(f64, f64)>;
+
+/// Posiciona os nós de `graph` em um círculo, em ordem crescente e no sentido horário, começando
+/// pelo topo.
+///
+/// É o posicionamento mais simples que nunca coloca dois nós no mesmo lugar, e funciona bem para
+/// grafos pequenos, como os dos exercícios.
+pub fn circular_layout<W: Num>(graph: &dyn GraphRef<W>) -> Layout {
+    let count = graph.node_count() as f64;
+    let mut layout = Layout::new();
+    for (i, n) in node_ids(graph).enumerate() {
+        // No SVG, o eixo y cresce para baixo, então o ângulo crescente segue o sentido horário.
+        let angle = TAU * i as f64 / count - TAU / 4.0;
+        layout.insert(n, (angle.cos(), angle.sin()));
+    }
+    layout
+}
+
+/// Posiciona os nós de `graph` com o algoritmo de Fruchterman e Reingold, que simula os nós como
+/// partículas que se repelem e as arestas como molas que puxam os seus nós.
+///
+/// Começa com posições aleatórias, sorteadas a partir de `seed`, e move os nós por `iterations`
+/// rodadas, cada uma com um deslocamento máximo menor que a anterior. Nós ligados tendem a ficar
+/// próximos, e componentes diferentes se afastam, o que mostra melhor a estrutura do grafo do que
+/// [`circular_layout`]. A direção e o peso das arestas não são considerados.
+///
+/// Cada rodada custa O(n² + m), então é indicado para grafos de até alguns milhares de nós.
+pub fn force_layout<W: Num>(graph: &dyn GraphRef<W>, iterations: usize, seed: u64) -> Layout {
+    let nodes: Vec<_> = node_ids(graph).collect();
+    let index: Map<Node, usize> = nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+    let edges: Vec<_> = graph
+        .edges_iter()
+        // Em grafos não direcionados, cada aresta aparece nos dois sentidos, mas só puxa uma vez.
+        .filter(|&(a, b, _)| a != b && (graph.is_directed() || a < b))
+        .map(|(a, b, _)| (index[&a], index[&b]))
+        .collect();
+
+    let mut rng = Rng::new(seed);
+    let mut position: Vec<_> = nodes
+        .iter()
+        .map(|_| (rng.next_f64(), rng.next_f64()))
+        .collect();
+    // Distância ideal entre os nós, para que eles ocupem um quadrado de lado 1.
+    let k = (1.0 / nodes.len().max(1) as f64).sqrt();
+    // A "temperatura" limita o deslocamento de cada rodada e diminui até zero.
+    let initial = 0.1;
+    for round in 0..iterations {
+        let temperature = initial * (1.0 - round as f64 / iterations as f64);
+        let mut displacement = vec![(0.0, 0.0); nodes.len()];
+        // Cada força é aplicada nos dois nós, em sentidos opostos. A distância mínima evita
+        // divisões por zero quando dois nós caem no mesmo lugar.
+        let mut push = |u: usize, v: usize, force: &dyn Fn(f64) -> f64| {
+            let (dx, dy) = (position[u].0 - position[v].0, position[u].1 - position[v].1);
+            let distance = (dx * dx + dy * dy).sqrt().max(1e-3);
+            let f = force(distance) / distance;
+            displacement[u].0 += dx * f;
+            displacement[u].1 += dy * f;
+            displacement[v].0 -= dx * f;
+            displacement[v].1 -= dy * f;
+        };
+        for u in 0..nodes.len() {
+            for v in 0..u {
+                push(u, v, &|d| k * k / d);
+            }
+        }
+        for &(u, v) in &edges {
+            push(u, v, &|d| -d * d / k);
+        }
+        for (p, (dx, dy)) in position.iter_mut().zip(displacement) {
+            let length = (dx * dx + dy * dy).sqrt();
+            if length > 0.0 {
+                let step = length.min(temperature) / length;
+                p.0 += dx * step;
+                p.1 += dy * step;
+            }
+            // Sem limites, nós isolados seriam empurrados para longe, e o resto do desenho ficaria
+            // espremido ao ajustar a imagem.
+            p.0 = p.0.clamp(0.0, 1.0);
+            p.1 = p.1.clamp(0.0, 1.0);
+        }
+    }
+
+    let mut layout = Layout::new();
+    for (&n, &p) in nodes.iter().zip(&position) {
+        layout.insert(n, p);
+    }
+    layout
+}
@@ -1,36 +1,1006 @@
-use std::collections::{BTreeMap as Map, BTreeSet as Set};
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Sem a feature `std`, o crate usa só o `core` e o `alloc`, que tem os vetores e as árvores de
+// busca, e pode rodar em sistemas embarcados. Com ela, o `alloc` continua sendo usado, já que a
+// biblioteca padrão só o reexporta.
+extern crate alloc;
+
+/// Os nomes do `alloc` que a _prelude_ da biblioteca padrão importaria em todos os módulos, mas
+/// que um crate `no_std` precisa importar. Os módulos que funcionam sem a feature `std` começam
+/// com `use crate::prelude::*;`.
+mod prelude {
+    pub(crate) use alloc::boxed::Box;
+    pub(crate) use alloc::string::{String, ToString};
+    pub(crate) use alloc::vec::Vec;
+    pub(crate) use alloc::{format, vec};
+}
+
+use alloc::collections::{BTreeMap as Map, BTreeSet as Set};
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display};
+use core::ops::{Add, Index, Range, RangeBounds, Sub};
+
+use bitmat::WORD_BITS;
+use edge_list::EdgeList;
+use prelude::*;
+
+/// Implementa os métodos de [`GraphRef`] repassando-os para o campo `graph`.
+///
+/// É usado pelos grafos que envolvem outro grafo, como [`NodeData`], que só precisam interceptar
+/// os métodos que modificam o grafo. Repassar também os métodos que possuem implementação padrão
+/// permite aproveitar as versões otimizadas do grafo envolvido.
+///
+/// Precisa ser definido antes dos `mod`s para que possa ser usado dentro deles.
+macro_rules! delegate_graph_queries {
+    () => {
+        fn edges(&self) -> Vec<$crate::Edge<W>> {
+            self.graph.edges()
+        }
+        fn edges_iter(&self) -> Box<dyn Iterator<Item = $crate::Edge<W>> + '_> {
+            self.graph.edges_iter()
+        }
+        fn node_count(&self) -> usize {
+            self.graph.node_count()
+        }
+        fn edge_count(&self) -> usize {
+            self.graph.edge_count()
+        }
+        fn has_node(&self, n: $crate::Node) -> bool {
+            self.graph.has_node(n)
+        }
+        fn has_edge(&self, a: $crate::Node, b: $crate::Node) -> bool {
+            self.graph.has_edge(a, b)
+        }
+        fn is_directed(&self) -> bool {
+            self.graph.is_directed()
+        }
+        fn self_loops(&self) -> $crate::SelfLoops {
+            self.graph.self_loops()
+        }
+        fn out_edges(&self, a: $crate::Node) -> Vec<$crate::Edge<W>> {
+            self.graph.out_edges(a)
+        }
+        fn in_edges(&self, a: $crate::Node) -> Vec<$crate::Edge<W>> {
+            self.graph.in_edges(a)
+        }
+        fn get_node_edges(&self, a: $crate::Node) -> Vec<$crate::Edge<W>> {
+            self.graph.get_node_edges(a)
+        }
+        fn get_edge_weight(&self, a: $crate::Node, b: $crate::Node) -> Option<W> {
+            self.graph.get_edge_weight(a, b)
+        }
+        fn edges_between(
+            &self,
+            from: &$crate::Set<$crate::Node>,
+            to: &$crate::Set<$crate::Node>,
+        ) -> Vec<$crate::Edge<W>> {
+            self.graph.edges_between(from, to)
+        }
+        fn neighbors(&self, n: $crate::Node) -> Box<dyn Iterator<Item = $crate::Node> + '_> {
+            self.graph.neighbors(n)
+        }
+        fn in_neighbors(&self, n: $crate::Node) -> Box<dyn Iterator<Item = $crate::Node> + '_> {
+            self.graph.in_neighbors(n)
+        }
+        fn for_each_neighbor(&self, n: $crate::Node, f: &mut dyn FnMut($crate::Node)) {
+            self.graph.for_each_neighbor(n, f)
+        }
+        fn for_each_edge(&self, f: &mut dyn FnMut($crate::Edge<W>)) {
+            self.graph.for_each_edge(f)
+        }
+        fn out_degree(&self, n: $crate::Node) -> usize {
+            self.graph.out_degree(n)
+        }
+        fn in_degree(&self, n: $crate::Node) -> usize {
+            self.graph.in_degree(n)
+        }
+        fn degree(&self, n: $crate::Node) -> usize {
+            self.graph.degree(n)
+        }
+        fn validate(&self) -> Result<(), $crate::GraphError> {
+            self.graph.validate()
+        }
+    };
+}
+
+/// Registra um evento do `tracing` com os campos `nome = valor`, caso a feature `tracing` esteja
+/// ativada.
+///
+/// Sem a feature, os valores são apenas avaliados, para que os contadores usados só nos eventos
+/// não gerem avisos de variáveis não lidas. Como não têm efeito, o compilador os elimina.
+macro_rules! trace_event {
+    ($message:literal $(, $field:ident = $value:expr)* $(,)?) => {{
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($field = $value,)* $message);
+        #[cfg(not(feature = "tracing"))]
+        let _ = ($(&$value,)*);
+    }};
+}
+
+mod arena;
+mod bitmat;
+mod budget;
+mod builder;
+mod connectivity;
+mod contraction;
+mod csr;
+mod data;
+mod diff;
+mod dynamic_paths;
+mod edge_id;
+mod edge_list;
+mod error;
+#[cfg(feature = "ffi")]
+mod ffi;
+mod filtered;
+#[cfg(feature = "std")]
+mod formats;
+mod frozen;
+mod generational;
+#[cfg(feature = "std")]
+mod generators;
+#[cfg(feature = "std")]
+mod input;
+mod journal;
+#[cfg(feature = "std")]
+mod keyed;
+#[cfg(feature = "std")]
+mod layout;
+mod metrics;
+#[cfg(all(feature = "mmap", unix))]
+mod mmap;
+mod observed;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod paths;
+#[cfg(feature = "petgraph")]
+mod petgraph_interop;
+mod product;
+mod property;
+#[cfg(feature = "python")]
+mod python;
+mod rng;
+#[cfg(feature = "serde")]
+mod serde_interop;
+#[cfg(feature = "proptest")]
+mod strategies;
+mod tiled;
+mod traversal;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+pub use arena::GraphArena;
+pub use bitmat::GraphBitMat;
+pub use budget::{Budget, CancelToken, Partial};
+#[cfg(feature = "std")]
+pub use builder::ConcurrentBuilder;
+pub use builder::{DuplicateEdges, GraphBuilder};
+pub use connectivity::{connected_components, minimum_spanning_tree, Connectivity, UnionFind};
+pub use contraction::ContractionHierarchy;
+pub use csr::GraphCsr;
+pub use data::{EdgeData, NodeData};
+pub use diff::{diff, GraphDiff};
+pub use dynamic_paths::DynamicShortestPaths;
+pub use edge_id::{EdgeId, EdgeIds};
+pub use error::GraphError;
+#[cfg(feature = "std")]
+pub use error::{InputProblem, ParseGraphError};
+pub use filtered::FilteredGraph;
+#[cfg(feature = "std")]
+pub use formats::{
+    from_binary, from_json, load_binary, read_csv, read_dimacs, read_dot, read_gml, read_graphml,
+    read_matrix_market, read_pajek, render_svg, save_binary, to_adjacency_matrix, to_binary,
+    to_csv, to_dimacs, to_dot, to_gml, to_graphml, to_json, to_matrix_market, to_pajek, to_svg,
+    BinaryWeight, CsvOptions, CsvSummary, DotOptions, FormatError,
+};
+pub use frozen::FrozenGraph;
+pub use generational::{GenerationalGraph, NodeHandle};
+#[cfg(feature = "std")]
+pub use generators::{
+    assign_weights, bipartite, complete, configuration_model, cycle, gnm, gnp, path, random_dag,
+    random_spanning_tree, random_tree, star, wheel, Collisions, WeightDistribution,
+};
+#[cfg(feature = "std")]
+pub use input::{
+    fill_graph, fill_graph_from_bytes, fill_graph_from_reader, fill_graph_from_reader_with,
+    is_compressed, open_input, validate_input, write_input_format, InputOptions,
+};
+pub use journal::{Checkpoint, JournaledGraph};
+#[cfg(feature = "std")]
+pub use keyed::KeyedGraph;
+#[cfg(feature = "std")]
+pub use layout::{circular_layout, force_layout, Layout};
+#[cfg(feature = "std")]
+pub use metrics::degree_assortativity;
+pub use metrics::{
+    average_neighbor_degree, average_neighbor_degrees, betweenness_centrality,
+    betweenness_centrality_within, clustering_coefficient, clustering_coefficients, diameter,
+    diameter_within, eccentricity, BetweennessOptions,
+};
+#[cfg(all(feature = "mmap", unix))]
+pub use mmap::MappedFile;
+pub use observed::{GraphEvent, ObservedGraph, ObserverId};
+#[cfg(feature = "rayon")]
+pub use parallel::{
+    par_betweenness_centrality, par_clustering_coefficients, par_connected_components,
+    par_delta_stepping, par_diameter, par_edges, par_fill_graph, par_nodes,
+};
+pub use paths::{
+    delta_stepping, dijkstra, distance_matrix, is_reachable, shortest_path, ShortestPaths,
+};
+#[cfg(feature = "petgraph")]
+pub use petgraph_interop::to_petgraph;
+pub use product::{cartesian_product, tensor_product};
+pub use property::{EdgePropertyMap, PropertyMap};
+#[cfg(feature = "proptest")]
+pub use strategies::arb_graph;
+pub use tiled::GraphTiledMat;
+pub use traversal::{breadth_first_search, depth_first_search, Control, Visitor};
+#[cfg(feature = "wasm")]
+pub use wasm::WasmGraph;
 
 /// Nós são identificados pelo tipo `u32`, que é um inteiro de 32 bits positivo.
 ///
 /// Equivale a um typedef em C++.
 pub type Node = u32;
 /// Definimos pesos das arestas como sendo inteiros de 32bits positivos.
+///
+/// Esse é o tipo de peso padrão dos grafos, mas qualquer tipo que implemente [`Num`] pode ser
+/// usado, como em `GraphAdj<f64>` ou `GraphMat<i64>`.
 pub type Weight = u32;
 /// Definimos nossas arestas como sendo uma tupla de dois nós e um peso.
-pub type Edge = (Node, Node, Weight);
+///
+/// Assim como em funções, parâmetros genéricos podem ter valores padrão: `Edge` sozinho equivale a
+/// `Edge<u32>`.
+pub type Edge<W = Weight> = (Node, Node, W);
+
+/// Um `trait` para os tipos numéricos que podem ser usados como peso de uma aresta.
+///
+/// Os `trait`s após o `:` são exigidos de quem implementa `Num`. Por exemplo, `Add<Output = Self>`
+/// significa que dois valores podem ser somados com `+`, resultando no mesmo tipo.
+///
+/// Repare que exigimos apenas `PartialOrd`, e não `Ord`, pois números de ponto flutuante não
+/// possuem ordem total (`NaN` não é comparável a nada).
+///
+/// O `'static` indica que o tipo não contém referências, o que permite guardar pesos dentro de
+/// iteradores em uma `Box` sem se preocupar com tempos de vida.
+pub trait Num:
+    'static + Copy + PartialOrd + Default + Debug + Display + Add<Output = Self> + Sub<Output = Self>
+{
+    /// O elemento neutro da soma.
+    const ZERO: Self;
+    /// O elemento neutro da multiplicação, útil como peso de grafos sem peso.
+    const ONE: Self;
+    /// Converte o valor para `f64`, o que pode perder precisão em inteiros muito grandes.
+    fn to_f64(self) -> f64;
+    /// Converte um `f64` para o tipo, arredondando para baixo nos inteiros. Valores fora do
+    /// intervalo do tipo são saturados, como no `as` do Rust.
+    fn from_f64(value: f64) -> Self;
+}
+
+/// Um `macro` gera código em tempo de compilação. Aqui, ele evita repetir a mesma implementação
+/// de `Num` para cada tipo numérico. Os inteiros e os números de ponto flutuante só diferem no
+/// arredondamento de `from_f64`.
+macro_rules! impl_num {
+    ($round:expr; $($t:ty),*) => {
+        $(
+            impl Num for $t {
+                const ZERO: Self = 0 as $t;
+                const ONE: Self = 1 as $t;
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+                fn from_f64(value: f64) -> Self {
+                    $round(value) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_num!(floor; u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+impl_num!(core::convert::identity; f32, f64);
+
+/// Arredonda `value` para baixo. `f64::floor` vem da biblioteca padrão, então não existe sem a
+/// feature `std`; aqui basta corrigir o truncamento do `as` nos números negativos. Valores fora do
+/// intervalo do `i64` já não têm casas decimais em um `f64`, então não precisam ser arredondados.
+fn floor(value: f64) -> f64 {
+    if value.abs() >= i64::MAX as f64 || value.is_nan() {
+        return value;
+    }
+    let truncated = value as i64 as f64;
+    if truncated > value {
+        truncated - 1.0
+    } else {
+        truncated
+    }
+}
+
+/// Compara dois pesos com uma ordem total, para ordenar arestas por peso.
+///
+/// `PartialOrd` não compara `NaN` com nenhum valor, o que faria a ordenação depender da posição
+/// das arestas, ou até causar um `panic`. Os pesos comparáveis seguem `partial_cmp`, e os outros
+/// seguem [`f64::total_cmp`], que coloca um `NaN` positivo depois de todos os números.
+fn total_order<W: Num>(x: W, y: W) -> Ordering {
+    x.partial_cmp(&y)
+        .unwrap_or_else(|| x.to_f64().total_cmp(&y.to_f64()))
+}
 
-/// Um `trait` que define os métodos que todo grafo deve implementar.
+/// Ordena as arestas por origem e destino, removendo repetições de um mesmo par.
+///
+/// Os métodos que retornam arestas usam essa ordem para que o resultado seja o mesmo
+/// independentemente da implementação.
+fn sorted_edges<W>(mut edges: Vec<Edge<W>>) -> Vec<Edge<W>> {
+    edges.sort_by_key(|e| (e.0, e.1));
+    edges.dedup_by_key(|e| (e.0, e.1));
+    edges
+}
+
+/// Retorna a quantidade de nós necessária para que todas as arestas sejam válidas, ou seja, o
+/// maior nó que aparece nelas mais um.
+fn node_bound<W>(edges: &[Edge<W>]) -> Node {
+    edges.iter().map(|e| e.0.max(e.1) + 1).max().unwrap_or(0)
+}
+
+/// Ordena as arestas por origem e destino e, quando um mesmo par aparece mais de uma vez, mantém
+/// apenas o último peso, assim como chamadas repetidas de `add_edge` fariam.
+fn sort_last_wins<W: Copy>(edges: &mut Vec<Edge<W>>) {
+    let len = sort_last_wins_in(edges);
+    edges.truncate(len);
+}
+
+/// Igual a [`sort_last_wins`], mas dentro de uma fatia, que não pode diminuir: as arestas que
+/// restam ficam no início dela, e a sua quantidade é retornada.
+fn sort_last_wins_in<W: Copy>(edges: &mut [Edge<W>]) -> usize {
+    // A ordenação é estável, ou seja, arestas repetidas continuam na ordem em que foram dadas.
+    // Assim, ao remover as repetições, basta ficar com o peso da última.
+    edges.sort_by_key(|e| (e.0, e.1));
+    let mut kept = 0;
+    for i in 0..edges.len() {
+        let edge = edges[i];
+        if kept > 0 && (edges[kept - 1].0, edges[kept - 1].1) == (edge.0, edge.1) {
+            edges[kept - 1].2 = edge.2;
+        } else {
+            edges[kept] = edge;
+            kept += 1;
+        }
+    }
+    kept
+}
+
+/// Define o que um grafo faz com laços, ou seja, arestas de um nó para ele mesmo.
+///
+/// Quando permitidos, laços são armazenados uma única vez, mesmo em grafos não direcionados, e
+/// aparecem uma única vez em `edges()`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SelfLoops {
+    /// Laços são aceitos normalmente.
+    #[default]
+    Allow,
+    /// Adicionar um laço causa um `panic`.
+    Reject,
+}
+
+impl SelfLoops {
+    /// Verifica se a aresta `a - b` é aceita pela política, causando um `panic` caso não seja.
+    fn check(self, a: Node, b: Node) {
+        if self == SelfLoops::Reject && a == b {
+            panic!("Tried to add self-loop on node {a} to a graph that rejects them");
+        }
+    }
+}
+
+/// Um `trait` que define os métodos de consulta que todo grafo deve implementar.
 ///
 /// `Trait`s são análogos a classes abstratas em C++, ou interfaces em outras linguagens
-pub trait Graph {
-    fn add_node(&mut self) -> Node;
-    fn add_edge(&mut self, a: Node, b: Node, weight: Weight);
-    fn edges(&self) -> Set<Edge>;
+///
+/// Os métodos que modificam o grafo ficam em [`GraphMut`]. Assim, algoritmos que apenas consultam o
+/// grafo podem receber um `&dyn GraphRef`, o que permite passar também visões somente leitura,
+/// como [`FilteredGraph`].
+///
+/// O parâmetro `W` é o tipo do peso das arestas. Como ele tem `Weight` como valor padrão,
+/// `dyn GraphRef` equivale a `dyn GraphRef<u32>`.
+///
+/// Os métodos que retornam arestas as retornam ordenadas por origem e destino, com exceção de
+/// `edges_iter`.
+pub trait GraphRef<W: Num = Weight> {
+    fn edges(&self) -> Vec<Edge<W>>;
+    /// Retorna um iterador sobre as arestas do grafo, sem alocar um vetor com todas elas.
+    ///
+    /// As arestas são as mesmas de `edges()`, mas a ordem depende da implementação.
+    fn edges_iter(&self) -> Box<dyn Iterator<Item = Edge<W>> + '_> {
+        Box::new(self.edges().into_iter())
+    }
     fn node_count(&self) -> usize;
-
-    fn get_node_edges(&self, a: Node) -> Set<Edge> {
-        self.edges()
-            .iter()
-            .copied()
-            .filter(|e| e.0 == a || e.1 == a)
+    /// Retorna a quantidade de arestas do grafo. Em grafos não direcionados, cada aresta é contada
+    /// uma única vez, como na primeira linha da entrada.
+    ///
+    /// A implementação padrão percorre todas as arestas, mas [`GraphAdj`] e [`GraphMat`] mantêm a
+    /// contagem atualizada a cada modificação, então nelas custa O(1).
+    fn edge_count(&self) -> usize {
+        let directed = self.is_directed();
+        self.edges_iter()
+            .filter(|&(a, b, _)| directed || a <= b)
+            .count()
+    }
+    /// Indica se `n` é um nó do grafo, ou seja, se foi criado e não foi removido.
+    fn has_node(&self, n: Node) -> bool;
+    /// Indica se o grafo é direcionado.
+    ///
+    /// Em um grafo não direcionado, cada aresta aparece em `edges()` nos dois sentidos, ou seja,
+    /// `(a, b, w)` e `(b, a, w)`. Já em um grafo direcionado, apenas o sentido adicionado existe.
+    fn is_directed(&self) -> bool;
+    /// Retorna a política do grafo para laços. Veja [`SelfLoops`].
+    fn self_loops(&self) -> SelfLoops {
+        SelfLoops::Allow
+    }
+    /// Retorna as arestas que saem do nó `a`, ou seja, as arestas da forma `(a, x, w)`.
+    fn out_edges(&self, a: Node) -> Vec<Edge<W>> {
+        sorted_edges(self.edges_iter().filter(|e| e.0 == a).collect())
+    }
+    /// Retorna as arestas que chegam no nó `a`, ou seja, as arestas da forma `(x, a, w)`.
+    fn in_edges(&self, a: Node) -> Vec<Edge<W>> {
+        sorted_edges(self.edges_iter().filter(|e| e.1 == a).collect())
+    }
+    /// Retorna as arestas que saem de `a` ou chegam em `a`. Em grafos não direcionados, inclui
+    /// os dois sentidos de cada aresta.
+    fn get_node_edges(&self, a: Node) -> Vec<Edge<W>> {
+        sorted_edges(self.edges_iter().filter(|e| e.0 == a || e.1 == a).collect())
+    }
+    /// Retorna as arestas `a -> b` com `a` em `from` e `b` em `to`, como as arestas de `edges()`
+    /// que atravessam os dois conjuntos. Nós de `from` que não existem são ignorados.
+    ///
+    /// Em grafos não direcionados, uma aresta com as duas pontas em `from` e em `to` aparece nos
+    /// dois sentidos. A implementação padrão percorre as arestas que saem de cada nó de `from`;
+    /// [`GraphAdj`] e [`GraphMat`] procuram diretamente cada nó de `to` quando isso é mais barato.
+    fn edges_between(&self, from: &Set<Node>, to: &Set<Node>) -> Vec<Edge<W>> {
+        from.iter()
+            .filter(|&&a| self.has_node(a))
+            .flat_map(|&a| self.out_edges(a))
+            .filter(|e| to.contains(&e.1))
             .collect()
     }
-    fn get_edge_weight(&self, a: Node, b: Node) -> Option<Weight> {
-        self.edges()
-            .iter()
+    /// Retorna as arestas de `edges()` cujo peso está em `range`, como em
+    /// `grafo.edges_with_weight_in(2..5)`, sem alocar um vetor com todas as arestas.
+    ///
+    /// Por ser genérico, não pode ser chamado em um `dyn GraphRef` (daí o `Self: Sized`).
+    fn edges_with_weight_in(&self, range: impl RangeBounds<W>) -> Vec<Edge<W>>
+    where
+        Self: Sized,
+    {
+        let mut edges = Vec::new();
+        self.for_each_edge(&mut |e| {
+            if range.contains(&e.2) {
+                edges.push(e);
+            }
+        });
+        sorted_edges(edges)
+    }
+    /// Retorna as `k` arestas de maior peso, da mais pesada para a mais leve, desempatando por
+    /// origem e destino. Em grafos não direcionados, cada aresta é considerada uma única vez, na
+    /// forma `(a, b, w)` com `a <= b`.
+    ///
+    /// Em vez de ordenar todas as arestas, separa as `k` mais pesadas em tempo linear e ordena
+    /// apenas elas: O(E + k log k). Arestas com peso `NaN` são consideradas mais pesadas que todas
+    /// as outras.
+    fn heaviest_edges(&self, k: usize) -> Vec<Edge<W>> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let directed = self.is_directed();
+        let mut edges = Vec::new();
+        self.for_each_edge(&mut |e| {
+            if directed || e.0 <= e.1 {
+                edges.push(e);
+            }
+        });
+        let order =
+            |x: &Edge<W>, y: &Edge<W>| total_order(y.2, x.2).then((x.0, x.1).cmp(&(y.0, y.1)));
+        if k < edges.len() {
+            edges.select_nth_unstable_by(k - 1, order);
+            edges.truncate(k);
+        }
+        edges.sort_unstable_by(order);
+        edges
+    }
+    /// Retorna o peso da aresta `a -> b`, ou `None` caso ela não exista.
+    ///
+    /// A implementação padrão percorre as arestas até encontrá-la, o que custa O(E). Todas as
+    /// estruturas do crate a sobrescrevem, buscando apenas entre as arestas que saem de `a`.
+    fn get_edge_weight(&self, a: Node, b: Node) -> Option<W> {
+        self.edges_iter()
             .find(|e| e.0 == a && e.1 == b)
             .map(|e| e.2)
     }
+    /// Indica se existe uma aresta de `a` para `b`.
+    fn has_edge(&self, a: Node, b: Node) -> bool {
+        self.get_edge_weight(a, b).is_some()
+    }
+    /// Retorna um iterador sobre os vizinhos de `n`, ou seja, os destinos das arestas que saem de
+    /// `n`. A ordem dos vizinhos depende da implementação.
+    ///
+    /// Como cada implementação retorna um tipo de iterador diferente, o iterador é retornado
+    /// dentro de uma `Box`, um ponteiro para a heap. Assim, o tipo de retorno é sempre o mesmo e
+    /// o método pode ser chamado em um `dyn GraphRef`.
+    fn neighbors(&self, n: Node) -> Box<dyn Iterator<Item = Node> + '_> {
+        Box::new(self.out_edges(n).into_iter().map(|e| e.1))
+    }
+    /// Retorna um iterador sobre os nós que possuem arestas para `n`, ou seja, os vizinhos de `n`
+    /// no grafo transposto, sem precisar construí-lo.
+    ///
+    /// Em grafos não direcionados, são os mesmos nós de `neighbors`.
+    fn in_neighbors(&self, n: Node) -> Box<dyn Iterator<Item = Node> + '_> {
+        if !self.is_directed() {
+            return self.neighbors(n);
+        }
+        Box::new(self.edges_iter().filter(move |e| e.1 == n).map(|e| e.0))
+    }
+    /// Chama `f` para cada vizinho de `n`, na mesma ordem de `neighbors`.
+    ///
+    /// É uma alternativa a `neighbors` que não precisa alocar uma `Box`, e que continua podendo ser
+    /// chamada em um `dyn GraphRef`, já que `f` é recebido como `&mut dyn FnMut` em vez de um tipo
+    /// genérico. Isso importa em laços internos de algoritmos, que visitam vizinhos muitas vezes.
+    fn for_each_neighbor(&self, n: Node, f: &mut dyn FnMut(Node)) {
+        self.neighbors(n).for_each(f);
+    }
+    /// Chama `f` para cada aresta do grafo, na mesma ordem de `edges_iter`. Veja
+    /// [`GraphRef::for_each_neighbor`].
+    fn for_each_edge(&self, f: &mut dyn FnMut(Edge<W>)) {
+        self.edges_iter().for_each(f);
+    }
+    /// Retorna a quantidade de arestas que saem de `n`.
+    fn out_degree(&self, n: Node) -> usize {
+        self.neighbors(n).count()
+    }
+    /// Retorna a quantidade de arestas que chegam em `n`.
+    ///
+    /// Em grafos não direcionados, é igual a `out_degree`.
+    fn in_degree(&self, n: Node) -> usize {
+        if self.is_directed() {
+            self.in_edges(n).len()
+        } else {
+            self.out_degree(n)
+        }
+    }
+    /// Retorna o grau de `n`, ou seja, a quantidade de arestas que incidem em `n`.
+    ///
+    /// Em grafos direcionados, é a soma de `in_degree` e `out_degree`. Em grafos não direcionados,
+    /// um laço conta uma única vez, de acordo com a forma como laços são armazenados (veja
+    /// [`SelfLoops`]).
+    fn degree(&self, n: Node) -> usize {
+        if self.is_directed() {
+            self.in_degree(n) + self.out_degree(n)
+        } else {
+            self.out_degree(n)
+        }
+    }
+    /// Verifica se o grafo está consistente: se toda aresta incide em nós existentes, se não há
+    /// arestas repetidas, se laços são permitidos e, em grafos não direcionados, se cada aresta
+    /// existe nos dois sentidos com o mesmo peso.
+    ///
+    /// Em um grafo construído apenas pelos métodos de [`GraphMut`], isso é sempre verdade. É útil
+    /// em testes e depois de carregar um grafo de fora do programa. Algumas implementações também
+    /// verificam a sua estrutura interna.
+    fn validate(&self) -> Result<(), GraphError> {
+        validate_edges(self.edges_iter(), self)
+    }
+    /// Indica se `self` e `other` possuem a mesma quantidade de nós e exatamente as mesmas
+    /// arestas, com os mesmos pesos.
+    ///
+    /// Como a comparação usa apenas os métodos de [`GraphRef`], grafos de implementações diferentes
+    /// podem ser comparados, por exemplo um [`GraphAdj`] e um [`GraphMat`] preenchidos com a mesma
+    /// entrada.
+    fn same_structure(&self, other: &dyn GraphRef<W>) -> bool {
+        self.node_count() == other.node_count() && self.edges() == other.edges()
+    }
+    /// Constrói o grafo de linha (_line graph_) do grafo: cada aresta vira um nó, e dois desses
+    /// nós são ligados, com peso `1`, quando as arestas correspondentes compartilham um nó.
+    ///
+    /// Em grafos direcionados, o nó da aresta `a -> b` é ligado ao das arestas que saem de `b`.
+    /// Em grafos não direcionados, cada aresta é considerada uma única vez, na forma `(a, b, w)`
+    /// com `a <= b`.
+    ///
+    /// Retorna também um vetor em que a posição `i` guarda a aresta original que virou o nó `i`.
+    fn line_graph(&self) -> (GraphAdj<W>, Vec<Edge<W>>) {
+        let directed = self.is_directed();
+        let edges: Vec<_> = self
+            .edges()
+            .into_iter()
+            .filter(|e| directed || e.0 <= e.1)
+            .collect();
+
+        // Agrupa os índices das arestas pelos nós em que incidem. Em grafos direcionados, basta
+        // saber de onde cada aresta sai.
+        let mut incident: Map<Node, Vec<usize>> = Map::new();
+        for (i, &(a, b, _)) in edges.iter().enumerate() {
+            incident.entry(a).or_default().push(i);
+            if !directed && a != b {
+                incident.entry(b).or_default().push(i);
+            }
+        }
+
+        let mut line = if directed {
+            GraphAdj::directed()
+        } else {
+            GraphAdj::default()
+        };
+        line.add_nodes(edges.len());
+        if directed {
+            for (i, &(_, b, _)) in edges.iter().enumerate() {
+                for &j in incident.get(&b).into_iter().flatten() {
+                    line.add_edge(i as Node, j as Node, W::ONE);
+                }
+            }
+        } else {
+            for group in incident.values() {
+                for (k, &i) in group.iter().enumerate() {
+                    for &j in &group[k + 1..] {
+                        line.add_edge(i as Node, j as Node, W::ONE);
+                    }
+                }
+            }
+        }
+        (line, edges)
+    }
+}
+
+/// Os métodos que modificam um grafo.
+///
+/// `GraphRef` é um _supertrait_ de `GraphMut`: todo tipo que implementa `GraphMut` também precisa
+/// implementar `GraphRef`, e os métodos de consulta podem ser usados em um `dyn GraphMut`.
+pub trait GraphMut<W: Num = Weight>: GraphRef<W> {
+    fn add_node(&mut self) -> Node;
+    /// Cria `count` nós de uma vez, retornando o intervalo de identificadores criados.
+    ///
+    /// Todas as implementações deste crate criam nós com identificadores consecutivos, então o
+    /// resultado é sempre um intervalo. Algumas implementações, como [`GraphMat`], conseguem
+    /// criar todos os nós com uma única realocação.
+    fn add_nodes(&mut self, count: usize) -> Range<Node> {
+        let mut nodes = 0..0;
+        for i in 0..count {
+            let node = self.add_node();
+            if i == 0 {
+                nodes.start = node;
+            }
+            nodes.end = node + 1;
+        }
+        nodes
+    }
+    fn add_edge(&mut self, a: Node, b: Node, weight: W);
+    /// Adiciona uma aresta sem peso entre `a` e `b`, o que equivale a uma aresta de peso `1`.
+    ///
+    /// Útil para grafos em que os pesos não importam, como [`GraphBitMat`], sem precisar inventar
+    /// um peso para cada aresta.
+    fn add_edge_unweighted(&mut self, a: Node, b: Node) {
+        self.add_edge(a, b, W::ONE);
+    }
+    /// Adiciona todas as arestas de `edges`, com o mesmo resultado de chamar `add_edge` para cada
+    /// uma delas, em ordem.
+    ///
+    /// Algumas implementações aproveitam que recebem todas as arestas de uma vez para ordená-las e
+    /// reservar memória antes de inseri-las. Por ser genérico, esse método não pode ser chamado em
+    /// um `dyn GraphMut` (daí o `Self: Sized`).
+    fn add_edges<I>(&mut self, edges: I)
+    where
+        I: IntoIterator<Item = Edge<W>>,
+        Self: Sized,
+    {
+        for (a, b, weight) in edges {
+            self.add_edge(a, b, weight);
+        }
+    }
+    /// Adiciona todas as arestas sem peso de `edges`, ou seja, com peso `1`. Útil para carregar
+    /// listas de pares de nós, sem precisar inventar um peso para cada uma. Veja
+    /// [`GraphMut::add_edge_unweighted`].
+    fn add_edges_unweighted<I>(&mut self, edges: I)
+    where
+        I: IntoIterator<Item = (Node, Node)>,
+        Self: Sized,
+    {
+        self.add_edges(edges.into_iter().map(|(a, b)| (a, b, W::ONE)));
+    }
+    /// Substitui o peso da aresta entre `a` e `b`, retornando o peso anterior.
+    ///
+    /// Diferente de `add_edge`, não cria a aresta: caso ela não exista, o grafo não é modificado e
+    /// o retorno é `None`.
+    fn set_edge_weight(&mut self, a: Node, b: Node, weight: W) -> Option<W> {
+        let previous = self.get_edge_weight(a, b)?;
+        self.add_edge(a, b, weight);
+        Some(previous)
+    }
+    /// Substitui o peso `w` da aresta entre `a` e `b` por `f(w)`, retornando o peso anterior. Por
+    /// exemplo, `graph.update_edge_weight(a, b, |w| w + 1)` incrementa o peso.
+    ///
+    /// Assim como [`GraphMut::set_edge_weight`], não cria a aresta caso ela não exista.
+    fn update_edge_weight(&mut self, a: Node, b: Node, f: impl FnOnce(W) -> W) -> Option<W>
+    where
+        Self: Sized,
+    {
+        let previous = self.get_edge_weight(a, b)?;
+        self.add_edge(a, b, f(previous));
+        Some(previous)
+    }
+    /// Remove o nó `n` e todas as arestas que incidem nele.
+    ///
+    /// Os identificadores dos demais nós não mudam, e o identificador de um nó removido não é
+    /// reutilizado por `add_node`, a não ser em grafos configurados para isso (veja
+    /// [`GraphAdj::with_id_reuse`]). Ou seja, após remoções, os nós deixam de ser necessariamente
+    /// `0..node_count()`.
+    ///
+    /// Causa um `panic` caso `n` não exista no grafo.
+    fn remove_node(&mut self, n: Node);
+    /// Remove a aresta entre `a` e `b`, retornando o seu peso caso ela existisse.
+    ///
+    /// Em grafos não direcionados, remove os dois sentidos da aresta.
+    fn remove_edge(&mut self, a: Node, b: Node) -> Option<W>;
+    /// Inverte o sentido de todas as arestas, transformando o grafo no seu transposto.
+    ///
+    /// Em grafos não direcionados, não faz nada. Veja também [`GraphMut::reversed`].
+    fn reverse(&mut self) {
+        if !self.is_directed() {
+            return;
+        }
+        let edges: Vec<_> = self.edges_iter().collect();
+        for &(a, b, _) in &edges {
+            self.remove_edge(a, b);
+        }
+        for (a, b, weight) in edges {
+            self.add_edge(b, a, weight);
+        }
+    }
+    /// Renumera os nós para `0..node_count()`, eliminando os buracos deixados por nós removidos, e
+    /// retorna o mapeamento dos identificadores antigos para os novos.
+    ///
+    /// A ordem relativa dos nós é mantida. Depois da compactação, [`GraphMat`] volta a ter uma
+    /// matriz sem linhas e colunas desperdiçadas, e `add_node` volta a criar o nó `node_count()`.
+    fn compact(&mut self) -> Map<Node, Node>;
+    /// Remove todos os nós e arestas, mantendo a direção e a política de laços.
+    ///
+    /// Diferente de `remove_node`, os identificadores voltam a começar em `0`, já que não sobra
+    /// nenhum nó antigo com que possam ser confundidos. Quando possível, a memória já alocada é
+    /// reaproveitada, para que o mesmo grafo possa ser preenchido várias vezes.
+    fn clear(&mut self);
+    /// Mantém apenas as arestas para as quais `keep` retorna `true`.
+    ///
+    /// Em grafos não direcionados, `keep` é chamado para os dois sentidos de cada aresta, e basta
+    /// um deles ser rejeitado para que a aresta seja removida.
+    fn retain_edges(&mut self, mut keep: impl FnMut(&Edge<W>) -> bool)
+    where
+        Self: Sized,
+    {
+        let rejected: Vec<_> = self.edges_iter().filter(|e| !keep(e)).collect();
+        for (a, b, _) in rejected {
+            self.remove_edge(a, b);
+        }
+    }
+    /// Contrai a aresta entre `a` e `b`, juntando os dois nós em `a`. Veja
+    /// [`GraphMut::merge_nodes`].
+    ///
+    /// Causa um `panic` caso a aresta `a -> b` não exista.
+    fn contract_edge(&mut self, a: Node, b: Node, combine: impl Fn(W, W) -> W)
+    where
+        Self: Sized,
+    {
+        if !self.has_edge(a, b) {
+            panic!("Tried to contract inexistent edge {a} {b}");
+        }
+        self.merge_nodes(a, b, combine);
+    }
+    /// Junta os nós `keep` e `remove` em `keep`, mesmo que não haja uma aresta entre eles. Útil,
+    /// por exemplo, para reduzir um grafo antes de particioná-lo.
+    ///
+    /// As arestas que incidiam em `remove` passam a incidir em `keep`, e `remove` é removido.
+    /// Quando os dois tinham uma aresta para um mesmo nó, as duas viram uma só, com peso
+    /// `combine(peso de keep, peso de remove)`. As arestas entre `keep` e `remove` e os laços de
+    /// `remove` são descartados, ou seja, a junção não cria laços.
+    ///
+    /// Causa um `panic` caso algum dos nós não exista ou caso eles sejam o mesmo nó.
+    fn merge_nodes(&mut self, keep: Node, remove: Node, combine: impl Fn(W, W) -> W)
+    where
+        Self: Sized,
+    {
+        let (a, b) = (keep, remove);
+        for n in [a, b] {
+            if !self.has_node(n) {
+                panic!("Tried to merge inexistent node {n}");
+            }
+        }
+        if a == b {
+            panic!("Tried to merge node {a} with itself");
+        }
+        let outgoing = self.out_edges(b);
+        // Em grafos não direcionados, `out_edges` já inclui todas as arestas de `b`.
+        let incoming = if self.is_directed() {
+            self.in_edges(b)
+        } else {
+            Vec::new()
+        };
+        self.remove_node(b);
+
+        let mut merge = |x: Node, y: Node, weight: W| match self.get_edge_weight(x, y) {
+            Some(existing) => self.add_edge(x, y, combine(existing, weight)),
+            None => self.add_edge(x, y, weight),
+        };
+        for (_, y, weight) in outgoing {
+            if y != a && y != b {
+                merge(a, y, weight);
+            }
+        }
+        for (x, _, weight) in incoming {
+            if x != a && x != b {
+                merge(x, a, weight);
+            }
+        }
+    }
+    /// Retorna uma cópia do grafo com o sentido de todas as arestas invertido. Veja
+    /// [`GraphMut::reverse`].
+    fn reversed(&self) -> Self
+    where
+        Self: Sized + Clone,
+    {
+        let mut result = self.clone();
+        result.reverse();
+        result
+    }
+    /// Retorna um novo grafo com os nós e arestas de `self` e de `other`, considerando que nós com
+    /// o mesmo identificador são o mesmo nó.
+    ///
+    /// Arestas que existem nos dois grafos ficam com o peso de `other`. As arestas de `other` são
+    /// adicionadas com `add_edge`, então seguem a direção de `self`: em um `self` não direcionado,
+    /// arestas direcionadas de `other` passam a valer nos dois sentidos.
+    ///
+    /// Causa um `panic` caso algum nó de `other` tenha sido removido de `self`, já que o
+    /// identificador dele não pode ser reutilizado.
+    fn union(&self, other: &dyn GraphRef<W>) -> Self
+    where
+        Self: Sized + Clone,
+    {
+        let mut result = self.clone();
+        // Cria de uma vez os nós que `other` possui além dos de `self`, removendo em seguida os
+        // que não existem em `other` (nós removidos dele).
+        let self_bound = node_ids(self).last().map_or(0, |n| n + 1);
+        let other_bound = node_ids(other).last().map_or(0, |n| n + 1);
+        if other_bound > self_bound {
+            let created = result.add_nodes((other_bound - self_bound) as usize);
+            for n in created {
+                if !other.has_node(n) {
+                    result.remove_node(n);
+                }
+            }
+        }
+        for (a, b, weight) in other.edges_iter() {
+            result.add_edge(a, b, weight);
+        }
+        result
+    }
+    /// Retorna um novo grafo com os nós e arestas de `self` e, separadamente, os de `other`.
+    ///
+    /// Os nós de `other` recebem novos identificadores, criados depois dos de `self`: o nó `n` de
+    /// `other` passa a ser o nó `n + offset`, onde `offset` é o primeiro nó criado. Assim, nenhum
+    /// nó é compartilhado e os dois grafos ficam desconexos entre si.
+    fn disjoint_union(&self, other: &dyn GraphRef<W>) -> Self
+    where
+        Self: Sized + Clone,
+    {
+        let mut result = self.clone();
+        let other_bound = node_ids(other).last().map_or(0, |n| n + 1);
+        let created = result.add_nodes(other_bound as usize);
+        let offset = created.start;
+        // Mantém os buracos deixados por nós removidos de `other`, para que o deslocamento seja o
+        // mesmo para todos os nós.
+        for n in created {
+            if !other.has_node(n - offset) {
+                result.remove_node(n);
+            }
+        }
+        for (a, b, weight) in other.edges_iter() {
+            result.add_edge(a + offset, b + offset, weight);
+        }
+        result
+    }
+    /// Retorna um novo grafo apenas com os nós e arestas que existem tanto em `self` quanto em
+    /// `other`.
+    ///
+    /// O peso de cada aresta é dado por `combine(peso em self, peso em other)`. Por exemplo,
+    /// `|a, _| a` mantém os pesos de `self`, e `u32::min` fica com o menor dos dois.
+    fn intersection(&self, other: &dyn GraphRef<W>, combine: impl Fn(W, W) -> W) -> Self
+    where
+        Self: Sized + Clone,
+    {
+        let mut result = self.clone();
+        let missing: Vec<_> = node_ids(self).filter(|&n| !other.has_node(n)).collect();
+        for n in missing {
+            result.remove_node(n);
+        }
+        for (a, b, weight) in result.edges() {
+            match other.get_edge_weight(a, b) {
+                Some(other_weight) => result.add_edge(a, b, combine(weight, other_weight)),
+                None => {
+                    result.remove_edge(a, b);
+                }
+            }
+        }
+        result
+    }
+    /// Retorna um novo grafo com os nós de `self` e apenas as arestas de `self` que não existem em
+    /// `other`, independentemente do peso.
+    fn difference(&self, other: &dyn GraphRef<W>) -> Self
+    where
+        Self: Sized + Clone,
+    {
+        let mut result = self.clone();
+        for (a, b, _) in self.edges_iter() {
+            if other.has_edge(a, b) {
+                result.remove_edge(a, b);
+            }
+        }
+        result
+    }
+}
+
+/// Verificações de [`GraphRef::validate`] que só dependem das arestas.
+///
+/// As arestas são recebidas separadamente do grafo para que as implementações possam passar as
+/// arestas como estão armazenadas, antes de qualquer remoção de repetições.
+fn validate_edges<W: Num, G: GraphRef<W> + ?Sized>(
+    edges: impl Iterator<Item = Edge<W>>,
+    graph: &G,
+) -> Result<(), GraphError> {
+    let mut seen = Vec::new();
+    for (a, b, weight) in edges {
+        if !graph.has_node(a) || !graph.has_node(b) {
+            return Err(GraphError::DanglingEdge { a, b });
+        }
+        if a == b && graph.self_loops() == SelfLoops::Reject {
+            return Err(GraphError::RejectedSelfLoop { n: a });
+        }
+        if !graph.is_directed() && graph.get_edge_weight(b, a) != Some(weight) {
+            return Err(GraphError::AsymmetricEdge { a, b });
+        }
+        seen.push((a, b));
+    }
+    seen.sort();
+    if let Some(pair) = seen.windows(2).find(|pair| pair[0] == pair[1]) {
+        let (a, b) = pair[0];
+        return Err(GraphError::DuplicateEdge { a, b });
+    }
+    Ok(())
+}
+
+/// Retorna o mapeamento usado por [`GraphMut::compact`]: o `i`-ésimo nó existente passa a ser o nó
+/// `i`.
+fn compact_mapping<W: Num>(graph: &dyn GraphRef<W>) -> Map<Node, Node> {
+    node_ids(graph)
+        .enumerate()
+        .map(|(new, old)| (old, new as Node))
+        .collect()
+}
+
+/// Quantidade de bytes alocados na heap pelo vetor `v`.
+///
+/// Usa a capacidade, e não o tamanho, já que é a capacidade que de fato está reservada.
+fn vec_bytes<T>(v: &Vec<T>) -> usize {
+    v.capacity() * core::mem::size_of::<T>()
+}
+
+/// Estimativa da quantidade de bytes alocados na heap por um `Map<K, V>` (ou um `Set<K>`, com
+/// `V = ()`) com `len` elementos.
+///
+/// A `BTreeMap` não informa quanta memória usa, então contamos apenas o espaço dos elementos. O
+/// valor real é um pouco maior, por causa dos ponteiros e do espaço livre nos nós da árvore.
+fn map_bytes<K, V>(len: usize) -> usize {
+    len * (core::mem::size_of::<K>() + core::mem::size_of::<V>())
+}
+
+/// Retorna os nós existentes de `graph`, em ordem crescente.
+///
+/// Como nós removidos deixam buracos, percorre os identificadores a partir de `0` até encontrar
+/// `node_count()` nós.
+fn node_ids<W: Num>(graph: &dyn GraphRef<W>) -> impl Iterator<Item = Node> + '_ {
+    (0..)
+        .filter(|&n| graph.has_node(n))
+        .take(graph.node_count())
 }
 
 /// Struct que representa um grafo implementado por meio de lista de adjacência.
@@ -40,156 +1010,1206 @@ pub trait Graph {
 ///     - Debug: Mostra o tipo e seus campos de forma intuitiva para debug
 ///     - Clone: Permite criar cópias da struct.
 #[derive(Default, Debug, Clone)]
-pub struct GraphAdj {
+pub struct GraphAdj<W = Weight> {
     next_node: Node,
-    node_edges: Map<Node, Vec<Edge>>,
+    /// Em grafos direcionados, armazena apenas as arestas que saem de cada nó. Listas com poucas
+    /// arestas não alocam memória (veja [`EdgeList`]).
+    ///
+    /// A lista de cada nó é mantida ordenada pelo nó de destino e sem repetições. Assim, buscar uma
+    /// aresta é uma busca binária, e a ordem de iteração não depende da ordem de inserção.
+    node_edges: Map<Node, EdgeList<W>>,
+    directed: bool,
+    self_loops: SelfLoops,
+    /// Indica se `add_node` deve reutilizar os identificadores de nós removidos.
+    reuse_ids: bool,
+    /// Identificadores de nós removidos que ainda não foram reutilizados (a _free list_). Só é
+    /// usado quando `reuse_ids` é verdadeiro.
+    free_ids: Set<Node>,
+    /// Quantidade de posições ocupadas nas listas e quantas delas são laços, mantidas a cada
+    /// modificação para que `edge_count` não precise percorrer as listas.
+    entry_count: usize,
+    loop_count: usize,
+}
+
+impl<W: Num> GraphAdj<W> {
+    /// Cria um grafo direcionado vazio.
+    ///
+    /// `GraphAdj::default()` continua criando um grafo não direcionado.
+    pub fn directed() -> Self {
+        Self {
+            directed: true,
+            ..Default::default()
+        }
+    }
+    /// Define a política do grafo para laços. Só afeta as arestas adicionadas depois.
+    pub fn with_self_loops(mut self, policy: SelfLoops) -> Self {
+        self.self_loops = policy;
+        self
+    }
+    /// Faz com que `add_node` reutilize os identificadores de nós removidos, começando pelo menor.
+    ///
+    /// Assim, programas que criam e removem nós por muito tempo não esgotam os identificadores, e
+    /// os nós continuam próximos de `0..node_count()`. Em compensação, um identificador guardado
+    /// de um nó removido pode passar a se referir a um nó novo.
+    ///
+    /// `add_nodes` continua criando nós com identificadores novos, para que eles formem um
+    /// intervalo.
+    pub fn with_id_reuse(mut self) -> Self {
+        self.reuse_ids = true;
+        self
+    }
+    /// Constrói um grafo não direcionado a partir de uma lista de arestas.
+    ///
+    /// A quantidade de nós é deduzida a partir do maior nó que aparece nas arestas: os nós vão de
+    /// `0` até ele, mesmo que alguns não tenham nenhuma aresta. Caso uma aresta apareça mais de uma
+    /// vez, o último peso é mantido.
+    pub fn from_edges(edges: impl IntoIterator<Item = Edge<W>>) -> Self {
+        let mut graph = Self::default();
+        graph.extend(edges);
+        graph
+    }
+    /// Retorna quantos bytes o grafo ocupa na heap, sem contar o próprio `GraphAdj`.
+    ///
+    /// Conta a árvore de listas e a capacidade de cada lista, ou seja, O(V + E). É uma estimativa:
+    /// o espaço interno das `BTreeMap`s não é contado (veja o código de `map_bytes`).
+    pub fn memory_usage(&self) -> usize {
+        let lists: usize = self.node_edges.values().map(EdgeList::heap_bytes).sum();
+        map_bytes::<Node, EdgeList<W>>(self.node_edges.len())
+            + lists
+            + map_bytes::<Node, ()>(self.free_ids.len())
+    }
+    /// Cria uma cópia imutável do grafo, otimizada para consultas. Veja [`FrozenGraph`].
+    pub fn freeze(&self) -> FrozenGraph<W> {
+        FrozenGraph::new(self)
+    }
+    /// Conta quantos vizinhos de saída `a` e `b` têm em comum.
+    ///
+    /// Como as listas estão ordenadas, basta percorrê-las juntas, como na etapa de junção do merge
+    /// sort, avançando sempre a que estiver no menor vizinho. Isso é útil, por exemplo, para contar
+    /// triângulos: cada vizinho em comum de uma aresta `a - b` forma um triângulo com ela.
+    pub fn common_neighbor_count(&self, a: Node, b: Node) -> usize {
+        let (mut xs, mut ys) = (self.out_slice(a), self.out_slice(b));
+        let mut count = 0;
+        while let (Some(x), Some(y)) = (xs.first(), ys.first()) {
+            match x.1.cmp(&y.1) {
+                core::cmp::Ordering::Less => xs = &xs[1..],
+                core::cmp::Ordering::Greater => ys = &ys[1..],
+                core::cmp::Ordering::Equal => {
+                    count += 1;
+                    xs = &xs[1..];
+                    ys = &ys[1..];
+                }
+            }
+        }
+        count
+    }
+    /// Retorna a lista de arestas que saem de `n`, ou uma lista vazia caso `n` não exista.
+    fn out_slice(&self, n: Node) -> &[Edge<W>] {
+        self.node_edges.get(&n).map_or(&[], |list| list)
+    }
+    /// Procura a aresta `a -> b` na lista de `a`, retornando a sua posição.
+    fn find(&self, a: Node, b: Node) -> Option<usize> {
+        self.out_slice(a).binary_search_by_key(&b, |e| e.1).ok()
+    }
+    /// Retorna as posições em que a aresta `a - b` é armazenada.
+    ///
+    /// Em grafos não direcionados, a aresta é armazenada nos dois sentidos, exceto quando é um
+    /// laço, que é armazenado só uma vez.
+    fn directions(&self, a: Node, b: Node) -> impl Iterator<Item = (Node, Node)> {
+        let mirrored = !self.directed && a != b;
+        core::iter::once((a, b)).chain(mirrored.then_some((b, a)))
+    }
+    /// Insere a posição `edge` na lista do seu nó de origem, sem espelhá-la, mantendo a lista
+    /// ordenada e a contagem de arestas. A posição não pode existir ainda.
+    pub(crate) fn insert_entry(&mut self, edge: Edge<W>) {
+        let list = self.node_edges.get_mut(&edge.0).unwrap();
+        let pos = list.partition_point(|e| e.1 < edge.1);
+        list.insert(pos, edge);
+        self.entry_count += 1;
+        self.loop_count += usize::from(edge.0 == edge.1);
+    }
+}
+
+/// Indica se a lista de `n` contém um laço, como `0` ou `1` para facilitar a contagem.
+fn has_loop<W>(list: &[Edge<W>], n: Node) -> usize {
+    usize::from(list.binary_search_by_key(&n, |e| e.1).is_ok())
+}
+
+/// Permite usar `collect()` para criar um grafo a partir de um iterador de arestas.
+///
+/// Veja [`GraphAdj::from_edges`].
+impl<W: Num> FromIterator<Edge<W>> for GraphAdj<W> {
+    fn from_iter<I: IntoIterator<Item = Edge<W>>>(iter: I) -> Self {
+        Self::from_edges(iter)
+    }
+}
+
+/// Adiciona as arestas de um iterador, criando os nós que ainda não existirem, assim como
+/// [`GraphAdj::from_edges`].
+impl<W: Num> Extend<Edge<W>> for GraphAdj<W> {
+    fn extend<I: IntoIterator<Item = Edge<W>>>(&mut self, iter: I) {
+        let edges: Vec<_> = iter.into_iter().collect();
+        let node_count = node_bound(&edges);
+        if node_count > self.next_node {
+            self.add_nodes((node_count - self.next_node) as usize);
+        }
+        self.add_edges(edges);
+    }
+}
+
+/// Versão sem pesos de `Extend`: todas as arestas recebem peso `1`.
+impl<W: Num> Extend<(Node, Node)> for GraphAdj<W> {
+    fn extend<I: IntoIterator<Item = (Node, Node)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(a, b)| (a, b, W::ONE)));
+    }
+}
+
+/// Formata as arestas do grafo da mesma forma que [`print_edges`].
+impl<W: Num> Display for GraphAdj<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_edges(f, self)
+    }
+}
+
+/// Permite ler o peso de uma aresta com `graph[(a, b)]`.
+///
+/// Causa um `panic` caso a aresta não exista; use `get_edge_weight` para evitar isso. Não há
+/// `IndexMut`, já que em grafos não direcionados a aresta também é armazenada no sentido
+/// `b -> a`, e uma referência mutável só poderia alterar um dos dois. Para mudar o peso, use
+/// `add_edge`.
+impl<W: Num> Index<(Node, Node)> for GraphAdj<W> {
+    type Output = W;
+
+    fn index(&self, (a, b): (Node, Node)) -> &W {
+        self.find(a, b)
+            .map(|pos| &self.node_edges[&a][pos].2)
+            .unwrap_or_else(|| panic!("Tried to read inexistent edge {a} {b}"))
+    }
 }
 
-impl Graph for GraphAdj {
-    fn edges(&self) -> Set<Edge> {
-        self.node_edges.values().flatten().copied().collect()
+impl<W: Num> GraphRef<W> for GraphAdj<W> {
+    fn edges(&self) -> Vec<Edge<W>> {
+        // As listas já estão ordenadas, e o `Map` percorre os nós em ordem crescente.
+        self.edges_iter().collect()
+    }
+    fn validate(&self) -> Result<(), GraphError> {
+        for (&n, edges) in &self.node_edges {
+            if n >= self.next_node {
+                return Err(GraphError::InvalidStorage(format!(
+                    "node {n} is not below next_node {}",
+                    self.next_node
+                )));
+            }
+            if let Some(e) = edges.iter().find(|e| e.0 != n) {
+                return Err(GraphError::InvalidStorage(format!(
+                    "edge {} {} is stored in the list of node {n}",
+                    e.0, e.1
+                )));
+            }
+            // Exigir ordem estritamente crescente também garante que não há arestas repetidas.
+            if edges.windows(2).any(|w| w[0].1 >= w[1].1) {
+                return Err(GraphError::InvalidStorage(format!(
+                    "edges of node {n} are not sorted"
+                )));
+            }
+        }
+        if let Some(n) = self.free_ids.iter().find(|n| self.has_node(**n)) {
+            return Err(GraphError::InvalidStorage(format!(
+                "node {n} exists but its id is marked as free"
+            )));
+        }
+        let entries = self.node_edges.values().map(|list| list.len()).sum();
+        let loops = self.node_edges.iter().map(|(&n, l)| has_loop(l, n)).sum();
+        if (self.entry_count, self.loop_count) != (entries, loops) {
+            return Err(GraphError::InvalidStorage(format!(
+                "edge count says {} entries and {} self-loops, but {entries} and {loops} are stored",
+                self.entry_count, self.loop_count
+            )));
+        }
+        // `edges_iter` percorre as listas como estão, então arestas repetidas aparecem repetidas.
+        validate_edges(self.edges_iter(), self)
     }
+    fn edges_iter(&self) -> Box<dyn Iterator<Item = Edge<W>> + '_> {
+        Box::new(self.node_edges.values().flatten().copied())
+    }
+    fn node_count(&self) -> usize {
+        self.node_edges.len()
+    }
+    fn edge_count(&self) -> usize {
+        // Em grafos não direcionados, cada aresta ocupa duas posições, exceto os laços.
+        if self.directed {
+            self.entry_count
+        } else {
+            (self.entry_count + self.loop_count) / 2
+        }
+    }
+    fn has_node(&self, n: Node) -> bool {
+        self.node_edges.contains_key(&n)
+    }
+    fn has_edge(&self, a: Node, b: Node) -> bool {
+        self.find(a, b).is_some()
+    }
+    fn get_edge_weight(&self, a: Node, b: Node) -> Option<W> {
+        // Busca binária na lista de `a`: O(log d), sendo `d` o grau de `a`. Mesmo com um milhão de
+        // vizinhos são cerca de 20 comparações, então um índice por _hash_ para nós de grau alto
+        // não compensaria o espaço extra e o custo de mantê-lo a cada modificação da lista.
+        self.find(a, b).map(|pos| self.node_edges[&a][pos].2)
+    }
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+    fn out_edges(&self, a: Node) -> Vec<Edge<W>> {
+        self.out_slice(a).to_vec()
+    }
+    fn edges_between(&self, from: &Set<Node>, to: &Set<Node>) -> Vec<Edge<W>> {
+        let mut edges = Vec::new();
+        for &a in from {
+            let list = self.out_slice(a);
+            // Com poucos destinos, procurar cada um na lista, em O(log d), é mais barato do que
+            // percorrê-la inteira.
+            if to.len() < list.len() {
+                edges.extend(to.iter().filter_map(|&b| Some(list[self.find(a, b)?])));
+            } else {
+                edges.extend(list.iter().filter(|e| to.contains(&e.1)));
+            }
+        }
+        edges
+    }
+    fn in_edges(&self, a: Node) -> Vec<Edge<W>> {
+        if !self.directed {
+            // As arestas que chegam em `a` são as da sua lista, no outro sentido, e continuam
+            // ordenadas pela origem.
+            return self
+                .out_slice(a)
+                .iter()
+                .map(|&(a, b, w)| (b, a, w))
+                .collect();
+        }
+        // Assim como em `get_node_edges`, a aresta `n -> a` é procurada na lista de cada nó.
+        (self.node_edges.values())
+            .filter_map(|list| Some(list[list.binary_search_by_key(&a, |e| e.1).ok()?]))
+            .collect()
+    }
+    fn get_node_edges(&self, a: Node) -> Vec<Edge<W>> {
+        let mut edges = self.out_slice(a).to_vec();
+        if self.directed {
+            // Não há listas de entrada, então a aresta `n -> a` é procurada na lista de cada nó,
+            // com uma busca binária: O(V log d), em vez de percorrer todas as arestas.
+            edges.extend(self.node_edges.values().filter_map(|list| {
+                let pos = list.binary_search_by_key(&a, |e| e.1).ok()?;
+                Some(list[pos])
+            }));
+        } else {
+            // As arestas que chegam em `a` são as da sua lista, no outro sentido.
+            edges.extend(self.out_slice(a).iter().map(|&(a, b, w)| (b, a, w)));
+        }
+        sorted_edges(edges)
+    }
+    fn neighbors(&self, n: Node) -> Box<dyn Iterator<Item = Node> + '_> {
+        Box::new(self.out_slice(n).iter().map(|e| e.1))
+    }
+    fn out_degree(&self, n: Node) -> usize {
+        self.node_edges.get(&n).map_or(0, |list| list.len())
+    }
+    fn for_each_neighbor(&self, n: Node, f: &mut dyn FnMut(Node)) {
+        for e in self.out_slice(n) {
+            f(e.1);
+        }
+    }
+    fn for_each_edge(&self, f: &mut dyn FnMut(Edge<W>)) {
+        for &e in self.node_edges.values().flatten() {
+            f(e);
+        }
+    }
+    fn self_loops(&self) -> SelfLoops {
+        self.self_loops
+    }
+}
+
+impl<W: Num> GraphMut<W> for GraphAdj<W> {
     fn add_node(&mut self) -> Node {
+        // Reutiliza o menor identificador livre, para que os nós continuem o mais próximo
+        // possível de `0..node_count()`.
+        if let Some(node) = self.free_ids.pop_first() {
+            self.node_edges.insert(node, EdgeList::default());
+            return node;
+        }
         let node = self.next_node;
-        self.node_edges.insert(node, vec![]);
+        self.node_edges.insert(node, EdgeList::default());
 
         self.next_node += 1;
         node
     }
-    fn node_count(&self) -> usize {
-        self.node_edges.len()
+    fn add_nodes(&mut self, count: usize) -> Range<Node> {
+        // Mesmo com `reuse_ids`, os nós são criados com novos identificadores, já que os
+        // identificadores livres não formam necessariamente um intervalo.
+        let start = self.next_node;
+        for node in start..start + count as Node {
+            self.node_edges.insert(node, EdgeList::default());
+        }
+        self.next_node += count as Node;
+        start..self.next_node
     }
-    fn add_edge(&mut self, a: Node, b: Node, weight: Weight) {
-        for (a, b) in [(a, b), (b, a)] {
+    fn add_edge(&mut self, a: Node, b: Node, weight: W) {
+        self.self_loops.check(a, b);
+        // Grafos direcionados registram apenas o sentido `a -> b`.
+        for (a, b) in self.directions(a, b) {
             let a_edges = self
                 .node_edges
                 .get_mut(&a)
                 .unwrap_or_else(|| panic!("Tried to add edge to inexistent node {a}"));
-            match a_edges.iter_mut().find(|e| e.1 == b) {
-                Some(existing_edge) => existing_edge.2 = weight,
-                None => a_edges.push((a, b, weight)),
+            // A lista está ordenada pelo destino, então uma aresta repetida é encontrada em
+            // O(log d). Só a inserção de uma aresta nova desloca as seguintes, o que é uma cópia
+            // contínua de memória; para inserir muitas arestas, `add_edges` ordena todas juntas.
+            match a_edges.binary_search_by_key(&b, |e| e.1) {
+                Ok(pos) => a_edges[pos].2 = weight,
+                Err(pos) => {
+                    a_edges.insert(pos, (a, b, weight));
+                    self.entry_count += 1;
+                    self.loop_count += usize::from(a == b);
+                }
             }
         }
     }
+    fn add_edges<I>(&mut self, edges: I)
+    where
+        I: IntoIterator<Item = Edge<W>>,
+    {
+        let mut entries = Vec::new();
+        for (a, b, weight) in edges {
+            self.self_loops.check(a, b);
+            entries.extend(self.directions(a, b).map(|(a, b)| (a, b, weight)));
+        }
+        sort_last_wins(&mut entries);
+
+        // Como as arestas estão ordenadas, as que saem de um mesmo nó estão lado a lado.
+        for group in entries.chunk_by(|x, y| x.0 == y.0) {
+            let a = group[0].0;
+            let a_edges = self
+                .node_edges
+                .get_mut(&a)
+                .unwrap_or_else(|| panic!("Tried to add edge to inexistent node {a}"));
+            // O grupo já está ordenado e sem repetições, então basta copiá-lo caso não haja
+            // arestas antigas. Caso contrário, as novas vêm depois das antigas, para que seus pesos
+            // prevaleçam ao ordenar a lista de novo.
+            let (len, had_loop) = (a_edges.len(), has_loop(a_edges, a));
+            a_edges.extend_from_slice(group);
+            if len > 0 {
+                a_edges.sort_last_wins();
+            }
+            // Só as posições novas são contadas: as repetidas apenas atualizaram o peso.
+            self.entry_count += a_edges.len() - len;
+            self.loop_count += has_loop(a_edges, a) - had_loop;
+        }
+    }
+    fn remove_node(&mut self, n: Node) {
+        let edges = self
+            .node_edges
+            .remove(&n)
+            .unwrap_or_else(|| panic!("Tried to remove inexistent node {n}"));
+        self.entry_count -= edges.len();
+        self.loop_count -= has_loop(&edges, n);
+        if self.reuse_ids {
+            self.free_ids.insert(n);
+        }
+        // Remove as arestas dos outros nós que apontam para `n`.
+        for edges in self.node_edges.values_mut() {
+            let len = edges.len();
+            edges.retain(|e| e.1 != n);
+            self.entry_count -= len - edges.len();
+        }
+    }
+    fn remove_edge(&mut self, a: Node, b: Node) -> Option<W> {
+        let mut removed = None;
+        for (a, b) in self.directions(a, b) {
+            let a_edges = self.node_edges.get_mut(&a)?;
+            if let Ok(pos) = a_edges.binary_search_by_key(&b, |e| e.1) {
+                // `remove` desloca os elementos seguintes, mantendo a lista ordenada.
+                removed = Some(a_edges.remove(pos).2);
+                self.entry_count -= 1;
+                self.loop_count -= usize::from(a == b);
+            }
+        }
+        removed
+    }
+    fn clear(&mut self) {
+        self.node_edges.clear();
+        self.free_ids.clear();
+        self.next_node = 0;
+        self.entry_count = 0;
+        self.loop_count = 0;
+    }
+    fn compact(&mut self) -> Map<Node, Node> {
+        // O mapeamento mantém a ordem dos nós, então as listas continuam ordenadas.
+        let mapping = compact_mapping(self);
+        let node_edges = core::mem::take(&mut self.node_edges);
+        self.node_edges = node_edges
+            .into_iter()
+            .map(|(a, edges)| {
+                let edges = edges
+                    .iter()
+                    .map(|&(a, b, weight)| (mapping[&a], mapping[&b], weight))
+                    .collect();
+                (mapping[&a], edges)
+            })
+            .collect();
+        self.next_node = mapping.len() as Node;
+        self.free_ids.clear();
+        mapping
+    }
+    fn reverse(&mut self) {
+        if !self.directed {
+            return;
+        }
+        // Esvazia as listas de todos os nós e redistribui as arestas pelo nó de destino.
+        let mut edges = Vec::new();
+        for a_edges in self.node_edges.values_mut() {
+            edges.extend_from_slice(a_edges);
+            a_edges.truncate(0);
+        }
+        // As arestas estão ordenadas por origem, então cada lista recebe os novos destinos em ordem
+        // crescente e continua ordenada.
+        for (a, b, weight) in edges {
+            // `b` existe, já que a aresta foi adicionada com `add_edge`.
+            self.node_edges.get_mut(&b).unwrap().push((b, a, weight));
+        }
+    }
 }
 
 /// Struct que representa um grafo implementado por matriz de adjacência.
+///
+/// Cada posição da matriz é um `Option<W>`: `None` representa a ausência de aresta, então pesos
+/// iguais a zero são pesos válidos, assim como em [`GraphAdj`].
+///
+/// Em um grafo não direcionado a matriz é simétrica, então apenas o triângulo inferior (com a
+/// diagonal) é armazenado: a aresta `a - b` fica em uma única posição, na linha do maior dos dois
+/// nós. Isso ocupa metade da memória de uma matriz completa.
 #[derive(Default, Debug, Clone)]
-pub struct GraphMat {
+pub struct GraphMat<W = Weight> {
     node_count: usize,
-    links: Vec<Weight>,
+    /// Dimensão da matriz alocada, que pode ser maior que `node_count` para que novos nós não
+    /// precisem realocá-la. As linhas e colunas depois de `node_count` ficam vazias.
+    stride: usize,
+    /// Matriz armazenada linha a linha (veja [`cell_index`]). Em grafos direcionados, o peso da
+    /// aresta `a -> b` fica na posição `a * stride + b`; nos não direcionados, a linha `a` tem só as
+    /// posições `b <= a` e começa na posição `a * (a + 1) / 2`.
+    links: Vec<Option<W>>,
+    /// Indica quais posições da matriz completa estão ocupadas, com um bit por posição e
+    /// `stride.div_ceil(64)` palavras por linha, no formato de [`GraphBitMat::row_words`]. Permite
+    /// operar sobre linhas inteiras com operações bit a bit (veja [`GraphMat::row_bits`]).
+    ///
+    /// Mesmo em grafos não direcionados, guarda as duas metades, para que cada linha seja um
+    /// trecho contínuo. Ocupa 1 bit por posição, contra os 64 de um `Option<u32>` em `links`.
+    presence: Vec<u64>,
+    directed: bool,
+    /// Nós removidos. Suas linhas e colunas continuam na matriz, mas ficam vazias.
+    removed: Set<Node>,
+    self_loops: SelfLoops,
+    /// Quantidade de posições ocupadas na matriz e quantas delas estão na diagonal, assim como em
+    /// [`GraphAdj`].
+    entry_count: usize,
+    loop_count: usize,
 }
 
-impl Graph for GraphMat {
-    fn add_node(&mut self) -> Node {
-        let new_node = self.node_count as Node;
+impl<W: Num> GraphMat<W> {
+    /// Cria um grafo direcionado vazio.
+    ///
+    /// `GraphMat::default()` continua criando um grafo não direcionado.
+    pub fn directed() -> Self {
+        Self {
+            directed: true,
+            ..Default::default()
+        }
+    }
+    /// Cria um grafo não direcionado vazio, com espaço para `nodes` nós sem realocar a matriz.
+    pub fn with_capacity(nodes: usize) -> Self {
+        let mut graph = Self::default();
+        graph.reserve(nodes);
+        graph
+    }
+    /// Define a política do grafo para laços. Só afeta as arestas adicionadas depois.
+    pub fn with_self_loops(mut self, policy: SelfLoops) -> Self {
+        self.self_loops = policy;
+        self
+    }
+    /// Quantidade de nós que cabem na matriz alocada, contando os já existentes.
+    pub fn capacity(&self) -> usize {
+        self.stride
+    }
+    /// Garante espaço para mais `additional` nós sem realocar a matriz.
+    pub fn reserve(&mut self, additional: usize) {
+        self.grow(self.node_count + additional);
+    }
+    /// Libera o espaço reservado para nós que ainda não existem.
+    pub fn shrink_to_fit(&mut self) {
+        if self.stride > self.node_count {
+            self.reallocate(self.node_count);
+        }
+        self.links.shrink_to_fit();
+    }
+    /// Realoca a matriz com dimensão `stride`, copiando as linhas existentes.
+    fn reallocate(&mut self, stride: usize) {
+        // No triângulo, a posição de uma linha não depende da dimensão, então basta aumentar ou
+        // diminuir o vetor. As posições removidas pertencem a nós que não existem, e estão vazias.
+        if !self.directed {
+            self.links.resize(cell_count(false, stride), None);
+            self.stride = stride;
+            self.rebuild_presence();
+            return;
+        }
+        // Sem nós, não há nada para copiar e a memória do vetor atual pode ser reaproveitada (por
+        // exemplo, depois de um `clear`).
+        if self.node_count == 0 {
+            self.links.clear();
+            self.links.resize(stride * stride, None);
+            self.stride = stride;
+            self.rebuild_presence();
+            return;
+        }
+        let mut links = vec![None; stride * stride];
+        // Cada linha antiga é copiada para o início da linha correspondente da nova matriz.
+        let new_lines = links.chunks_mut(stride);
+        let old_lines = self.links.chunks(self.stride);
+        for (new_line, old_line) in new_lines.zip(old_lines).take(self.node_count) {
+            new_line[..self.node_count].copy_from_slice(&old_line[..self.node_count]);
+        }
+        self.links = links;
+        self.stride = stride;
+        self.rebuild_presence();
+    }
+    /// Quantidade de palavras de cada linha de `presence`.
+    fn words_per_row(&self) -> usize {
+        self.stride.div_ceil(WORD_BITS)
+    }
+    /// Recalcula `presence` a partir de `links`, depois de uma operação que move várias posições
+    /// de uma vez.
+    fn rebuild_presence(&mut self) {
+        let words_per_row = self.words_per_row();
+        self.presence.clear();
+        self.presence.resize(self.stride * words_per_row, 0);
+        // Fora dos nós existentes, a matriz está vazia.
+        for a in 0..self.node_count {
+            for b in 0..self.node_count {
+                if self.links[self.cell(a, b)].is_some() {
+                    self.presence[a * words_per_row + b / WORD_BITS] |= 1 << (b % WORD_BITS);
+                }
+            }
+        }
+    }
+    /// Posição da aresta `a -> b` em `links`.
+    fn cell(&self, a: usize, b: usize) -> usize {
+        cell_index(self.directed, self.stride, a, b)
+    }
+    /// Percorre as posições da linha `a` em `links`, uma para cada nó, contando os removidos.
+    ///
+    /// No triângulo, as posições até a diagonal são contínuas, e as seguintes ficam na coluna `a`
+    /// das linhas abaixo: a posição de `b > a` é a do seu espelho, `b -> a`.
+    fn row_cells(&self, a: usize) -> impl Iterator<Item = &Option<W>> + '_ {
+        let n = self.node_count;
+        let (head, tail) = if a >= n {
+            (0..0, n..n)
+        } else if self.directed {
+            (a * self.stride..a * self.stride + n, n..n)
+        } else {
+            let start = a * (a + 1) / 2;
+            (start..start + a + 1, a + 1..n)
+        };
+        self.links[head]
+            .iter()
+            .chain(tail.map(move |b| &self.links[b * (b + 1) / 2 + a]))
+    }
+    /// Garante que a matriz comporte `node_count` nós.
+    ///
+    /// A dimensão cresce pelo menos 50% a cada realocação, de forma que adicionar os nós um a um
+    /// custa, no total, O(n²) cópias, e não O(n³). O fator não é 2, como em `Vec`, porque a
+    /// memória da matriz cresce com o quadrado da dimensão.
+    fn grow(&mut self, node_count: usize) {
+        if node_count <= self.stride {
+            return;
+        }
+        // Na primeira alocação, como ao ler um arquivo, a matriz fica com o tamanho exato.
+        let stride = if self.node_count == 0 {
+            node_count
+        } else {
+            node_count.max(self.stride + self.stride / 2)
+        };
+        self.reallocate(stride);
+    }
+    /// Retorna quantos bytes o grafo ocupa na heap, sem contar o próprio `GraphMat`.
+    ///
+    /// A matriz ocupa [`capacity`](Self::capacity)`²` posições, ou cerca de metade disso em grafos
+    /// não direcionados, mesmo que o grafo tenha poucas arestas. Compare com
+    /// [`GraphAdj::memory_usage`], que cresce com O(V + E).
+    pub fn memory_usage(&self) -> usize {
+        vec_bytes(&self.links)
+            + vec_bytes(&self.presence)
+            + map_bytes::<Node, ()>(self.removed.len())
+    }
+    /// Percorre a linha do nó `n`: a posição `b` contém o peso da aresta `n -> b`, caso ela
+    /// exista.
+    ///
+    /// A linha tem uma posição para cada nó, contando os removidos, e fica vazia caso `n` esteja
+    /// fora da matriz. Como grafos não direcionados guardam só metade da matriz, a linha não é um
+    /// trecho contínuo da memória; para operar sobre ela de uma vez, use
+    /// [`row_bits`](Self::row_bits).
+    pub fn row(&self, n: Node) -> impl Iterator<Item = Option<W>> + '_ {
+        self.row_cells(n as usize).copied()
+    }
+    /// Retorna a linha do nó `n` como um conjunto de bits, no mesmo formato de
+    /// [`GraphBitMat::row_words`]: o bit `b % 64` da palavra `b / 64` indica se existe a aresta
+    /// `n -> b`. Bits de nós que não existem são sempre zero.
+    ///
+    /// Os bits são mantidos junto com a matriz, então a linha não é copiada. Com ela, operações
+    /// sobre uma linha inteira (como a interseção em [`row_and`](Self::row_and)) processam 64 nós
+    /// por instrução, em vez de um por vez.
+    pub fn row_bits(&self, n: Node) -> &[u64] {
+        if n as usize >= self.node_count {
+            return &[];
+        }
+        let words_per_row = self.words_per_row();
+        let start = n as usize * words_per_row;
+        &self.presence[start..start + words_per_row]
+    }
+    /// Retorna os nós que são vizinhos de saída de `a` e de `b` ao mesmo tempo, como em
+    /// [`row_bits`](Self::row_bits).
+    pub fn row_and(&self, a: Node, b: Node) -> Vec<u64> {
+        let (xs, ys) = (self.row_bits(a), self.row_bits(b));
+        xs.iter().zip(ys).map(|(x, y)| x & y).collect()
+    }
+    /// Retorna os nós que são vizinhos de saída de `a` ou de `b`, como em
+    /// [`row_bits`](Self::row_bits).
+    pub fn row_or(&self, a: Node, b: Node) -> Vec<u64> {
+        let (xs, ys) = (self.row_bits(a), self.row_bits(b));
+        // Caso só uma das linhas exista, a outra conta como vazia.
+        let len = xs.len().max(ys.len());
+        let word = |words: &[u64], i| words.get(i).copied().unwrap_or(0);
+        (0..len).map(|i| word(xs, i) | word(ys, i)).collect()
+    }
+    /// Conta quantos vizinhos de saída `a` e `b` têm em comum, fazendo o `AND` de suas linhas
+    /// palavra por palavra, assim como [`GraphBitMat::common_neighbor_count`].
+    pub fn common_neighbor_count(&self, a: Node, b: Node) -> usize {
+        self.row_bits(a)
+            .iter()
+            .zip(self.row_bits(b))
+            .map(|(x, y)| (x & y).count_ones() as usize)
+            .sum()
+    }
+    /// Percorre as arestas do grafo contando cada uma uma única vez, na mesma ordem de
+    /// [`GraphRef::edges`].
+    ///
+    /// Em grafos não direcionados, a aresta `a - b` aparece apenas como `(a, b, w)`, com `a <= b`,
+    /// ou seja, somente o triângulo superior da matriz é visitado. Em grafos direcionados, as
+    /// arestas são as mesmas de [`GraphRef::edges_iter`]. Nada é alocado: as arestas são
+    /// produzidas à medida que o iterador avança.
+    pub fn upper_edges(&self) -> impl Iterator<Item = Edge<W>> + '_ {
+        let n = self.node_count;
+        (0..n).flat_map(move |a| {
+            let first = if self.directed { 0 } else { a };
+            (first..n)
+                .filter_map(move |b| Some((a as Node, b as Node, self.links[self.cell(a, b)]?)))
+        })
+    }
+    /// Retorna as arestas que chegam em `b`, na forma `(origem, peso)`, em um grafo direcionado.
+    fn column(&self, b: Node) -> impl Iterator<Item = (Node, W)> + '_ {
+        // Sem essa verificação, um `b` fora do intervalo começaria em uma das linhas seguintes.
+        // Nesse caso, começamos no fim do vetor, e a coluna fica vazia.
+        let start = if (b as usize) < self.node_count {
+            b as usize
+        } else {
+            self.links.len()
+        };
+        // Percorre a coluna `b`, pulando `stride` elementos por vez. `step_by` não aceita zero,
+        // por isso o `max`.
+        self.links[start..]
+            .iter()
+            .step_by(self.stride.max(1))
+            .enumerate()
+            .filter_map(|(a, weight)| Some((a as Node, (*weight)?)))
+    }
+    /// Escreve `weight` na posição `a -> b` da matriz, atualizando `presence` e a contagem de
+    /// arestas caso a posição passe a estar ocupada ou vazia.
+    ///
+    /// Em grafos não direcionados, `a -> b` e `b -> a` são a mesma posição, então as duas
+    /// direções são escritas de uma vez.
+    fn set_entry(&mut self, a: usize, b: usize, weight: Option<W>) {
+        let cell = self.cell(a, b);
+        let was_set = core::mem::replace(&mut self.links[cell], weight).is_some();
+        if was_set == weight.is_some() {
+            return;
+        }
+        let mirrored = !self.directed && a != b;
+        self.flip_presence(a, b);
+        if mirrored {
+            self.flip_presence(b, a);
+        }
+        let (entries, diagonal) = (1 + usize::from(mirrored), usize::from(a == b));
+        if weight.is_some() {
+            self.entry_count += entries;
+            self.loop_count += diagonal;
+        } else {
+            self.entry_count -= entries;
+            self.loop_count -= diagonal;
+        }
+    }
+    /// Inverte o bit de `presence` da posição `a -> b`.
+    fn flip_presence(&mut self, a: usize, b: usize) {
+        let word = a * self.words_per_row() + b / WORD_BITS;
+        self.presence[word] ^= 1 << (b % WORD_BITS);
+    }
+}
 
-        let new_node_count = self.node_count + 1;
-        // Cria novo vetor cujo tamanho é `(node_count+1) ^ 2`
-        let mut new_links = vec![0; new_node_count.pow(2)];
+/// Posição da aresta `a -> b` no vetor de uma matriz com dimensão `stride`.
+///
+/// Em grafos não direcionados, apenas o triângulo inferior é armazenado, linha a linha: as linhas
+/// anteriores à linha `a` têm `1 + 2 + ... + a = a * (a + 1) / 2` posições, e a aresta fica na
+/// linha do maior nó.
+fn cell_index(directed: bool, stride: usize, a: usize, b: usize) -> usize {
+    if directed {
+        a * stride + b
+    } else {
+        let (row, col) = if a >= b { (a, b) } else { (b, a) };
+        row * (row + 1) / 2 + col
+    }
+}
 
-        // Caso hajam nós no vetor, precisamos copiar as informações para o novo.
-        if self.node_count > 0 {
-            // Cria um iterador que agrupa `new_node_count` elementos por vez do novo vetor.
-            // Ou seja, temos um vetor que representa uma linha do vetor a cada iteração.
-            let new_lines = new_links.chunks_mut(new_node_count);
-            // Cria um iterador que agroupa `node_count` elementos por vez do vetor antigo.
-            let old_lines = self.links.chunks_mut(self.node_count);
-            for (new_line, old_line) in new_lines.zip(old_lines) {
-                // Limita as linhas do novo vetor para que tenham exatamente `node_count` elementos
-                new_line[..self.node_count]
-                    // Copia os pesos das arestas do antigo vetor para o novo.
-                    .copy_from_slice(old_line);
-            }
+/// Quantidade de posições do vetor de uma matriz com dimensão `stride`.
+fn cell_count(directed: bool, stride: usize) -> usize {
+    if directed {
+        stride * stride
+    } else {
+        stride * (stride + 1) / 2
+    }
+}
+
+/// Adiciona as arestas de um iterador, criando os nós que ainda não existirem.
+impl<W: Num> Extend<Edge<W>> for GraphMat<W> {
+    fn extend<I: IntoIterator<Item = Edge<W>>>(&mut self, iter: I) {
+        let edges: Vec<_> = iter.into_iter().collect();
+        let node_count = node_bound(&edges) as usize;
+        if node_count > self.node_count {
+            // Cria todos os nós de uma vez, já que cada criação pode realocar a matriz.
+            self.add_nodes(node_count - self.node_count);
+        }
+        self.add_edges(edges);
+    }
+}
+
+/// Versão sem pesos de `Extend`: todas as arestas recebem peso `1`.
+impl<W: Num> Extend<(Node, Node)> for GraphMat<W> {
+    fn extend<I: IntoIterator<Item = (Node, Node)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(a, b)| (a, b, W::ONE)));
+    }
+}
+
+/// Formata as arestas do grafo da mesma forma que [`print_edges`].
+impl<W: Num> Display for GraphMat<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_edges(f, self)
+    }
+}
+
+/// Converte uma lista de adjacência em uma matriz, mantendo os identificadores dos nós, a direção e
+/// a política de laços.
+impl<W: Num> From<&GraphAdj<W>> for GraphMat<W> {
+    fn from(graph: &GraphAdj<W>) -> Self {
+        let mut mat = Self {
+            directed: graph.directed,
+            self_loops: graph.self_loops,
+            ..Default::default()
+        };
+        mat.add_nodes(graph.next_node as usize);
+        // Nós que foram removidos da lista continuam removidos na matriz.
+        mat.removed = (0..graph.next_node)
+            .filter(|n| !graph.node_edges.contains_key(n))
+            .collect();
+        for (a, b, weight) in graph.edges_iter() {
+            mat.set_entry(a as usize, b as usize, Some(weight));
         }
+        mat
+    }
+}
+
+/// Converte uma matriz em uma lista de adjacência, mantendo os identificadores dos nós, a direção e
+/// a política de laços.
+impl<W: Num> From<&GraphMat<W>> for GraphAdj<W> {
+    fn from(graph: &GraphMat<W>) -> Self {
+        let mut adj = Self {
+            next_node: graph.node_count as Node,
+            directed: graph.directed,
+            self_loops: graph.self_loops,
+            // As duas estruturas armazenam as arestas nas mesmas posições.
+            entry_count: graph.entry_count,
+            loop_count: graph.loop_count,
+            ..Default::default()
+        };
+        for n in node_ids(graph) {
+            adj.node_edges.insert(n, graph.out_edges(n).into());
+        }
+        adj
+    }
+}
 
-        self.links = new_links;
-        self.node_count += 1;
+/// Permite ler o peso de uma aresta com `graph[(a, b)]`.
+///
+/// Assim como em [`GraphAdj`], causa um `panic` caso a aresta não exista, e não há `IndexMut`.
+impl<W: Num> Index<(Node, Node)> for GraphMat<W> {
+    type Output = W;
 
-        new_node
+    fn index(&self, (a, b): (Node, Node)) -> &W {
+        if !self.has_edge(a, b) {
+            panic!("Tried to read inexistent edge {a} {b}");
+        }
+        // `has_edge` garante que a posição está dentro da matriz e possui um peso.
+        self.links[self.cell(a as usize, b as usize)]
+            .as_ref()
+            .unwrap()
+    }
+}
+
+impl<W: Num> GraphRef<W> for GraphMat<W> {
+    fn validate(&self) -> Result<(), GraphError> {
+        let expected = cell_count(self.directed, self.stride);
+        if self.links.len() != expected || self.node_count > self.stride {
+            return Err(GraphError::InvalidStorage(format!(
+                "matrix has {} entries for {} nodes, expected {expected}",
+                self.links.len(),
+                self.node_count,
+            )));
+        }
+        // As posições reservadas para nós futuros precisam estar vazias, ou esses nós já
+        // nasceriam com arestas. Como os nós existentes vêm primeiro, as suas posições são as
+        // primeiras do triângulo.
+        let used = if self.directed {
+            self.stride * self.node_count
+        } else {
+            cell_count(false, self.node_count)
+        };
+        let spare = self.links.iter().enumerate().find(|&(i, weight)| {
+            weight.is_some() && (i >= used || self.directed && i % self.stride >= self.node_count)
+        });
+        if let Some((i, _)) = spare {
+            return Err(GraphError::InvalidStorage(format!(
+                "position {i} is outside of the matrix"
+            )));
+        }
+        if let Some(n) = self
+            .removed
+            .iter()
+            .find(|&&n| n as usize >= self.node_count)
+        {
+            return Err(GraphError::InvalidStorage(format!(
+                "removed node {n} is outside of the matrix"
+            )));
+        }
+        let words_per_row = self.words_per_row();
+        let cells = || (0..self.stride).flat_map(|a| (0..self.stride).map(move |b| (a, b)));
+        let is_set = |(a, b)| self.links[self.cell(a, b)].is_some();
+        let mismatch = cells().find(|&(a, b)| {
+            let bit = self
+                .presence
+                .get(a * words_per_row + b / WORD_BITS)
+                .copied();
+            is_set((a, b)) != (bit.unwrap_or(0) >> (b % WORD_BITS) & 1 == 1)
+        });
+        if self.presence.len() != self.stride * words_per_row || mismatch.is_some() {
+            return Err(GraphError::InvalidStorage(
+                "presence bits do not match the matrix".to_string(),
+            ));
+        }
+        // As posições são contadas na matriz completa, mesmo que só o triângulo seja armazenado.
+        let entries = cells().filter(|&cell| is_set(cell)).count();
+        let loops = (0..self.node_count).filter(|&n| is_set((n, n))).count();
+        if (self.entry_count, self.loop_count) != (entries, loops) {
+            return Err(GraphError::InvalidStorage(format!(
+                "edge count says {} entries and {} self-loops, but {entries} and {loops} are stored",
+                self.entry_count, self.loop_count
+            )));
+        }
+        // Arestas em linhas ou colunas de nós removidos são detectadas como arestas soltas.
+        validate_edges(self.edges_iter(), self)
     }
     fn node_count(&self) -> usize {
-        self.node_count
+        // `self.node_count` é a dimensão da matriz, que inclui os nós removidos.
+        self.node_count - self.removed.len()
     }
-    fn get_edge_weight(&self, a: Node, b: Node) -> Option<Weight> {
-        let idx = a as usize * self.node_count + b as usize;
-        let w = *self.links.get(idx)?;
-        if w == 0 {
-            None
+    fn edge_count(&self) -> usize {
+        if self.directed {
+            self.entry_count
         } else {
-            Some(w)
+            (self.entry_count + self.loop_count) / 2
         }
     }
-    fn edges(&self) -> Set<Edge> {
-        self.links
-            .iter()
-            // Iteramos sobre cópias em vez de referências
-            .copied()
-            // Adicionamos um contador à cada elemento
+    fn has_node(&self, n: Node) -> bool {
+        (n as usize) < self.node_count && !self.removed.contains(&n)
+    }
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+    fn self_loops(&self) -> SelfLoops {
+        self.self_loops
+    }
+    fn out_edges(&self, a: Node) -> Vec<Edge<W>> {
+        self.row_cells(a as usize)
             .enumerate()
-            // Filtra links cujo peso é 0
-            .filter(|(_, weight)| *weight > 0)
-            // Transforma uma tupla de posição e peso em `Edge`.
-            .map(|(i, weight)| {
-                let y = i / self.node_count;
-                let x = i % self.node_count;
-                (x as Node, y as Node, weight)
-            })
+            .filter_map(|(b, weight)| Some((a, b as Node, (*weight)?)))
             .collect()
     }
-    fn add_edge(&mut self, a: Node, b: Node, weight: Weight) {
+    fn neighbors(&self, n: Node) -> Box<dyn Iterator<Item = Node> + '_> {
+        Box::new(
+            self.row_cells(n as usize)
+                .enumerate()
+                .filter(|(_, weight)| weight.is_some())
+                .map(|(b, _)| b as Node),
+        )
+    }
+    fn out_degree(&self, n: Node) -> usize {
+        self.row_bits(n)
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+    fn in_edges(&self, b: Node) -> Vec<Edge<W>> {
+        // Em grafos não direcionados, a coluna é igual à linha.
+        if !self.directed {
+            let edges = self.out_edges(b).into_iter();
+            return edges.map(|(b, a, weight)| (a, b, weight)).collect();
+        }
+        self.column(b).map(|(a, weight)| (a, b, weight)).collect()
+    }
+    fn get_node_edges(&self, a: Node) -> Vec<Edge<W>> {
+        // Percorre apenas a linha e a coluna de `a`.
+        let mut edges = self.out_edges(a);
+        edges.extend(self.in_edges(a));
+        sorted_edges(edges)
+    }
+    fn in_neighbors(&self, n: Node) -> Box<dyn Iterator<Item = Node> + '_> {
+        if !self.directed {
+            return self.neighbors(n);
+        }
+        Box::new(self.column(n).map(|(a, _)| a))
+    }
+    fn edges_between(&self, from: &Set<Node>, to: &Set<Node>) -> Vec<Edge<W>> {
+        // Consulta apenas as posições de `from` × `to`, em vez das linhas inteiras.
+        let mut edges = Vec::new();
+        for &a in from {
+            if !self.has_node(a) {
+                continue;
+            }
+            edges.extend(
+                to.iter()
+                    .filter_map(|&b| Some((a, b, self.get_edge_weight(a, b)?))),
+            );
+        }
+        edges
+    }
+    fn get_edge_weight(&self, a: Node, b: Node) -> Option<W> {
+        // Sem essa verificação, um `b` fora do intervalo acessaria a linha seguinte.
+        if a as usize >= self.node_count || b as usize >= self.node_count {
+            return None;
+        }
+        self.links[self.cell(a as usize, b as usize)]
+    }
+    fn edges(&self) -> Vec<Edge<W>> {
+        // Como percorremos a matriz linha a linha, as arestas já saem ordenadas.
+        self.edges_iter().collect()
+    }
+    fn edges_iter(&self) -> Box<dyn Iterator<Item = Edge<W>> + '_> {
+        // Percorre as linhas dos nós existentes, ignorando o espaço reservado.
+        Box::new((0..self.node_count).flat_map(move |row| {
+            self.row_cells(row)
+                // Adicionamos um contador à cada elemento
+                .enumerate()
+                // Filtra posições sem aresta e transforma uma tupla de posição e peso em `Edge`. A
+                // linha indica o nó de origem e a coluna o nó de destino.
+                .filter_map(move |(col, weight)| Some((row as Node, col as Node, (*weight)?)))
+        }))
+    }
+}
+
+impl<W: Num> GraphMut<W> for GraphMat<W> {
+    fn add_node(&mut self) -> Node {
+        self.add_nodes(1).start
+    }
+    fn add_nodes(&mut self, count: usize) -> Range<Node> {
+        let first_node = self.node_count as Node;
+        let new_node_count = self.node_count + count;
+        // As linhas e colunas reservadas já estão vazias, então os novos nós só precisam ser
+        // contados, a não ser que a matriz precise crescer.
+        self.grow(new_node_count);
+        self.node_count = new_node_count;
+        first_node..new_node_count as Node
+    }
+    fn add_edge(&mut self, a: Node, b: Node, weight: W) {
+        self.self_loops.check(a, b);
+        for n in [a, b] {
+            if !self.has_node(n) {
+                panic!("Tried to add edge to inexistent node {n}");
+            }
+        }
         // Converte nós em `usizes` para simplificar a indexação.
         let a = a as usize;
         let b = b as usize;
-        // Registra a ligação para o nó `a`
-        self.links[a * self.node_count + b] = weight;
-        // Registra a ligação para o nó `b`
-        self.links[b * self.node_count + a] = weight;
-    }
-}
-
-/// Dado um vetor de linhas no formato "a b w", onde a e b são vértices e w é o peso da aresta
-/// entre eles, preenche o grafo `graph`.
-pub fn fill_graph(input_data: &[Vec<u32>], graph: &mut dyn Graph) {
-    // Separa o vetor entre o primeiro elemento e o resto.
-    let (head, tail) = input_data.split_first().expect("Vetor veio vazio");
-    // Tenta desestruturar o vetor `head` em dois valores, executando o `else`
-    // caso não seja possível.
-    let [vertex_count, edge_count] = head[..] else {
-        panic!("Esperava que a primeira linha contivesse exatamente dois valores.");
-    };
-
-    // Cria `vertex_count` nós.
-    for _ in 0..vertex_count {
-        // Para simplificar essa parte, pressupõe-se que os nós retornados são criados em órdem
-        // crescente com incremento de 1, sendo o primeiro nó `0`.
-        graph.add_node();
+        // Registra a ligação para o nó `a` e, caso o grafo não seja direcionado, também para o nó
+        // `b`, já que as duas direções ocupam a mesma posição.
+        self.set_entry(a, b, Some(weight));
     }
+    fn remove_node(&mut self, n: Node) {
+        if n as usize >= self.node_count || !self.removed.insert(n) {
+            panic!("Tried to remove inexistent node {n}");
+        }
+        let n = n as usize;
+        // Esvazia a linha e a coluna do nó, removendo todas as arestas incidentes. Em grafos não
+        // direcionados, a coluna é a mesma posição da linha.
+        for b in 0..self.node_count {
+            self.set_entry(n, b, None);
+            if self.directed {
+                self.set_entry(b, n, None);
+            }
+        }
+    }
+    fn remove_edge(&mut self, a: Node, b: Node) -> Option<W> {
+        let weight = self.get_edge_weight(a, b)?;
+        self.set_entry(a as usize, b as usize, None);
+        Some(weight)
+    }
+    fn clear(&mut self) {
+        // `Vec::clear` mantém a capacidade do vetor, que é reaproveitada por `add_nodes`.
+        self.links.clear();
+        self.presence.clear();
+        self.node_count = 0;
+        self.stride = 0;
+        self.removed.clear();
+        self.entry_count = 0;
+        self.loop_count = 0;
+    }
+    fn compact(&mut self) -> Map<Node, Node> {
+        let mapping = compact_mapping(self);
+        let n = mapping.len();
+        // Copia cada elemento de um nó existente para a sua nova posição. Como a ordem dos nós é
+        // mantida, basta pular as linhas e colunas dos nós removidos.
+        let mut links = vec![None; cell_count(self.directed, n)];
+        for (&old_a, &new_a) in &mapping {
+            for (&old_b, &new_b) in &mapping {
+                links[cell_index(self.directed, n, new_a as usize, new_b as usize)] =
+                    self.links[self.cell(old_a as usize, old_b as usize)];
+            }
+        }
+        self.links = links;
+        self.node_count = n;
+        self.stride = n;
+        self.removed.clear();
+        self.rebuild_presence();
+        mapping
+    }
+    fn reverse(&mut self) {
+        if !self.directed {
+            return;
+        }
+        // Transpõe a matriz trocando cada elemento acima da diagonal com o seu espelho.
+        let (n, stride) = (self.node_count, self.stride);
+        for a in 0..n {
+            for b in a + 1..n {
+                self.links.swap(a * stride + b, b * stride + a);
+            }
+        }
+        self.rebuild_presence();
+    }
+}
 
-    // Converte `edge_count` para `usize` para indexação.
-    //
-    // `usize` é um inteiro positivo cujo tamanho é definido pela arquitetura,
-    // comummente utilizado para indexação.
-    let edge_count = edge_count as usize;
-
-    // Adiciona `edge_count` arestas ao grafo
-    for edge_data in &tail[..edge_count] {
-        let [a, b, weight] = edge_data[..] else {
-            panic!("Esperava que cada linha de aresta tivesse exatamente três valores.");
-        };
-        // Adiciona uma aresta entre o nó `a` e o nó `b`
-        //
-        // Como dito anteriormente, os nós são crescentes e começam em 0, portanto, precisamos
-        // subtrair 1 dos identificadores das entradas.
-        graph.add_edge(a - 1, b - 1, weight);
+/// Escreve as arestas do grafo em `f`, uma por linha, no formato `a b w` da entrada.
+fn write_edges<W: Num>(f: &mut fmt::Formatter<'_>, graph: &dyn GraphRef<W>) -> fmt::Result {
+    for edge in graph.edges_iter() {
+        // Como os nós começam em 0, somamos 1 para ficar igual à entrada.
+        writeln!(f, "{} {} {}", edge.0 + 1, edge.1 + 1, edge.2)?;
     }
+    Ok(())
 }
 
 /// Printa as arestas do grafo
-pub fn print_edges(graph: &dyn Graph) {
-    let edges = graph.edges();
-    for edge in edges {
+///
+/// As arestas são escritas à medida que são percorridas por [`GraphRef::edges_iter`], sem montar
+/// um vetor com todas elas.
+#[cfg(feature = "std")]
+pub fn print_edges<W: Num>(graph: &dyn GraphRef<W>) {
+    for edge in graph.edges_iter() {
         // Como os nós começam em 0, somamos 1 para ficar igual à entrada.
         println!("{} {} {}", edge.0 + 1, edge.1 + 1, edge.2);
     }
 }
+
+/// Desenha a matriz de adjacência de `graph` como uma grade alinhada, com o número de cada nó
+/// (começando em 1, como na entrada) no início das linhas e no topo das colunas.
+///
+/// A entrada da linha `a` e coluna `b` é o peso da aresta `a -> b`, ou `.` caso ela não exista.
+/// Serve para comparar, a olho, dois grafos pequenos, como um [`GraphMat`] e um [`GraphAdj`]
+/// montados a partir da mesma entrada.
+pub fn format_matrix<W: Num>(graph: &dyn GraphRef<W>) -> String {
+    let nodes: Vec<_> = node_ids(graph).collect();
+    let labels: Vec<_> = nodes.iter().map(|n| (n + 1).to_string()).collect();
+    let cells: Vec<Vec<_>> = nodes
+        .iter()
+        .map(|&a| {
+            nodes
+                .iter()
+                .map(|&b| {
+                    graph
+                        .get_edge_weight(a, b)
+                        .map_or(".".to_string(), |w| w.to_string())
+                })
+                .collect()
+        })
+        .collect();
+    // Cada coluna tem a largura do maior texto nela, contando o rótulo.
+    let label_width = labels.iter().map(String::len).max().unwrap_or(0);
+    let widths: Vec<_> = (0..nodes.len())
+        .map(|j| {
+            cells
+                .iter()
+                .map(|row| row[j].len())
+                .chain([labels[j].len()])
+                .max()
+                .unwrap()
+        })
+        .collect();
+
+    let mut out = format!("{:label_width$} |", "");
+    for (label, width) in labels.iter().zip(&widths) {
+        out.push_str(&format!(" {label:>width$}"));
+    }
+    out.push('\n');
+    out.push_str(&"-".repeat(out.len() - 1));
+    out.push('\n');
+    for (label, row) in labels.iter().zip(&cells) {
+        out.push_str(&format!("{label:>label_width$} |"));
+        for (cell, width) in row.iter().zip(&widths) {
+            out.push_str(&format!(" {cell:>width$}"));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Printa a matriz de adjacência do grafo, no formato de [`format_matrix`].
+#[cfg(feature = "std")]
+pub fn print_matrix<W: Num>(graph: &dyn GraphRef<W>) {
+    print!("{}", format_matrix(graph));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heaviest_edges_puts_nan_first() {
+        let mut graph: GraphAdj<f64> = GraphAdj::default();
+        graph.add_nodes(4);
+        graph.add_edge(0, 1, 1.0);
+        graph.add_edge(1, 2, f64::NAN);
+        graph.add_edge(0, 2, 3.0);
+        graph.add_edge(2, 3, 2.0);
+        let heaviest = graph.heaviest_edges(3);
+        let pairs: Vec<_> = heaviest.iter().map(|e| (e.0, e.1)).collect();
+        assert_eq!(pairs, [(1, 2), (0, 2), (2, 3)]);
+        assert!(heaviest[0].2.is_nan());
+    }
+}
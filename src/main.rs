@@ -1,33 +1,1807 @@
-use std::str::FromStr;
-use ex1::{GraphAdj, GraphMat, fill_graph, print_edges};
-
-fn main() {
-    // Obtém o nome do arquivo a partir do argv[1].
-    let filename = std::env::args()
-        .nth(1)
-        .expect("Esperava o nome do arquivo de entrada");
-
-    // Lê o arquivo inteiro e o armazena na memória.
-    let input_data = std::fs::read_to_string(filename).expect("Falha ao ler arquivo de entrada");
-    let input_data: Vec<Vec<u32>> = input_data
-        // Separa a string por fim de linha
-        .split('\n')
-        .map(|line|
-            // Separa cada linha por espaços
-            line.split_whitespace()
-                // Tenta converter a string em u32
-                .map(|num| u32::from_str(num).expect("Número inválido"))
-                // Coleta os resultados em um Vec
-                .collect::<Vec<u32>>())
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs::File;
+use std::io::{BufRead, BufWriter, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use ex1::{
+    circular_layout, complete, connected_components, cycle, diff, fill_graph_from_reader,
+    force_layout, from_binary, from_json, gnm, gnp, is_reachable, minimum_spanning_tree,
+    open_input, path, read_csv, read_dimacs, read_dot, read_gml, read_graphml, read_matrix_market,
+    read_pajek, shortest_path, star, to_adjacency_matrix, to_binary, to_csv, to_dimacs, to_dot,
+    to_gml, to_graphml, to_json, to_matrix_market, to_pajek, to_svg, validate_input, wheel,
+    write_input_format, CsvOptions, DotOptions, Edge, GraphAdj, GraphArena, GraphCsr, GraphMat,
+    GraphMut, GraphRef, GraphTiledMat, InputOptions, Node, SelfLoops, Weight,
+};
+
+#[cfg(feature = "rayon")]
+use ex1::par_fill_graph;
+#[cfg(all(feature = "mmap", unix))]
+use ex1::{fill_graph_from_bytes, is_compressed, MappedFile};
+
+/// Indica se os resultados devem ser omitidos, com `--quiet`.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Indica se o andamento da leitura e dos algoritmos deve ser mostrado, com `--progress`.
+static PROGRESS: AtomicBool = AtomicBool::new(false);
+
+/// Indica se a entrada deve ser lida em paralelo, com `--parallel`.
+#[cfg(feature = "rayon")]
+static PARALLEL: AtomicBool = AtomicBool::new(false);
+
+/// Indica se o arquivo de entrada deve ser mapeado na memória, com `--mmap`.
+#[cfg(all(feature = "mmap", unix))]
+static MMAP: AtomicBool = AtomicBool::new(false);
+
+/// Como `print!`, mas sem escrever nada com `--quiet`.
+macro_rules! out {
+    ($($arg:tt)*) => {
+        if !QUIET.load(Ordering::Relaxed) {
+            print!($($arg)*);
+        }
+    };
+}
+
+/// Como `println!`, mas sem escrever nada com `--quiet`.
+macro_rules! outln {
+    ($($arg:tt)*) => {
+        if !QUIET.load(Ordering::Relaxed) {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Carrega grafos no formato `n m` / `a b w` e mostra informações sobre eles.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Estrutura usada para guardar o grafo. Só ela é construída, o que importa em entradas
+    /// grandes, já que a matriz ocupa memória proporcional ao quadrado da quantidade de nós. Sem
+    /// ela, `print` mostra a matriz e a lista, e os outros subcomandos usam a lista.
+    #[arg(long, global = true, value_enum)]
+    representation: Option<Representation>,
+    /// Formato dos resultados. `print` escreve o grafo, e `path` e `mst` escrevem as arestas
+    /// encontradas, como um grafo com os mesmos nós, nos formatos `dot`, `json` e `csv`. `convert`
+    /// usa a opção `--to`.
+    #[arg(long, global = true, value_enum, default_value = "plain")]
+    format: OutputFormat,
+    /// Não usa cores no terminal, assim como quando a variável `NO_COLOR` está definida.
+    #[arg(long, global = true)]
+    no_color: bool,
+    /// Não mostra os resultados, só os erros, para usar o programa em scripts: o resultado fica
+    /// no código de saída, que é 0 em caso de sucesso, 1 em caso de erro, como um arquivo
+    /// inválido, e 2 quando a consulta não tem resposta, como em `path` entre nós sem um caminho.
+    /// Este último caso também não mostra nenhuma mensagem.
+    #[arg(long, short, global = true)]
+    quiet: bool,
+    /// Mostra na saída de erro o andamento da leitura do arquivo, com a quantidade de linhas já
+    /// lidas, e o tempo de cada etapa, como a montagem do grafo e os algoritmos.
+    #[arg(long, global = true)]
+    progress: bool,
+    /// Lê o arquivo de entrada em paralelo, carregando-o inteiro na memória.
+    #[cfg(feature = "rayon")]
+    #[arg(long, global = true)]
+    parallel: bool,
+    /// Mapeia o arquivo de entrada na memória e o lê diretamente de lá, sem copiá-lo. Não aceita
+    /// a entrada padrão nem arquivos comprimidos.
+    #[cfg(all(feature = "mmap", unix))]
+    #[arg(long, global = true)]
+    mmap: bool,
+}
+
+/// Formatos aceitos por `--format`.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Texto para ser lido no terminal.
+    Plain,
+    Dot,
+    Json,
+    Csv,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Representation {
+    /// Lista de adjacência (`GraphAdj`).
+    Adj,
+    /// Matriz de adjacência (`GraphMat`).
+    Mat,
+    /// Formato CSR (`GraphCsr`).
+    Csr,
+    /// Lista de adjacência em um único vetor (`GraphArena`).
+    Arena,
+    /// Matriz de adjacência dividida em blocos (`GraphTiledMat`).
+    Tiled,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Mostra as arestas do grafo, montado como matriz e como lista de adjacência.
+    Print(Input),
+    /// Mostra a quantidade de nós e arestas, os graus, a densidade e as componentes conexas.
+    Stats(Input),
+    /// Mostra as estatísticas de vários arquivos, um por linha, e um resumo com o mínimo, a média
+    /// e o máximo de cada uma.
+    Batch {
+        /// Arquivos de entrada. Um diretório equivale a todos os arquivos dentro dele.
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Mostra um caminho mínimo entre dois nós e o seu peso total.
+    Path {
+        /// Arquivo de entrada, ou `-` para a entrada padrão. Como vem antes dos nós, não pode ser
+        /// omitido.
+        file: String,
+        /// Nó de origem, numerado a partir de 1 como no arquivo.
+        source: Node,
+        /// Nó de destino, numerado a partir de 1 como no arquivo.
+        target: Node,
+    },
+    /// Indica se há um caminho entre dois nós, numerados a partir de 1. Mostra `sim` ou `não`, e
+    /// termina com o código de saída 0 ou 2, respectivamente.
+    Reachable {
+        /// Arquivo de entrada, ou `-` para a entrada padrão.
+        file: String,
+        source: Node,
+        target: Node,
+    },
+    /// Mostra a quantidade de componentes conexas do grafo.
+    Components {
+        #[command(flatten)]
+        input: Input,
+        /// Mostra também os nós de cada componente.
+        #[arg(short, long)]
+        verbose: bool,
+    },
+    /// Mostra as arestas de uma árvore geradora mínima e o seu peso total.
+    Mst(Input),
+    /// Compara dois grafos, mostrando as arestas adicionadas, removidas e com peso alterado do
+    /// primeiro para o segundo.
+    Diff {
+        /// Grafo de antes, ou `-` para a entrada padrão.
+        before: String,
+        /// Grafo de depois, ou `-` para a entrada padrão.
+        after: String,
+    },
+    /// Responde uma pergunta sobre um nó ou uma aresta, com os nós numerados a partir de 1.
+    Query {
+        /// Arquivo de entrada, ou `-` para a entrada padrão.
+        file: String,
+        #[command(subcommand)]
+        query: Query,
+    },
+    /// Verifica o arquivo de entrada, mostrando todos os problemas encontrados, com as suas linhas.
+    ///
+    /// Além dos erros que impedem a leitura, aponta arestas repetidas, laços e linhas além das
+    /// arestas prometidas. Termina com um código de saída diferente de zero caso encontre algum
+    /// problema.
+    Validate(Input),
+    /// Compara o tempo de construção, a memória e o tempo de consulta das representações.
+    ///
+    /// Sem `--representation`, compara todas as representações aceitas pela opção.
+    Bench {
+        #[command(flatten)]
+        input: Input,
+        /// Quantidade de pares de nós consultados com `has_edge`.
+        #[arg(long, default_value_t = 100_000)]
+        queries: usize,
+    },
+    /// Abre um prompt para consultar e modificar o grafo com comandos como `neighbors 5`,
+    /// `weight 2 7`, `path 1 9`, `add-edge 3 4 10` e `stats`. `help` mostra todos os comandos.
+    Repl {
+        /// Arquivo de entrada. Como os comandos são lidos da entrada padrão, não pode ser `-`.
+        file: String,
+    },
+    /// Gera um grafo aleatório ou clássico, escrito no formato de entrada.
+    Generate {
+        /// Modelo do grafo gerado.
+        #[arg(long, value_enum)]
+        model: Model,
+        /// Quantidade de nós.
+        #[arg(long)]
+        nodes: usize,
+        /// Probabilidade de cada par de nós ser ligado, no modelo `gnp`.
+        #[arg(long, required_if_eq("model", "gnp"))]
+        prob: Option<f64>,
+        /// Quantidade de arestas, no modelo `gnm`.
+        #[arg(long, required_if_eq("model", "gnm"))]
+        edges: Option<usize>,
+        /// Semente do gerador. A mesma semente sempre gera o mesmo grafo.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Arquivo de saída. Sem ele, o grafo é escrito na saída padrão.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Desenha o grafo em SVG.
+    Visualize {
+        #[command(flatten)]
+        input: Input,
+        /// Arquivo de saída. Sem ele, a imagem é escrita na saída padrão.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Posicionamento dos nós.
+        #[arg(long, value_enum, default_value = "force")]
+        layout: LayoutKind,
+        /// Semente usada nas posições iniciais de `force`.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Escreve o grafo em DOT em vez de SVG, para desenhá-lo com o Graphviz, como em
+        /// `ex1 visualize grafo.txt --via-dot | dot -Tpng -o grafo.png`.
+        #[arg(long)]
+        via_dot: bool,
+    },
+    /// Converte um grafo de um formato para outro.
+    ///
+    /// Os formatos são deduzidos pelas extensões dos arquivos (`.dot`, `.json`, `.graphml`,
+    /// `.gml`, `.net`, `.dimacs`, `.mtx`, `.csv`, `.bin`; qualquer outra é o formato de entrada),
+    /// ou escolhidos com `--from` e `--to`. O grafo é sempre montado como lista de adjacência.
+    Convert {
+        #[command(flatten)]
+        input: Input,
+        /// Arquivo de saída. Sem ele, o resultado é escrito na saída padrão, e `--to` é
+        /// obrigatório.
+        output: Option<PathBuf>,
+        /// Formato da entrada, caso não possa ser deduzido pela extensão.
+        #[arg(long, value_enum)]
+        from: Option<Format>,
+        /// Formato da saída, caso não possa ser deduzido pela extensão.
+        #[arg(long, value_enum)]
+        to: Option<Format>,
+        /// Monta o grafo como direcionado. Sem essa opção, cada aresta lida vale nos dois
+        /// sentidos.
+        #[arg(long)]
+        directed: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum Query {
+    /// Grau do nó.
+    Degree { node: Node },
+    /// Vizinhos do nó.
+    Neighbors { node: Node },
+    /// Peso da aresta entre dois nós. Termina com erro caso ela não exista.
+    Edge { a: Node, b: Node },
+}
+
+#[derive(Args)]
+struct Input {
+    /// Arquivo de entrada. Sem nome, ou com `-`, lê da entrada padrão.
+    #[arg(default_value = "-")]
+    file: String,
+}
+
+/// Posicionamentos aceitos por `visualize --layout`.
+#[derive(Clone, Copy, ValueEnum)]
+enum LayoutKind {
+    /// Nós em um círculo, em ordem crescente.
+    Circular,
+    /// Nós ligados ficam próximos (`force_layout`).
+    Force,
+}
+
+/// Modelos aceitos por `generate`.
+#[derive(Clone, Copy, ValueEnum)]
+enum Model {
+    /// Erdős–Rényi: cada par de nós é ligado com probabilidade `--prob`.
+    Gnp,
+    /// Erdős–Rényi: `--edges` pares de nós, sorteados entre todos, são ligados.
+    Gnm,
+    /// Grafo completo: todos os pares de nós são ligados.
+    Complete,
+    /// Caminho passando por todos os nós, em ordem.
+    Path,
+    /// Ciclo passando por todos os nós, em ordem.
+    Cycle,
+    /// Estrela: o primeiro nó é ligado a todos os outros.
+    Star,
+    /// Roda: uma estrela cujas pontas também formam um ciclo.
+    Wheel,
+}
+
+/// Formatos aceitos por `convert`.
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// O próprio formato de entrada, `n m` seguido de `a b w`.
+    Input,
+    Dot,
+    Json,
+    Graphml,
+    Gml,
+    Pajek,
+    Dimacs,
+    MatrixMarket,
+    Csv,
+    /// Formato binário do crate, para carregar grafos grandes rapidamente.
+    Binary,
+    /// Matriz de adjacência completa, com uma linha de números por nó. Só pode ser escrita.
+    AdjacencyMatrix,
+}
+
+impl Format {
+    /// Deduz o formato pela extensão de `path`. Em arquivos comprimidos, como `grafo.dot.gz`,
+    /// usa a extensão anterior à da compressão.
+    fn from_extension(path: &Path) -> Option<Format> {
+        let mut extension = path.extension()?;
+        if extension == "gz" || extension == "zst" {
+            extension = Path::new(path.file_stem()?).extension()?;
+        }
+        Some(match extension.to_str()? {
+            "txt" => Format::Input,
+            "dot" | "gv" => Format::Dot,
+            "json" => Format::Json,
+            "graphml" => Format::Graphml,
+            "gml" => Format::Gml,
+            "net" => Format::Pajek,
+            "dimacs" | "gr" | "col" => Format::Dimacs,
+            "mtx" => Format::MatrixMarket,
+            "csv" => Format::Csv,
+            "bin" => Format::Binary,
+            _ => return None,
+        })
+    }
+}
+
+/// Erro de uma consulta que não tem resposta, como um caminho entre nós de componentes
+/// diferentes. O programa termina com o código de saída 2, e não 1.
+#[derive(Debug)]
+struct Infeasible(String);
+
+impl Display for Infeasible {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for Infeasible {}
+
+fn main() -> ExitCode {
+    // O `clap` usa o código 2 para argumentos inválidos, mas aqui ele indica uma consulta sem
+    // resposta, então esses erros terminam com o código 1.
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => {
+            let _ = e.print();
+            return if e.use_stderr() {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            };
+        }
+    };
+    QUIET.store(cli.quiet, Ordering::Relaxed);
+    PROGRESS.store(cli.progress, Ordering::Relaxed);
+    #[cfg(feature = "rayon")]
+    PARALLEL.store(cli.parallel, Ordering::Relaxed);
+    #[cfg(all(feature = "mmap", unix))]
+    MMAP.store(cli.mmap, Ordering::Relaxed);
+    // Em vez de causar um `panic`, os erros são mostrados de forma legível, e o programa termina
+    // com um código de saída diferente de zero.
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) if e.is::<Infeasible>() => {
+            if !QUIET.load(Ordering::Relaxed) && !e.to_string().is_empty() {
+                eprintln!("Erro: {e}");
+            }
+            ExitCode::from(2)
+        }
+        Err(e) => {
+            eprintln!("Erro: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// `Box<dyn Error>` aceita qualquer tipo de erro, então o operador `?` funciona tanto com os
+// erros de leitura do arquivo quanto com os de `fill_graph_from_reader`.
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let representation = cli.representation.unwrap_or(Representation::Adj);
+    let format = cli.format;
+    let terminal = Terminal::detect(cli.no_color);
+    match cli.command {
+        Command::Print(input) if format != OutputFormat::Plain => {
+            out!("{}", write_graph(&*load(&input, representation)?, format));
+        }
+        Command::Print(input) => match cli.representation {
+            Some(representation) => {
+                let graph = load(&input, representation)?;
+                let header = format!("Arestas do grafo por {}:", representation.name());
+                outln!("{}", terminal.paint(header, BOLD));
+                print_edges(&graph.edges(), terminal);
+            }
+            None => {
+                // A entrada padrão só pode ser lida uma vez, então a matriz é construída a partir
+                // da lista.
+                let graph_adj = load_adj(&input)?;
+                let graph_mat = GraphMat::from(&graph_adj);
+
+                outln!(
+                    "{}",
+                    terminal.paint("Arestas do grafo por matriz de adj:", BOLD)
+                );
+                print_edges(&graph_mat.edges(), terminal);
+                outln!(
+                    "{}",
+                    terminal.paint("Arestas do grafo por lista de adj:", BOLD)
+                );
+                print_edges(&graph_adj.edges(), terminal);
+            }
+        },
+        Command::Stats(input) => print_stats(&*load(&input, representation)?, format, terminal)?,
+        Command::Batch { files } => batch(&files, representation, format, terminal)?,
+        Command::Path {
+            file,
+            source,
+            target,
+        } => {
+            let graph = load(&Input { file }, representation)?;
+            let (a, b) = (node(&*graph, source)?, node(&*graph, target)?);
+            let found = stage("Buscando o caminho", || shortest_path(&*graph, a, b));
+            let Some((path, total)) = found else {
+                return Err(Infeasible(format!("não há caminho de {source} a {target}")).into());
+            };
+            let ids: Vec<_> = path.iter().map(|n| (n + 1).to_string()).collect();
+            match format {
+                OutputFormat::Plain => print_path(&path, total, terminal),
+                OutputFormat::Json => {
+                    outln!("{{\"path\": [{}], \"weight\": {total}}}", ids.join(", "));
+                }
+                OutputFormat::Dot | OutputFormat::Csv => {
+                    let edges: Vec<_> = path
+                        .windows(2)
+                        .map(|w| (w[0], w[1], graph.get_edge_weight(w[0], w[1]).unwrap()))
+                        .collect();
+                    out!("{}", write_edge_set(&*graph, &edges, true, format));
+                }
+            }
+        }
+        Command::Reachable {
+            file,
+            source,
+            target,
+        } => {
+            let graph = load(&Input { file }, representation)?;
+            let (a, b) = (node(&*graph, source)?, node(&*graph, target)?);
+            let reachable = stage("Buscando o caminho", || is_reachable(&*graph, a, b));
+            match format {
+                OutputFormat::Plain => outln!("{}", if reachable { "sim" } else { "não" }),
+                OutputFormat::Json => outln!("{{\"reachable\": {reachable}}}"),
+                OutputFormat::Csv => outln!("reachable\n{reachable}"),
+                OutputFormat::Dot => return Err(unsupported("reachable", format)),
+            }
+            // A resposta já foi mostrada, então o erro não tem mensagem.
+            if !reachable {
+                return Err(Infeasible(String::new()).into());
+            }
+        }
+        Command::Components { input, verbose } => {
+            let graph = load(&input, representation)?;
+            let components = stage("Buscando as componentes", || connected_components(&*graph));
+            let ids = |component: &Vec<Node>| -> Vec<String> {
+                component.iter().map(|n| (n + 1).to_string()).collect()
+            };
+            match format {
+                OutputFormat::Plain => {
+                    outln!("Componentes conexas: {}", components.len());
+                    if verbose {
+                        for (i, component) in components.iter().enumerate() {
+                            outln!("{}: {}", i + 1, ids(component).join(" "));
+                        }
+                    }
+                }
+                // Os formatos para outros programas sempre incluem os nós de cada componente.
+                OutputFormat::Json => {
+                    let lists: Vec<_> = components
+                        .iter()
+                        .map(|c| format!("[{}]", ids(c).join(", ")))
+                        .collect();
+                    outln!(
+                        "{{\"count\": {}, \"components\": [{}]}}",
+                        components.len(),
+                        lists.join(", ")
+                    );
+                }
+                OutputFormat::Csv => {
+                    outln!("node,component");
+                    for (i, component) in components.iter().enumerate() {
+                        for id in ids(component) {
+                            outln!("{id},{}", i + 1);
+                        }
+                    }
+                }
+                OutputFormat::Dot => return Err(unsupported("components", format)),
+            }
+        }
+        Command::Mst(input) => {
+            let graph = load(&input, representation)?;
+            let tree = stage("Montando a árvore geradora", || {
+                minimum_spanning_tree(&*graph)
+            });
+            if format != OutputFormat::Plain {
+                out!("{}", write_edge_set(&*graph, &tree, false, format));
+                return Ok(());
+            }
+            print_edges(&tree, terminal);
+            let total: Weight = tree.iter().map(|&(_, _, weight)| weight).sum();
+            outln!("{} {total}", terminal.paint("Peso total:", BOLD));
+            if tree.len() + 1 < graph.node_count() {
+                outln!("O grafo não é conexo, então o resultado é uma floresta.");
+            }
+        }
+        Command::Diff { before, after } => {
+            if before == "-" && after == "-" {
+                return Err("só um dos grafos pode vir da entrada padrão".into());
+            }
+            let changes = diff(
+                &*load(&Input { file: before }, representation)?,
+                &*load(&Input { file: after }, representation)?,
+            );
+            let (old_nodes, new_nodes) = changes.node_counts;
+            // Cada mudança de aresta: o tipo, os nós, numerados a partir de 1, e os pesos antes
+            // e depois, que faltam nas arestas adicionadas ou removidas.
+            type Change = (&'static str, Node, Node, Option<Weight>, Option<Weight>);
+            let mut rows: Vec<Change> = Vec::new();
+            rows.extend(
+                changes
+                    .removed
+                    .iter()
+                    .map(|&(a, b, w)| ("removed", a, b, Some(w), None)),
+            );
+            rows.extend(
+                changes
+                    .added
+                    .iter()
+                    .map(|&(a, b, w)| ("added", a, b, None, Some(w))),
+            );
+            rows.extend(
+                changes
+                    .reweighted
+                    .iter()
+                    .map(|&(a, b, w, v)| ("reweighted", a, b, Some(w), Some(v))),
+            );
+            rows.sort_by_key(|&(_, a, b, _, _)| (a, b));
+            let weight = |w: Option<Weight>| w.map_or(String::new(), |w| w.to_string());
+            match format {
+                OutputFormat::Plain => {
+                    if old_nodes != new_nodes {
+                        outln!("Nós: {old_nodes} -> {new_nodes}");
+                    }
+                    for &(change, a, b, old, new) in &rows {
+                        let (a, b) = (a + 1, b + 1);
+                        let line = match change {
+                            "removed" => {
+                                terminal.paint(format!("- {a} {b} {}", weight(old)), REMOVED)
+                            }
+                            "added" => terminal.paint(format!("+ {a} {b} {}", weight(new)), ADDED),
+                            _ => terminal.paint(
+                                format!("~ {a} {b} {} -> {}", weight(old), weight(new)),
+                                HIGHLIGHT,
+                            ),
+                        };
+                        outln!("{line}");
+                    }
+                    if changes.is_empty() {
+                        outln!("Os grafos são iguais.");
+                    } else {
+                        outln!(
+                            "Arestas adicionadas: {}, removidas: {}, com peso alterado: {}.",
+                            changes.added.len(),
+                            changes.removed.len(),
+                            changes.reweighted.len()
+                        );
+                    }
+                }
+                OutputFormat::Json => {
+                    let list = |change: &str| {
+                        let items: Vec<_> = rows
+                            .iter()
+                            .filter(|row| row.0 == change)
+                            .map(|&(_, a, b, old, new)| {
+                                let weights: Vec<_> = [old, new]
+                                    .into_iter()
+                                    .flatten()
+                                    .map(|w| w.to_string())
+                                    .collect();
+                                format!("[{}, {}, {}]", a + 1, b + 1, weights.join(", "))
+                            })
+                            .collect();
+                        format!("[{}]", items.join(", "))
+                    };
+                    let nodes = format!("{{\"before\": {old_nodes}, \"after\": {new_nodes}}}");
+                    let fields: Vec<_> = [("nodes", nodes)]
+                        .into_iter()
+                        .chain(
+                            ["added", "removed", "reweighted"].map(|change| (change, list(change))),
+                        )
+                        .map(|(key, value)| format!("\"{key}\": {value}"))
+                        .collect();
+                    outln!("{{{}}}", fields.join(", "));
+                }
+                OutputFormat::Csv => {
+                    outln!("change,source,target,old_weight,new_weight");
+                    for &(change, a, b, old, new) in &rows {
+                        outln!(
+                            "{change},{},{},{},{}",
+                            a + 1,
+                            b + 1,
+                            weight(old),
+                            weight(new)
+                        );
+                    }
+                }
+                OutputFormat::Dot => return Err(unsupported("diff", format)),
+            }
+        }
+        Command::Query { file, query } => {
+            let graph = load(&Input { file }, representation)?;
+            let graph = &*graph;
+            // Cada resposta é um conjunto de campos, escrito como texto, JSON ou CSV.
+            let fields: Vec<(&str, String)> = match query {
+                Query::Degree { node: id } => {
+                    let n = node(graph, id)?;
+                    vec![
+                        ("node", id.to_string()),
+                        ("degree", graph.degree(n).to_string()),
+                    ]
+                }
+                Query::Neighbors { node: id } => {
+                    let n = node(graph, id)?;
+                    let neighbors: Vec<_> = graph.neighbors(n).map(|b| b + 1).collect();
+                    let list = |separator: &str| {
+                        let ids: Vec<_> = neighbors.iter().map(Node::to_string).collect();
+                        ids.join(separator)
+                    };
+                    let value = match format {
+                        OutputFormat::Json => format!("[{}]", list(", ")),
+                        _ => list(" "),
+                    };
+                    vec![("node", id.to_string()), ("neighbors", value)]
+                }
+                Query::Edge { a, b } => {
+                    let (x, y) = (node(graph, a)?, node(graph, b)?);
+                    let Some(weight) = graph.get_edge_weight(x, y) else {
+                        return Err(Infeasible(format!("não há aresta entre {a} e {b}")).into());
+                    };
+                    vec![
+                        ("source", a.to_string()),
+                        ("target", b.to_string()),
+                        ("weight", weight.to_string()),
+                    ]
+                }
+            };
+            match format {
+                // A resposta é o último campo; os anteriores apenas repetem a pergunta.
+                OutputFormat::Plain => outln!("{}", fields.last().unwrap().1),
+                OutputFormat::Json => {
+                    let fields: Vec<_> = fields
+                        .iter()
+                        .map(|(key, value)| format!("\"{key}\": {value}"))
+                        .collect();
+                    outln!("{{{}}}", fields.join(", "));
+                }
+                OutputFormat::Csv => {
+                    let keys: Vec<_> = fields.iter().map(|(key, _)| *key).collect();
+                    let values: Vec<_> = fields.iter().map(|(_, value)| value.as_str()).collect();
+                    outln!("{}\n{}", keys.join(","), values.join(","));
+                }
+                OutputFormat::Dot => return Err(unsupported("query", format)),
+            }
+        }
+        Command::Validate(input) => {
+            let problems = validate_input(open(&input)?, &InputOptions::default())?;
+            for problem in &problems {
+                outln!("{problem}");
+            }
+            match problems.len() {
+                0 => outln!("Nenhum problema encontrado."),
+                1 => return Err(format!("1 problema encontrado em {}", input.file).into()),
+                count => {
+                    return Err(format!("{count} problemas encontrados em {}", input.file).into())
+                }
+            }
+        }
+        Command::Bench { input, queries } => {
+            let representations = match cli.representation {
+                Some(representation) => vec![representation],
+                None => vec![
+                    Representation::Adj,
+                    Representation::Mat,
+                    Representation::Csr,
+                    Representation::Arena,
+                    Representation::Tiled,
+                ],
+            };
+            bench(&input, &representations, queries, format)?;
+        }
+        Command::Repl { file } => {
+            if file == "-" {
+                let message = "o modo interativo lê os comandos da entrada padrão, então o grafo \
+                               precisa vir de um arquivo";
+                return Err(message.into());
+            }
+            if cli.quiet {
+                return Err("o modo interativo não pode ser usado com --quiet".into());
+            }
+            repl(&mut *load(&Input { file }, representation)?, terminal)?;
+        }
+        Command::Visualize {
+            input,
+            output,
+            layout,
+            seed,
+            via_dot,
+        } => {
+            let graph = load(&input, representation)?;
+            let image = if via_dot {
+                to_dot(&*graph, &DotOptions::default())
+            } else {
+                let layout = stage("Posicionando os nós", || match layout {
+                    LayoutKind::Circular => circular_layout(&*graph),
+                    LayoutKind::Force => force_layout(&*graph, 300, seed),
+                });
+                to_svg(&*graph, &layout)
+            };
+            match output {
+                Some(path) => std::fs::write(&path, image)
+                    .map_err(|e| format!("falha ao criar {}: {e}", path.display()))?,
+                None => out!("{image}"),
+            }
+        }
+        Command::Generate {
+            model,
+            nodes,
+            prob,
+            edges,
+            seed,
+            output,
+        } => {
+            let graph: GraphAdj = match model {
+                Model::Gnp => {
+                    // `clap` já garante que `--prob` foi passado com `--model gnp`.
+                    let prob = prob.unwrap_or_default();
+                    if !(0.0..=1.0).contains(&prob) {
+                        return Err(format!(
+                            "a probabilidade precisa estar entre 0 e 1, não {prob}"
+                        )
+                        .into());
+                    }
+                    gnp(nodes, prob, seed)
+                }
+                Model::Gnm => {
+                    let edges = edges.unwrap_or_default();
+                    let pairs = nodes as u64 * nodes.saturating_sub(1) as u64 / 2;
+                    if edges as u64 > pairs {
+                        return Err(format!(
+                            "{nodes} nós formam só {pairs} pares, então não cabem {edges} arestas"
+                        )
+                        .into());
+                    }
+                    gnm(nodes, edges, seed)
+                }
+                Model::Complete => complete(nodes),
+                Model::Path => path(nodes),
+                Model::Cycle => cycle(nodes),
+                Model::Star => star(nodes),
+                Model::Wheel => wheel(nodes),
+            };
+            match output {
+                Some(path) => {
+                    let file = File::create(&path)
+                        .map_err(|e| format!("falha ao criar {}: {e}", path.display()))?;
+                    write_input_format(&graph, BufWriter::new(file))?;
+                }
+                None => write_input_format(&graph, std::io::stdout().lock())?,
+            }
+        }
+        Command::Convert {
+            input,
+            output,
+            from,
+            to,
+            directed,
+        } => {
+            // Sem extensão conhecida, a entrada está no formato do exercício.
+            let from = from
+                .or_else(|| Format::from_extension(Path::new(&input.file)))
+                .unwrap_or(Format::Input);
+            let to = match (to, &output) {
+                (Some(to), _) => to,
+                (None, Some(path)) => Format::from_extension(path).ok_or_else(|| {
+                    format!(
+                        "não foi possível deduzir o formato de {}; use --to",
+                        path.display()
+                    )
+                })?,
+                (None, None) => {
+                    return Err("sem arquivo de saída, informe o formato com --to".into())
+                }
+            };
+
+            let mut bytes = Vec::new();
+            open(&input)?.read_to_end(&mut bytes)?;
+            let mut graph = if directed {
+                GraphAdj::directed()
+            } else {
+                GraphAdj::default()
+            };
+            read_as(&bytes, from, &mut graph)?;
+            let bytes = write_as(&graph, to);
+            match output {
+                Some(path) => std::fs::write(&path, bytes)
+                    .map_err(|e| format!("falha ao criar {}: {e}", path.display()))?,
+                None => std::io::stdout().lock().write_all(&bytes)?,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Lê `bytes`, no formato `format`, para `graph`.
+fn read_as(bytes: &[u8], format: Format, graph: &mut GraphAdj) -> Result<(), Box<dyn Error>> {
+    match format {
+        Format::Input => fill_graph_from_reader(bytes, graph)?,
+        Format::Binary => {
+            from_binary(bytes, graph)?;
+        }
+        Format::AdjacencyMatrix => {
+            return Err("o formato adjacency-matrix só pode ser escrito".into());
+        }
+        _ => {
+            let text = std::str::from_utf8(bytes).map_err(|_| "a entrada não é um texto UTF-8")?;
+            match format {
+                Format::Dot => {
+                    read_dot(text, graph)?;
+                }
+                Format::Json => {
+                    from_json(text, graph)?;
+                }
+                Format::Graphml => {
+                    read_graphml(text, graph)?;
+                }
+                Format::Gml => {
+                    read_gml(text, graph)?;
+                }
+                Format::Pajek => {
+                    read_pajek(text, graph)?;
+                }
+                Format::Dimacs => {
+                    read_dimacs(text, graph)?;
+                }
+                Format::MatrixMarket => {
+                    read_matrix_market(text, graph)?;
+                }
+                Format::Csv => {
+                    read_csv(text, graph, &CsvOptions::default())?;
+                }
+                Format::Input | Format::Binary | Format::AdjacencyMatrix => unreachable!(),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Escreve `graph` no formato `format`.
+fn write_as(graph: &GraphAdj, format: Format) -> Vec<u8> {
+    let text = match format {
+        Format::Input => {
+            let mut bytes = Vec::new();
+            // Escrever em um `Vec` nunca falha.
+            write_input_format(graph, &mut bytes).unwrap();
+            return bytes;
+        }
+        Format::Binary => return to_binary(graph),
+        Format::Dot => to_dot(graph, &DotOptions::default()),
+        Format::Json => to_json(graph),
+        Format::Graphml => to_graphml(graph),
+        Format::Gml => to_gml(graph),
+        Format::Pajek => to_pajek(graph),
+        Format::Dimacs => to_dimacs(graph),
+        Format::MatrixMarket => to_matrix_market(graph),
+        Format::Csv => to_csv(graph, &CsvOptions::default()),
+        Format::AdjacencyMatrix => to_adjacency_matrix(graph),
+    };
+    text.into_bytes()
+}
+
+/// Escreve `graph` em um dos formatos de `--format` diferentes de `plain`.
+fn write_graph(graph: &dyn GraphRef, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Plain | OutputFormat::Dot => to_dot(graph, &DotOptions::default()),
+        OutputFormat::Json => to_json(graph),
+        OutputFormat::Csv => to_csv(graph, &CsvOptions::default()),
+    }
+}
+
+/// Escreve as arestas `edges`, encontradas em `graph`, como um grafo com os mesmos nós.
+fn write_edge_set(
+    graph: &dyn GraphRef,
+    edges: &[Edge],
+    directed: bool,
+    format: OutputFormat,
+) -> String {
+    let mut result: GraphAdj = if directed {
+        GraphAdj::directed()
+    } else {
+        GraphAdj::default()
+    };
+    // Os grafos lidos de um arquivo não têm buracos, então os nós são os mesmos de `graph`.
+    result.add_nodes(graph.node_count());
+    result.add_edges(edges.iter().copied());
+    write_graph(&result, format)
+}
+
+/// Erro para um subcomando cujo resultado não pode ser escrito em `format`.
+fn unsupported(command: &str, format: OutputFormat) -> Box<dyn Error> {
+    let name = format.to_possible_value().unwrap();
+    format!("o formato {} não se aplica a `{command}`", name.get_name()).into()
+}
+
+impl Representation {
+    /// Nome usado na opção `--representation`.
+    fn key(self) -> &'static str {
+        match self {
+            Representation::Adj => "adj",
+            Representation::Mat => "mat",
+            Representation::Csr => "csr",
+            Representation::Arena => "arena",
+            Representation::Tiled => "tiled",
+        }
+    }
+    fn name(self) -> &'static str {
+        match self {
+            Representation::Adj => "lista de adj",
+            Representation::Mat => "matriz de adj",
+            Representation::Csr => "CSR",
+            Representation::Arena => "arena",
+            Representation::Tiled => "matriz em blocos",
+        }
+    }
+}
+
+/// Lê o grafo do arquivo de entrada, ou da entrada padrão, guardando-o em `representation`.
+fn load(
+    input: &Input,
+    representation: Representation,
+) -> Result<Box<dyn GraphMut>, Box<dyn Error>> {
+    Ok(match representation {
+        Representation::Adj => Box::new(load_adj(input)?),
+        Representation::Mat => {
+            let mut graph = GraphMat::default();
+            fill(input, &mut graph)?;
+            Box::new(graph)
+        }
+        // Cada aresta adicionada a um `GraphCsr` desloca os seus vetores, então ele é construído
+        // de uma vez a partir da lista.
+        Representation::Csr => {
+            let graph = load_adj(input)?;
+            let bound = (0..)
+                .filter(|&n| graph.has_node(n))
+                .take(graph.node_count())
+                .last();
+            let node_count = bound.map_or(0, |n| n as usize + 1);
+            stage("Montando o CSR", || {
+                Box::new(GraphCsr::from_edges(node_count, graph.edges_iter()))
+            })
+        }
+        Representation::Arena => {
+            let mut graph = GraphArena::default();
+            fill(input, &mut graph)?;
+            Box::new(graph)
+        }
+        Representation::Tiled => {
+            let mut graph = GraphTiledMat::default();
+            fill(input, &mut graph)?;
+            Box::new(graph)
+        }
+    })
+}
+
+/// Medidas de [`bench`] para uma representação.
+struct Measurement {
+    representation: Representation,
+    build: Duration,
+    memory: usize,
+    lookups: Duration,
+    traversal: Duration,
+}
+
+/// Mede cada uma das `representations` com o grafo de `input`, e escreve a comparação.
+fn bench(
+    input: &Input,
+    representations: &[Representation],
+    queries: usize,
+    format: OutputFormat,
+) -> Result<(), Box<dyn Error>> {
+    // O texto é lido uma única vez, para que a construção não meça a leitura do disco, e para
+    // que a entrada padrão possa ser usada por todas as representações.
+    let mut text = Vec::new();
+    open(input)?.read_to_end(&mut text)?;
+
+    let mut results = Vec::new();
+    for &representation in representations {
+        let start = Instant::now();
+        let (graph, memory): (Box<dyn GraphRef>, usize) = match representation {
+            Representation::Adj => {
+                let mut graph = GraphAdj::default();
+                fill_graph_from_reader(&text[..], &mut graph)?;
+                let memory = graph.memory_usage();
+                (Box::new(graph), memory)
+            }
+            Representation::Mat => {
+                let mut graph = GraphMat::default();
+                fill_graph_from_reader(&text[..], &mut graph)?;
+                let memory = graph.memory_usage();
+                (Box::new(graph), memory)
+            }
+            Representation::Csr => {
+                let mut adj = GraphAdj::default();
+                fill_graph_from_reader(&text[..], &mut adj)?;
+                let graph = GraphCsr::from_edges(adj.node_count(), adj.edges_iter());
+                let memory = graph.memory_usage();
+                (Box::new(graph), memory)
+            }
+            Representation::Arena => {
+                let mut graph = GraphArena::default();
+                fill_graph_from_reader(&text[..], &mut graph)?;
+                let memory = graph.memory_usage();
+                (Box::new(graph), memory)
+            }
+            Representation::Tiled => {
+                let mut graph = GraphTiledMat::default();
+                fill_graph_from_reader(&text[..], &mut graph)?;
+                let memory = graph.memory_usage();
+                (Box::new(graph), memory)
+            }
+        };
+        let build = start.elapsed();
+
+        // Os mesmos pares, sorteados com uma semente fixa, são consultados em todas as
+        // representações. A soma impede que o compilador descarte as consultas.
+        let nodes = graph.node_count() as u64;
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        let mut next = || {
+            // xorshift64: um gerador simples, suficiente para espalhar as consultas.
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % nodes.max(1)) as Node
+        };
+        let start = Instant::now();
+        let mut found = 0;
+        for _ in 0..if nodes == 0 { 0 } else { queries } {
+            found += graph.has_edge(next(), next()) as usize;
+        }
+        std::hint::black_box(found);
+        let lookups = start.elapsed();
+
+        let start = Instant::now();
+        let mut visited = 0;
+        for n in 0..nodes as Node {
+            graph.for_each_neighbor(n, &mut |_| visited += 1);
+        }
+        std::hint::black_box(visited);
+        let traversal = start.elapsed();
+
+        results.push(Measurement {
+            representation,
+            build,
+            memory,
+            lookups,
+            traversal,
+        });
+    }
+
+    match format {
+        OutputFormat::Plain => {
+            outln!(
+                "{:<16} {:>12} {:>14} {:>12} {:>12}",
+                "Estrutura",
+                "Construção",
+                "Memória (B)",
+                "Consultas",
+                "Percurso"
+            );
+            for m in &results {
+                outln!(
+                    "{:<16} {:>12} {:>14} {:>12} {:>12}",
+                    m.representation.name(),
+                    format!("{:.2?}", m.build),
+                    m.memory,
+                    format!("{:.2?}", m.lookups),
+                    format!("{:.2?}", m.traversal)
+                );
+            }
+        }
+        // Nos formatos para outros programas, os tempos são escritos em segundos.
+        OutputFormat::Json => {
+            let rows: Vec<_> = results
+                .iter()
+                .map(|m| {
+                    let fields = [
+                        ("representation", format!("\"{}\"", m.representation.key())),
+                        ("build", m.build.as_secs_f64().to_string()),
+                        ("memory", m.memory.to_string()),
+                        ("lookups", m.lookups.as_secs_f64().to_string()),
+                        ("traversal", m.traversal.as_secs_f64().to_string()),
+                    ];
+                    let fields: Vec<_> = fields
+                        .iter()
+                        .map(|(key, value)| format!("\"{key}\": {value}"))
+                        .collect();
+                    format!("{{{}}}", fields.join(", "))
+                })
+                .collect();
+            outln!("[{}]", rows.join(", "));
+        }
+        OutputFormat::Csv => {
+            outln!("representation,build,memory,lookups,traversal");
+            for m in &results {
+                outln!(
+                    "{},{},{},{},{}",
+                    m.representation.key(),
+                    m.build.as_secs_f64(),
+                    m.memory,
+                    m.lookups.as_secs_f64(),
+                    m.traversal.as_secs_f64()
+                );
+            }
+        }
+        OutputFormat::Dot => return Err(unsupported("bench", format)),
+    }
+    Ok(())
+}
+
+/// Comandos do modo interativo, mostrados por `help`.
+const REPL_HELP: &str = "\
+Comandos (os nós são numerados a partir de 1, como no arquivo):
+  neighbors <n>          vizinhos de n
+  degree <n>             grau de n
+  weight <a> <b>         peso da aresta a b
+  path <a> <b>           caminho mínimo de a até b
+  add-edge <a> <b> [w]   adiciona a aresta a b, com peso w (1 caso omitido)
+  remove-edge <a> <b>    remove a aresta a b
+  add-node               adiciona um nó
+  stats                  estatísticas do grafo
+  edges                  arestas do grafo
+  help                   mostra esta mensagem
+  quit                   sai do programa";
+
+/// Lê comandos da entrada padrão até o fim dela, ou até `quit`, aplicando-os a `graph`.
+fn repl(graph: &mut dyn GraphMut, terminal: Terminal) -> Result<(), Box<dyn Error>> {
+    println!(
+        "Grafo com {} nós. Digite `help` para ver os comandos.",
+        graph.node_count()
+    );
+    let mut line = String::new();
+    loop {
+        print!("> ");
+        std::io::stdout().flush()?;
+        line.clear();
+        if std::io::stdin().read_line(&mut line)? == 0 {
+            // Fim da entrada, como com Ctrl-D.
+            println!();
+            return Ok(());
+        }
+        let words: Vec<_> = line.split_whitespace().collect();
+        match words.first() {
+            None => continue,
+            Some(&"quit" | &"exit") => return Ok(()),
+            Some(_) => {
+                // Um comando inválido não encerra o modo interativo: o erro é mostrado, e o
+                // próximo comando é lido.
+                if let Err(e) = repl_command(graph, &words, terminal) {
+                    println!("Erro: {e}");
+                }
+            }
+        }
+    }
+}
+
+/// Executa um comando do modo interativo. `words` tem pelo menos uma palavra.
+fn repl_command(
+    graph: &mut dyn GraphMut,
+    words: &[&str],
+    terminal: Terminal,
+) -> Result<(), Box<dyn Error>> {
+    let args = &words[1..];
+    // Lê o argumento `i` como um nó do grafo, numerado a partir de 1.
+    let node_arg = |graph: &dyn GraphRef, i: usize| -> Result<Node, Box<dyn Error>> {
+        let word = args.get(i).ok_or("faltam argumentos; veja `help`")?;
+        let id: Node = word.parse().map_err(|_| format!("nó inválido: {word}"))?;
+        Ok(node(graph, id)?)
+    };
+    let expect_args = |count: usize| -> Result<(), Box<dyn Error>> {
+        if args.len() > count {
+            return Err("argumentos demais; veja `help`".into());
+        }
+        Ok(())
+    };
+    match words[0] {
+        "help" => println!("{REPL_HELP}"),
+        "neighbors" => {
+            expect_args(1)?;
+            let n = node_arg(graph, 0)?;
+            let neighbors: Vec<_> = graph.neighbors(n).map(|b| (b + 1).to_string()).collect();
+            if neighbors.is_empty() {
+                println!("O nó {} não tem vizinhos.", n + 1);
+            } else {
+                println!("{}", neighbors.join(" "));
+            }
+        }
+        "degree" => {
+            expect_args(1)?;
+            println!("{}", graph.degree(node_arg(graph, 0)?));
+        }
+        "weight" => {
+            expect_args(2)?;
+            let (a, b) = (node_arg(graph, 0)?, node_arg(graph, 1)?);
+            match graph.get_edge_weight(a, b) {
+                Some(weight) => println!("{weight}"),
+                None => println!("Não há aresta entre {} e {}.", a + 1, b + 1),
+            }
+        }
+        "path" => {
+            expect_args(2)?;
+            let (a, b) = (node_arg(graph, 0)?, node_arg(graph, 1)?);
+            match shortest_path(graph, a, b) {
+                Some((path, total)) => print_path(&path, total, terminal),
+                None => println!("Não há caminho de {} a {}.", a + 1, b + 1),
+            }
+        }
+        "add-edge" => {
+            expect_args(3)?;
+            let (a, b) = (node_arg(graph, 0)?, node_arg(graph, 1)?);
+            let weight = match args.get(2) {
+                Some(w) => w.parse().map_err(|_| format!("peso inválido: {w}"))?,
+                None => 1,
+            };
+            if a == b && graph.self_loops() == SelfLoops::Reject {
+                return Err("o grafo não aceita laços".into());
+            }
+            graph.add_edge(a, b, weight);
+        }
+        "remove-edge" => {
+            expect_args(2)?;
+            let (a, b) = (node_arg(graph, 0)?, node_arg(graph, 1)?);
+            if graph.remove_edge(a, b).is_none() {
+                println!("Não há aresta entre {} e {}.", a + 1, b + 1);
+            }
+        }
+        "add-node" => {
+            expect_args(0)?;
+            println!("Nó {} adicionado.", graph.add_node() + 1);
+        }
+        "stats" => {
+            expect_args(0)?;
+            print_stats(graph, OutputFormat::Plain, terminal)?;
+        }
+        "edges" => {
+            expect_args(0)?;
+            print_edges(&graph.edges(), terminal);
+        }
+        command => return Err(format!("comando desconhecido: {command}; veja `help`").into()),
+    }
+    Ok(())
+}
+
+/// Converte um nó numerado a partir de 1, como no arquivo, para o nó correspondente do grafo.
+fn node(graph: &dyn GraphRef, id: Node) -> Result<Node, String> {
+    match id.checked_sub(1) {
+        Some(n) if graph.has_node(n) => Ok(n),
+        _ => Err(format!("o nó {id} não existe no grafo")),
+    }
+}
+
+/// Lê o grafo como uma lista de adjacência.
+fn load_adj(input: &Input) -> Result<GraphAdj, Box<dyn Error>> {
+    let mut graph = GraphAdj::default();
+    fill(input, &mut graph)?;
+    Ok(graph)
+}
+
+/// Lê o arquivo de entrada para `graph`, em paralelo caso `--parallel` tenha sido usado.
+fn fill(input: &Input, graph: &mut dyn GraphMut) -> Result<(), Box<dyn Error>> {
+    #[cfg(all(feature = "mmap", unix))]
+    if MMAP.load(Ordering::Relaxed) {
+        return fill_mapped(input, graph);
+    }
+    #[cfg(feature = "rayon")]
+    if PARALLEL.load(Ordering::Relaxed) {
+        let mut text = String::new();
+        open(input)?.read_to_string(&mut text)?;
+        return Ok(par_fill_graph(&text, graph, &InputOptions::default())?);
+    }
+    fill_graph_from_reader(open(input)?, graph)?;
+    Ok(())
+}
+
+/// Lê o arquivo de entrada mapeando-o na memória, com `--mmap`.
+///
+/// Como o texto não passa por um leitor, `--progress` só mostra o tempo da leitura inteira.
+#[cfg(all(feature = "mmap", unix))]
+fn fill_mapped(input: &Input, graph: &mut dyn GraphMut) -> Result<(), Box<dyn Error>> {
+    let filename = &input.file;
+    if filename == "-" {
+        return Err("--mmap precisa de um arquivo, e não da entrada padrão".into());
+    }
+    // O programa não modifica o arquivo; caso outro programa o modifique durante a leitura, o
+    // resultado pode ser um grafo incorreto.
+    let map = unsafe { MappedFile::open(filename) }
+        .map_err(|e| format!("falha ao abrir {filename}: {e}"))?;
+    if is_compressed(&map) {
+        return Err(format!("{filename} está comprimido, e não pode ser lido com --mmap").into());
+    }
+    stage(&format!("Lendo {filename}"), || {
+        #[cfg(feature = "rayon")]
+        if PARALLEL.load(Ordering::Relaxed) {
+            let text = std::str::from_utf8(&map)
+                .map_err(|e| format!("{filename} não é um texto UTF-8 válido: {e}"))?;
+            return Ok(par_fill_graph(text, graph, &InputOptions::default())?);
+        }
+        Ok(fill_graph_from_bytes(
+            &map,
+            graph,
+            &InputOptions::default(),
+        )?)
+    })
+}
+
+/// Abre o arquivo de entrada, ou a entrada padrão.
+fn open(input: &Input) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
+    let filename = &input.file;
+    // A leitura é feita em blocos, permitindo que o arquivo seja processado uma linha por vez,
+    // sem precisar guardá-lo inteiro na memória.
+    let reader = open_input(filename).map_err(|e| format!("falha ao abrir {filename}: {e}"))?;
+    if PROGRESS.load(Ordering::Relaxed) {
+        return Ok(Box::new(Progress::new(reader, filename)));
+    }
+    Ok(reader)
+}
+
+/// Leitor que conta as linhas lidas e, periodicamente, mostra a contagem na saída de erro.
+///
+/// A contagem é feita em `consume`, então vale tanto para a leitura por linhas quanto para
+/// `read_to_end`. Como as entradas comprimidas são descomprimidas antes, o total do arquivo não
+/// serve para calcular uma porcentagem, e só a quantidade já lida é mostrada.
+struct Progress<R> {
+    inner: R,
+    name: String,
+    lines: u64,
+    bytes: u64,
+    start: Instant,
+    last: Instant,
+}
+
+impl<R: BufRead> Progress<R> {
+    /// Intervalo mínimo entre duas atualizações, para não deixar a leitura mais lenta.
+    const INTERVAL: Duration = Duration::from_millis(250);
+
+    fn new(inner: R, name: &str) -> Self {
+        let now = Instant::now();
+        Self {
+            inner,
+            name: name.to_string(),
+            lines: 0,
+            bytes: 0,
+            start: now,
+            last: now,
+        }
+    }
+    fn report(&self, end: &str) {
+        // `\r` volta ao início da linha, então cada atualização substitui a anterior.
+        eprint!(
+            "\rLendo {}: {} linhas, {:.1} MB ({:.1?}){end}",
+            self.name,
+            self.lines,
+            self.bytes as f64 / 1e6,
+            self.start.elapsed()
+        );
+    }
+}
+
+impl<R: BufRead> Read for Progress<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let available = self.fill_buf()?;
+        let amount = available.len().min(buf.len());
+        buf[..amount].copy_from_slice(&available[..amount]);
+        self.consume(amount);
+        Ok(amount)
+    }
+}
+
+impl<R: BufRead> BufRead for Progress<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+    fn consume(&mut self, amount: usize) {
+        // Os dados já estão no buffer, então `fill_buf` não lê nada de novo aqui.
+        if let Ok(buf) = self.inner.fill_buf() {
+            let consumed = &buf[..amount.min(buf.len())];
+            self.lines += consumed.iter().filter(|&&b| b == b'\n').count() as u64;
+        }
+        self.bytes += amount as u64;
+        self.inner.consume(amount);
+        if self.last.elapsed() >= Self::INTERVAL {
+            self.last = Instant::now();
+            self.report("");
+        }
+    }
+}
+
+impl<R> Drop for Progress<R> {
+    fn drop(&mut self) {
+        eprintln!(
+            "\rLido {}: {} linhas, {:.1} MB em {:.1?}",
+            self.name,
+            self.lines,
+            self.bytes as f64 / 1e6,
+            self.start.elapsed()
+        );
+    }
+}
+
+/// Executa uma etapa demorada, mostrando na saída de erro quanto tempo ela levou, caso
+/// `--progress` tenha sido usado.
+fn stage<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    if !PROGRESS.load(Ordering::Relaxed) {
+        return f();
+    }
+    eprint!("{name}...");
+    let start = Instant::now();
+    let result = f();
+    eprintln!(" {:.1?}", start.elapsed());
+    result
+}
+
+/// Estatísticas calculadas por [`stats`]: o nome usado nos formatos para outros programas, o nome
+/// mostrado no terminal e as casas decimais usadas ao escrever o valor.
+const STATS: [(&str, &str, usize); 8] = [
+    ("nodes", "Nós", 0),
+    ("edges", "Arestas", 0),
+    ("self_loops", "Laços", 0),
+    ("min_degree", "Grau mínimo", 0),
+    ("max_degree", "Grau máximo", 0),
+    ("average_degree", "Grau médio", 2),
+    ("density", "Densidade", 4),
+    ("components", "Componentes conexas", 0),
+];
+
+/// Calcula as estatísticas de [`STATS`], na mesma ordem.
+fn stats(graph: &dyn GraphRef) -> [f64; 8] {
+    let nodes = graph.node_count();
+    let edges = graph.edge_count();
+    let self_loops = graph.edges_iter().filter(|&(a, b, _)| a == b).count();
+    let degrees: Vec<_> = (0..)
+        .filter(|&n| graph.has_node(n))
+        .take(nodes)
+        .map(|n| graph.degree(n))
         .collect();
+    let min = degrees.iter().min().copied().unwrap_or(0);
+    let max = degrees.iter().max().copied().unwrap_or(0);
+    let average = if nodes == 0 {
+        0.0
+    } else {
+        degrees.iter().sum::<usize>() as f64 / nodes as f64
+    };
+    // Fração dos pares de nós distintos ligados por uma aresta.
+    let density = if nodes < 2 {
+        0.0
+    } else {
+        2.0 * (edges - self_loops) as f64 / (nodes * (nodes - 1)) as f64
+    };
+
+    let components = connected_components(graph).len();
+
+    [
+        nodes as f64,
+        edges as f64,
+        self_loops as f64,
+        min as f64,
+        max as f64,
+        average,
+        density,
+        components as f64,
+    ]
+}
+
+/// Escreve os valores de [`stats`] com as casas decimais de cada estatística, ou com pelo menos
+/// `precision` casas.
+fn stat_texts(values: &[f64; 8], precision: usize) -> Vec<String> {
+    values
+        .iter()
+        .zip(STATS)
+        .map(|(value, (_, _, digits))| format!("{value:.*}", digits.max(precision)))
+        .collect()
+}
+
+/// Junta os valores de [`stats`] em um objeto JSON.
+fn stats_json(values: &[f64; 8], precision: usize) -> String {
+    let fields: Vec<_> = STATS
+        .iter()
+        .zip(stat_texts(values, precision))
+        .map(|((key, _, _), value)| format!("\"{key}\": {value}"))
+        .collect();
+    format!("{{{}}}", fields.join(", "))
+}
+
+fn print_stats(
+    graph: &dyn GraphRef,
+    format: OutputFormat,
+    terminal: Terminal,
+) -> Result<(), Box<dyn Error>> {
+    let values = stage("Calculando as estatísticas", || stats(graph));
+    let texts = stat_texts(&values, 0);
+    match format {
+        OutputFormat::Plain => {
+            // No terminal, os valores ficam alinhados em uma coluna.
+            let width = if terminal.aligned {
+                STATS
+                    .iter()
+                    .map(|(_, label, _)| label.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            } else {
+                0
+            };
+            for ((_, label, _), value) in STATS.iter().zip(&texts) {
+                let padding = width.saturating_sub(label.chars().count());
+                let label = format!("{label}:{:padding$}", "");
+                outln!("{} {value}", terminal.paint(label, BOLD));
+            }
+        }
+        OutputFormat::Json => outln!("{}", stats_json(&values, 0)),
+        OutputFormat::Csv => {
+            outln!("statistic,value");
+            for ((key, _, _), value) in STATS.iter().zip(&texts) {
+                outln!("{key},{value}");
+            }
+        }
+        OutputFormat::Dot => return Err(unsupported("stats", format)),
+    }
+    Ok(())
+}
+
+/// Mostra as estatísticas de cada arquivo de `files`, seguidas do mínimo, da média e do máximo de
+/// cada uma entre os arquivos. Diretórios são trocados pelos arquivos dentro deles, em ordem
+/// alfabética.
+///
+/// Um arquivo que não pode ser lido não interrompe os outros: o erro é mostrado, e o arquivo fica
+/// fora do resumo.
+fn batch(
+    files: &[PathBuf],
+    representation: Representation,
+    format: OutputFormat,
+    terminal: Terminal,
+) -> Result<(), Box<dyn Error>> {
+    let mut paths = Vec::new();
+    for file in files {
+        if file.is_dir() {
+            let mut entries = Vec::new();
+            for entry in std::fs::read_dir(file)? {
+                let path = entry?.path();
+                if path.is_file() {
+                    entries.push(path);
+                }
+            }
+            entries.sort();
+            paths.extend(entries);
+        } else {
+            paths.push(file.clone());
+        }
+    }
 
-    let mut graph_adj = GraphAdj::default();
-    let mut graph_mat = GraphMat::default();
-    fill_graph(&input_data, &mut graph_adj);
-    fill_graph(&input_data, &mut graph_mat);
+    let mut rows = Vec::new();
+    let mut failures = 0;
+    for path in &paths {
+        let input = Input {
+            file: path.display().to_string(),
+        };
+        match load(&input, representation) {
+            Ok(graph) => rows.push((input.file, stats(&*graph))),
+            Err(e) => {
+                eprintln!("Erro em {}: {e}", input.file);
+                failures += 1;
+            }
+        }
+    }
+
+    // Cada linha do resumo combina uma coluna de todos os arquivos. A média tem pelo menos duas
+    // casas decimais, mesmo nas estatísticas inteiras.
+    let combine = |f: fn(f64, f64) -> f64, start: f64| {
+        let mut values = [start; 8];
+        for (_, row) in &rows {
+            for (value, &x) in values.iter_mut().zip(row) {
+                *value = f(*value, x);
+            }
+        }
+        values
+    };
+    let mut mean = combine(|a, b| a + b, 0.0);
+    mean.iter_mut().for_each(|x| *x /= rows.len() as f64);
+    let summary = if rows.is_empty() {
+        Vec::new()
+    } else {
+        vec![
+            ("min", "Mínimo", combine(f64::min, f64::INFINITY), 0),
+            ("mean", "Média", mean, 2),
+            ("max", "Máximo", combine(f64::max, f64::NEG_INFINITY), 0),
+        ]
+    };
+
+    match format {
+        OutputFormat::Plain => {
+            let header = std::iter::once("Arquivo".to_string())
+                .chain(STATS.iter().map(|(_, label, _)| label.to_string()));
+            let mut table = vec![header.collect::<Vec<_>>()];
+            for (file, values) in &rows {
+                table.push([vec![file.clone()], stat_texts(values, 0)].concat());
+            }
+            for (_, name, values, precision) in &summary {
+                table.push([vec![name.to_string()], stat_texts(values, *precision)].concat());
+            }
+            let mut widths = vec![0; table[0].len()];
+            for row in &table {
+                for (width, cell) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(cell.chars().count());
+                }
+            }
+            for (i, row) in table.iter().enumerate() {
+                let cells: Vec<_> = row
+                    .iter()
+                    .zip(&widths)
+                    .enumerate()
+                    .map(|(j, (cell, width))| {
+                        let padding = width - cell.chars().count();
+                        // O nome do arquivo fica à esquerda e os números, à direita.
+                        if j == 0 {
+                            format!("{cell}{:padding$}", "")
+                        } else {
+                            format!("{:padding$}{cell}", "")
+                        }
+                    })
+                    .collect();
+                let text = cells.join("  ");
+                // O cabeçalho e as linhas do resumo ficam em negrito.
+                if i == 0 || i > rows.len() {
+                    outln!("{}", terminal.paint(text.trim_end(), BOLD));
+                } else {
+                    outln!("{}", text.trim_end());
+                }
+            }
+        }
+        OutputFormat::Csv => {
+            let keys: Vec<_> = STATS.iter().map(|(key, _, _)| *key).collect();
+            outln!("file,{}", keys.join(","));
+            for (file, values) in &rows {
+                outln!("{},{}", csv_field(file), stat_texts(values, 0).join(","));
+            }
+            // As linhas do resumo levam o seu nome no lugar do arquivo.
+            for (key, _, values, precision) in &summary {
+                outln!("{key},{}", stat_texts(values, *precision).join(","));
+            }
+        }
+        OutputFormat::Json => {
+            let files: Vec<_> = rows
+                .iter()
+                .map(|(file, values)| {
+                    format!(
+                        "{{\"file\": {file:?}, \"stats\": {}}}",
+                        stats_json(values, 0)
+                    )
+                })
+                .collect();
+            let summary: Vec<_> = summary
+                .iter()
+                .map(|(key, _, values, precision)| {
+                    format!("\"{key}\": {}", stats_json(values, *precision))
+                })
+                .collect();
+            outln!(
+                "{{\"files\": [{}], \"summary\": {{{}}}}}",
+                files.join(", "),
+                summary.join(", ")
+            );
+        }
+        OutputFormat::Dot => return Err(unsupported("batch", format)),
+    }
+
+    match failures {
+        0 => Ok(()),
+        1 => Err("1 arquivo não pôde ser lido".into()),
+        count => Err(format!("{count} arquivos não puderam ser lidos").into()),
+    }
+}
+
+/// Coloca `field` entre aspas caso ele tenha vírgulas ou aspas, como pede o CSV.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Como os resultados são mostrados no terminal: com as colunas alinhadas e, se permitido, com
+/// cores.
+///
+/// Quando a saída é redirecionada para um arquivo ou para outro programa, ela continua igual ao
+/// texto simples de sempre, que segue o formato da entrada.
+#[derive(Clone, Copy)]
+struct Terminal {
+    aligned: bool,
+    color: bool,
+}
+
+/// Códigos ANSI usados nos resultados.
+const BOLD: &str = "1";
+/// Destaque para as arestas mais pesadas.
+const HIGHLIGHT: &str = "1;33";
+/// Arestas adicionadas e removidas, em `diff`.
+const ADDED: &str = "32";
+const REMOVED: &str = "31";
+/// Nós de um caminho encontrado.
+const PATH: &str = "1;32";
+
+impl Terminal {
+    /// As cores são desligadas com `--no-color` ou com a variável `NO_COLOR` com qualquer valor
+    /// não vazio, como sugere <https://no-color.org>.
+    fn detect(no_color: bool) -> Self {
+        let aligned = std::io::stdout().is_terminal();
+        let no_color = no_color || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        Self {
+            aligned,
+            color: aligned && !no_color,
+        }
+    }
+    /// Envolve `text` com o código ANSI `code`, caso as cores estejam ligadas.
+    fn paint(self, text: impl Display, code: &str) -> String {
+        if self.color {
+            format!("\x1b[{code}m{text}\x1b[0m")
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Mostra uma aresta `a b w` por linha, com os nós numerados a partir de 1.
+///
+/// No terminal, as colunas são alinhadas à direita e as arestas de maior peso são destacadas,
+/// caso nem todas tenham o mesmo peso.
+fn print_edges(edges: &[Edge], terminal: Terminal) {
+    let rows: Vec<[String; 3]> = edges
+        .iter()
+        .map(|(a, b, w)| [(a + 1).to_string(), (b + 1).to_string(), w.to_string()])
+        .collect();
+    // Com largura 0, os números são escritos sem espaços extras.
+    let mut widths = [0; 3];
+    if terminal.aligned {
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+    }
+    let weights = edges.iter().map(|&(_, _, w)| w);
+    let (min, max) = (weights.clone().min(), weights.max());
+    for ((_, _, weight), [a, b, w]) in edges.iter().zip(&rows) {
+        let line = format!("{a:>0$} {b:>1$} {w:>2$}", widths[0], widths[1], widths[2]);
+        if Some(*weight) == max && min != max {
+            outln!("{}", terminal.paint(line, HIGHLIGHT));
+        } else {
+            outln!("{line}");
+        }
+    }
+}
 
-    println!("Arestas do grafo por matriz de adj:");
-    print_edges(&graph_mat);
-    println!("Arestas do grafo por lista de adj:");
-    print_edges(&graph_adj);
+/// Mostra os nós de um caminho, numerados a partir de 1, e o seu peso total.
+fn print_path(path: &[Node], total: Weight, terminal: Terminal) {
+    let ids: Vec<_> = path.iter().map(|n| terminal.paint(n + 1, PATH)).collect();
+    outln!("{} {}", terminal.paint("Caminho:", BOLD), ids.join(" "));
+    outln!("{} {total}", terminal.paint("Peso total:", BOLD));
 }
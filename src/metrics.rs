@@ -0,0 +1,399 @@
+//! Medidas da estrutura de um grafo, como o diâmetro e o coeficiente de agrupamento.
+//!
+//! Com a feature `rayon`, as mesmas medidas podem ser calculadas em paralelo por
+//! [`crate::par_diameter`], [`crate::par_clustering_coefficients`] e
+//! [`crate::par_betweenness_centrality`].
+
+use crate::prelude::*;
+use crate::rng::Rng;
+use crate::{
+    breadth_first_search, node_ids, Budget, Control, Edge, GraphRef, Node, Num, Partial,
+    PropertyMap, Set, Visitor,
+};
+
+/// Retorna a maior distância, em quantidade de arestas, de `source` até os outros nós, seguindo a
+/// direção das arestas em grafos direcionados. Retorna `None` caso algum nó não seja alcançável.
+///
+/// Usa uma busca em largura, então custa O(V + E) e ignora os pesos. Causa um `panic` caso `source`
+/// não exista.
+pub fn eccentricity<W: Num>(graph: &dyn GraphRef<W>, source: Node) -> Option<usize> {
+    if !graph.has_node(source) {
+        panic!("Tried to find eccentricity of inexistent node {source}");
+    }
+    /// Distância de cada nó descoberto até a origem.
+    struct Levels {
+        distance: PropertyMap<usize>,
+        farthest: usize,
+    }
+    impl<W: Num> Visitor<W> for Levels {
+        fn tree_edge(&mut self, (a, b, _): Edge<W>) -> Control {
+            // A busca descobre os nós em ordem de distância, então o último é o mais distante.
+            self.farthest = self.distance[a] + 1;
+            self.distance.insert(b, self.farthest);
+            Control::Continue
+        }
+    }
+    let mut levels = Levels {
+        distance: PropertyMap::new(),
+        farthest: 0,
+    };
+    levels.distance.insert(source, 0);
+    breadth_first_search(graph, [source], &mut levels);
+    (levels.distance.len() == graph.node_count()).then_some(levels.farthest)
+}
+
+/// Retorna o diâmetro de `graph`: a maior distância, em quantidade de arestas, entre dois nós.
+/// Retorna `None` caso algum par de nós não tenha um caminho, e `Some(0)` para um grafo vazio.
+///
+/// Faz uma busca em largura a partir de cada nó, então custa O(V · (V + E)).
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "diameter", level = "debug", skip_all)
+)]
+pub fn diameter<W: Num>(graph: &dyn GraphRef<W>) -> Option<usize> {
+    node_ids(graph).try_fold(0, |max, n| Some(max.max(eccentricity(graph, n)?)))
+}
+
+/// Versão de [`diameter`] que respeita os limites de `budget`, em que cada passo é uma busca em
+/// largura.
+///
+/// Caso os limites sejam atingidos, o resultado parcial é a maior excentricidade encontrada até
+/// então, que é um limite inferior para o diâmetro. Um `None` é sempre definitivo: basta um nó que
+/// não alcança todos os outros.
+pub fn diameter_within<W: Num>(graph: &dyn GraphRef<W>, budget: &Budget) -> Partial<Option<usize>> {
+    let mut max = 0;
+    for (steps, n) in node_ids(graph).enumerate() {
+        if budget.is_exhausted(steps) {
+            return Partial {
+                value: Some(max),
+                complete: false,
+            };
+        }
+        let Some(e) = eccentricity(graph, n) else {
+            return Partial {
+                value: None,
+                complete: true,
+            };
+        };
+        max = max.max(e);
+    }
+    Partial {
+        value: Some(max),
+        complete: true,
+    }
+}
+
+/// Retorna o coeficiente de agrupamento local de `n`: a fração dos pares de vizinhos de `n` que
+/// também são vizinhos entre si, ou seja, que formam um triângulo com `n`.
+///
+/// A direção das arestas e os laços são ignorados. Nós com menos de dois vizinhos têm coeficiente
+/// `0`. Causa um `panic` caso `n` não exista.
+pub fn clustering_coefficient<W: Num>(graph: &dyn GraphRef<W>, n: Node) -> f64 {
+    if !graph.has_node(n) {
+        panic!("Tried to find clustering coefficient of inexistent node {n}");
+    }
+    let neighbors = undirected_neighbors(graph, n);
+    let k = neighbors.len();
+    if k < 2 {
+        return 0.0;
+    }
+    // Cada ligação entre vizinhos é contada uma vez, a partir do menor dos dois.
+    let links: usize = neighbors
+        .iter()
+        .map(|&u| {
+            undirected_neighbors(graph, u)
+                .range(u + 1..)
+                .filter(|v| neighbors.contains(v))
+                .count()
+        })
+        .sum();
+    links as f64 / (k * (k - 1) / 2) as f64
+}
+
+/// Calcula o [`clustering_coefficient`] de todos os nós de `graph`.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "clustering_coefficients", level = "debug", skip_all)
+)]
+pub fn clustering_coefficients<W: Num>(graph: &dyn GraphRef<W>) -> PropertyMap<f64> {
+    let mut coefficients = PropertyMap::new();
+    for n in node_ids(graph) {
+        coefficients.insert(n, clustering_coefficient(graph, n));
+    }
+    coefficients
+}
+
+/// Vizinhos de `n` nos dois sentidos, sem o próprio `n`.
+fn undirected_neighbors<W: Num>(graph: &dyn GraphRef<W>, n: Node) -> Set<Node> {
+    let mut neighbors: Set<_> = graph.neighbors(n).collect();
+    if graph.is_directed() {
+        neighbors.extend(graph.in_neighbors(n));
+    }
+    neighbors.remove(&n);
+    neighbors
+}
+
+/// Retorna o grau médio dos vizinhos de `n`, ou `0` caso `n` não tenha vizinhos.
+///
+/// Em grafos direcionados, considera os nós para os quais `n` tem arestas e o grau de saída deles.
+/// Um laço conta `n` como vizinho de si mesmo. Causa um `panic` caso `n` não exista.
+pub fn average_neighbor_degree<W: Num>(graph: &dyn GraphRef<W>, n: Node) -> f64 {
+    if !graph.has_node(n) {
+        panic!("Tried to find average neighbor degree of inexistent node {n}");
+    }
+    let (mut count, mut total) = (0, 0);
+    graph.for_each_neighbor(n, &mut |m| {
+        count += 1;
+        total += graph.out_degree(m);
+    });
+    match count {
+        0 => 0.0,
+        _ => total as f64 / count as f64,
+    }
+}
+
+/// Calcula o [`average_neighbor_degree`] de todos os nós de `graph`.
+pub fn average_neighbor_degrees<W: Num>(graph: &dyn GraphRef<W>) -> PropertyMap<f64> {
+    let mut averages = PropertyMap::new();
+    for n in node_ids(graph) {
+        averages.insert(n, average_neighbor_degree(graph, n));
+    }
+    averages
+}
+
+/// Calcula o coeficiente de assortatividade de grau de `graph`: a correlação de Pearson entre os
+/// graus das duas pontas de cada aresta.
+///
+/// Um valor próximo de `1` indica que nós de grau alto se ligam a outros de grau alto, como em
+/// redes sociais, e um próximo de `-1`, que se ligam a nós de grau baixo, como em redes com poucos
+/// centros e muitas pontas. Em grafos não direcionados, cada aresta entra nos dois sentidos; nos
+/// direcionados, a aresta `a -> b` correlaciona o grau de saída de `a` com o grau de entrada de `b`.
+///
+/// Retorna `None` caso o grafo não tenha arestas ou caso todas as arestas tenham pontas de mesmo
+/// grau, como em grafos regulares, em que a correlação não é definida. Precisa da feature `std`,
+/// pela raiz quadrada.
+#[cfg(feature = "std")]
+pub fn degree_assortativity<W: Num>(graph: &dyn GraphRef<W>) -> Option<f64> {
+    use crate::Map;
+
+    // Os graus são contados em uma única passada pelas arestas, já que `in_degree` pode custar
+    // O(E) por nó.
+    let mut out_degree: Map<Node, usize> = Map::new();
+    let mut in_degree: Map<Node, usize> = Map::new();
+    graph.for_each_edge(&mut |(a, b, _)| {
+        *out_degree.entry(a).or_default() += 1;
+        *in_degree.entry(b).or_default() += 1;
+    });
+    let (mut count, mut sum_x, mut sum_y) = (0.0, 0.0, 0.0);
+    let (mut sum_xx, mut sum_yy, mut sum_xy) = (0.0, 0.0, 0.0);
+    graph.for_each_edge(&mut |(a, b, _)| {
+        let x = out_degree[&a] as f64;
+        // Em grafos não direcionados, os graus de entrada e de saída são iguais.
+        let y = in_degree[&b] as f64;
+        count += 1.0;
+        sum_x += x;
+        sum_y += y;
+        sum_xx += x * x;
+        sum_yy += y * y;
+        sum_xy += x * y;
+    });
+    let covariance = sum_xy / count - (sum_x / count) * (sum_y / count);
+    let variance_x = sum_xx / count - (sum_x / count) * (sum_x / count);
+    let variance_y = sum_yy / count - (sum_y / count) * (sum_y / count);
+    // Abaixo dessa variância, os graus são todos iguais, a menos de arredondamentos.
+    if count == 0.0 || variance_x <= 1e-12 || variance_y <= 1e-12 {
+        return None;
+    }
+    Some(covariance / (variance_x * variance_y).sqrt())
+}
+
+/// Opções de [`betweenness_centrality`].
+#[derive(Debug, Clone, Default)]
+pub struct BetweennessOptions {
+    /// Usa apenas essa quantidade de origens, sorteadas entre os nós, em vez de todos eles. O
+    /// resultado é multiplicado pela proporção entre os nós e as origens, o que dá uma estimativa
+    /// do valor exato, que fica melhor quanto mais origens forem usadas. Com `None`, o resultado é
+    /// exato.
+    pub sample_sources: Option<usize>,
+    /// Semente do sorteio das origens. A mesma semente sempre sorteia as mesmas origens.
+    pub seed: u64,
+}
+
+/// Calcula a centralidade de intermediação (_betweenness_) de todos os nós de `graph`: para cada
+/// nó `v`, a soma, sobre todos os pares de outros nós `s` e `t`, da fração dos caminhos mínimos de
+/// `s` até `t` que passam por `v`.
+///
+/// Usa o algoritmo de Brandes: uma busca em largura a partir de cada origem conta os caminhos
+/// mínimos até cada nó, e os nós são então percorridos do mais distante para o mais próximo,
+/// acumulando a fração dos caminhos que passa por cada um. Custa O(V · (V + E)), ou
+/// O(k · (V + E)) com `k` origens sorteadas (veja [`BetweennessOptions::sample_sources`]).
+///
+/// As distâncias são em quantidade de arestas, ignorando os pesos, e seguem a direção das arestas
+/// em grafos direcionados. Em grafos não direcionados, cada par é contado uma única vez.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "betweenness_centrality", level = "debug", skip_all)
+)]
+pub fn betweenness_centrality<W: Num>(
+    graph: &dyn GraphRef<W>,
+    options: &BetweennessOptions,
+) -> PropertyMap<f64> {
+    let sources = betweenness_sources(graph, options);
+    let mut brandes = Brandes::new(graph);
+    let mut total = vec![0.0; brandes.distance.len()];
+    for &s in &sources {
+        brandes.accumulate(graph, s, &mut total);
+    }
+    trace_event!("betweenness finished", sources = sources.len());
+    betweenness_map(graph, sources.len(), &total)
+}
+
+/// Versão de [`betweenness_centrality`] que respeita os limites de `budget`, em que cada passo é a
+/// busca a partir de uma origem.
+///
+/// As origens são processadas em uma ordem sorteada com [`BetweennessOptions::seed`], então as
+/// que foram processadas antes de atingir os limites são uma amostra das origens, e o resultado
+/// parcial é a estimativa correspondente, como com [`BetweennessOptions::sample_sources`]. Por
+/// causa da ordem, o resultado completo pode diferir do de `betweenness_centrality` por
+/// arredondamentos.
+pub fn betweenness_centrality_within<W: Num>(
+    graph: &dyn GraphRef<W>,
+    options: &BetweennessOptions,
+    budget: &Budget,
+) -> Partial<PropertyMap<f64>> {
+    let mut sources = betweenness_sources(graph, options);
+    Rng::new(options.seed).shuffle(&mut sources);
+    let mut brandes = Brandes::new(graph);
+    let mut total = vec![0.0; brandes.distance.len()];
+    let mut done = 0;
+    while done < sources.len() && !budget.is_exhausted(done) {
+        brandes.accumulate(graph, sources[done], &mut total);
+        done += 1;
+    }
+    Partial {
+        value: betweenness_map(graph, done, &total),
+        complete: done == sources.len(),
+    }
+}
+
+/// Origens usadas por [`betweenness_centrality`], em ordem crescente.
+pub(crate) fn betweenness_sources<W: Num>(
+    graph: &dyn GraphRef<W>,
+    options: &BetweennessOptions,
+) -> Vec<Node> {
+    let mut nodes: Vec<_> = node_ids(graph).collect();
+    let Some(count) = options.sample_sources.filter(|&k| k < nodes.len()) else {
+        return nodes;
+    };
+    // Embaralha só as `count` primeiras posições, pelo algoritmo de Fisher-Yates.
+    let mut rng = Rng::new(options.seed);
+    for i in 0..count {
+        let j = i + (rng.next_u64() % (nodes.len() - i) as u64) as usize;
+        nodes.swap(i, j);
+    }
+    nodes.truncate(count);
+    nodes.sort_unstable();
+    nodes
+}
+
+/// Converte a soma das dependências de `sources` origens na centralidade de cada nó.
+pub(crate) fn betweenness_map<W: Num>(
+    graph: &dyn GraphRef<W>,
+    sources: usize,
+    total: &[f64],
+) -> PropertyMap<f64> {
+    let mut scale = graph.node_count() as f64 / sources.max(1) as f64;
+    // Em grafos não direcionados, o par `s`, `t` é encontrado a partir das duas pontas.
+    if !graph.is_directed() {
+        scale /= 2.0;
+    }
+    let mut centrality = PropertyMap::new();
+    for n in node_ids(graph) {
+        centrality.insert(n, total[n as usize] * scale);
+    }
+    centrality
+}
+
+/// Vetores usados em cada busca de [`betweenness_centrality`], reaproveitados entre as origens.
+pub(crate) struct Brandes {
+    /// Distância de cada nó até a origem, ou `usize::MAX` caso ainda não tenha sido alcançado.
+    distance: Vec<usize>,
+    /// Quantidade de caminhos mínimos da origem até cada nó. É um `f64` porque a quantidade pode
+    /// crescer exponencialmente com a distância.
+    paths: Vec<f64>,
+    /// Fração dos caminhos mínimos a partir da origem que passa por cada nó.
+    dependency: Vec<f64>,
+    /// Nós alcançados, em ordem de distância.
+    order: Vec<Node>,
+}
+
+impl Brandes {
+    pub(crate) fn new<W: Num>(graph: &dyn GraphRef<W>) -> Self {
+        let bound = node_ids(graph).last().map_or(0, |n| n as usize + 1);
+        Self {
+            distance: vec![usize::MAX; bound],
+            paths: vec![0.0; bound],
+            dependency: vec![0.0; bound],
+            order: Vec::new(),
+        }
+    }
+    /// Soma em `total` a dependência de cada nó em relação à origem `s`.
+    pub(crate) fn accumulate<W: Num>(
+        &mut self,
+        graph: &dyn GraphRef<W>,
+        s: Node,
+        total: &mut [f64],
+    ) {
+        let Self {
+            distance,
+            paths,
+            dependency,
+            order,
+        } = self;
+        // Só os nós alcançados pela busca anterior precisam ser limpos.
+        for &n in order.iter() {
+            distance[n as usize] = usize::MAX;
+            paths[n as usize] = 0.0;
+            dependency[n as usize] = 0.0;
+        }
+        order.clear();
+        distance[s as usize] = 0;
+        paths[s as usize] = 1.0;
+        order.push(s);
+        // `order` também serve de fila: os nós depois de `head` ainda não foram visitados.
+        let mut head = 0;
+        while head < order.len() {
+            let a = order[head] as usize;
+            head += 1;
+            let next = distance[a] + 1;
+            graph.for_each_neighbor(a as Node, &mut |b| {
+                let b = b as usize;
+                if distance[b] == usize::MAX {
+                    distance[b] = next;
+                    order.push(b as Node);
+                }
+                // Todo caminho mínimo até `a` continua em um caminho mínimo até `b`.
+                if distance[b] == next {
+                    paths[b] += paths[a];
+                }
+            });
+        }
+        // Do mais distante para o mais próximo, cada nó recebe a dependência dos nós seguintes
+        // nos seus caminhos, proporcional à fração dos caminhos que vem dele.
+        for &a in order.iter().rev() {
+            let a = a as usize;
+            let next = distance[a] + 1;
+            let mut sum = 0.0;
+            graph.for_each_neighbor(a as Node, &mut |b| {
+                let b = b as usize;
+                if distance[b] == next {
+                    sum += paths[a] / paths[b] * (1.0 + dependency[b]);
+                }
+            });
+            dependency[a] = sum;
+            if a != s as usize {
+                total[a] += sum;
+            }
+        }
+    }
+}
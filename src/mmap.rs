@@ -0,0 +1,84 @@
+//! Leitura de arquivos mapeados na memória, com a feature `mmap` (apenas em sistemas Unix).
+
+use std::fs::File;
+use std::io;
+use std::ops::Deref;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+use std::ptr::NonNull;
+
+/// Arquivo mapeado na memória, somente para leitura, que pode ser usado como um `&[u8]`.
+///
+/// O sistema operacional carrega as páginas do arquivo à medida que são acessadas, e pode
+/// descartá-las quando a memória fica escassa, já que elas podem ser lidas de novo do disco.
+/// Assim, ler um arquivo grande com [`crate::fill_graph_from_bytes`] não precisa de uma cópia do
+/// texto na memória do programa, como acontece ao lê-lo para uma `String`.
+pub struct MappedFile {
+    ptr: NonNull<u8>,
+    len: usize,
+}
+
+// O mapeamento é somente leitura, então pode ser lido por várias threads ao mesmo tempo.
+unsafe impl Send for MappedFile {}
+unsafe impl Sync for MappedFile {}
+
+impl MappedFile {
+    /// Mapeia o arquivo `path` na memória.
+    ///
+    /// # Safety
+    ///
+    /// O arquivo não pode ser modificado nem truncado enquanto o mapeamento existir, seja por
+    /// este ou por outro programa: como os bytes não são copiados, as mudanças apareceriam no
+    /// `&[u8]`, que o Rust considera imutável. Truncar o arquivo faz com que o acesso às páginas
+    /// removidas encerre o programa com um `SIGBUS`.
+    pub unsafe fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        // `mmap` não aceita um tamanho zero, e um arquivo vazio não tem nada para mapear.
+        if len == 0 {
+            return Ok(Self {
+                ptr: NonNull::dangling(),
+                len,
+            });
+        }
+        // O mapeamento continua válido depois que o arquivo é fechado, ao fim desta função.
+        let ptr = libc::mmap(
+            std::ptr::null_mut(),
+            len,
+            libc::PROT_READ,
+            libc::MAP_PRIVATE,
+            file.as_raw_fd(),
+            0,
+        );
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        // Avisa que o arquivo será lido do início ao fim, para que as páginas sejam carregadas
+        // antes de serem acessadas. É apenas uma dica, então um erro aqui é ignorado.
+        libc::madvise(ptr, len, libc::MADV_SEQUENTIAL);
+        Ok(Self {
+            ptr: NonNull::new_unchecked(ptr.cast()),
+            len,
+        })
+    }
+}
+
+impl Deref for MappedFile {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // `ptr` aponta para `len` bytes mapeados, que só são liberados em `drop`.
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Drop for MappedFile {
+    fn drop(&mut self) {
+        if self.len > 0 {
+            unsafe {
+                libc::munmap(self.ptr.as_ptr().cast(), self.len);
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use core::fmt;
+use core::ops::Range;
+
+use crate::prelude::*;
+use crate::{Edge, GraphAdj, GraphMut, GraphRef, Map, Node, Num, Weight};
+
+/// Uma modificação feita em um [`ObservedGraph`], repassada aos observadores.
+///
+/// Em grafos não direcionados, cada aresta gera um único evento, com os nós na ordem em que foram
+/// passados ao método (ou, ao remover um nó, com ele primeiro).
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphEvent<'a, W = Weight> {
+    NodeAdded(Node),
+    /// Um nó foi removido. As suas arestas já foram anunciadas como [`GraphEvent::EdgeRemoved`].
+    NodeRemoved(Node),
+    /// Uma aresta que não existia foi adicionada.
+    EdgeAdded(Edge<W>),
+    /// O peso de uma aresta existente foi substituído por `weight`.
+    EdgeUpdated {
+        a: Node,
+        b: Node,
+        previous: W,
+        weight: W,
+    },
+    EdgeRemoved(Edge<W>),
+    /// O sentido de todas as arestas de um grafo direcionado foi invertido.
+    Reversed,
+    /// Os nós foram renumerados por [`GraphMut::compact`], com o mapeamento dos identificadores
+    /// antigos para os novos.
+    Renumbered(&'a Map<Node, Node>),
+    /// Todos os nós e arestas foram removidos por [`GraphMut::clear`].
+    Cleared,
+}
+
+/// Identifica um observador de um [`ObservedGraph`], para que ele possa ser removido.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ObserverId(usize);
+
+type Observer<W> = Box<dyn FnMut(&GraphEvent<'_, W>)>;
+
+/// Grafo que avisa observadores a cada modificação.
+///
+/// Estruturas derivadas do grafo, como um índice espacial dos nós ou as arestas agrupadas por
+/// peso, precisam ser atualizadas a cada modificação. Em vez de envolver cada chamada, basta
+/// registrar uma função com [`ObservedGraph::observe`], que recebe um [`GraphEvent`] depois de
+/// cada modificação. Para que a função possa atualizar uma estrutura que também é lida fora dela,
+/// a estrutura pode ser compartilhada com um `Rc<RefCell<_>>`.
+///
+/// Envolve qualquer implementação de [`GraphMut`] (por padrão, [`GraphAdj`]) e também implementa
+/// [`GraphMut`]. Os métodos compostos, como `set_edge_weight`, `retain_edges` e `merge_nodes`,
+/// usam os métodos básicos e geram os eventos correspondentes.
+pub struct ObservedGraph<G = GraphAdj, W = Weight> {
+    graph: G,
+    observers: Vec<(ObserverId, Observer<W>)>,
+    next_id: usize,
+}
+
+impl<G: Default, W> Default for ObservedGraph<G, W> {
+    fn default() -> Self {
+        Self::new(G::default())
+    }
+}
+
+// As funções dos observadores não implementam `Debug`, então mostramos apenas quantas são.
+impl<G: fmt::Debug, W> fmt::Debug for ObservedGraph<G, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservedGraph")
+            .field("graph", &self.graph)
+            .field("observers", &self.observers.len())
+            .finish()
+    }
+}
+
+impl<G, W> ObservedGraph<G, W> {
+    /// Envolve o grafo `graph`, sem nenhum observador.
+    pub fn new(graph: G) -> Self {
+        Self {
+            graph,
+            observers: Vec::new(),
+            next_id: 0,
+        }
+    }
+    /// Retorna uma referência ao grafo envolvido.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+    /// Descarta os observadores e retorna o grafo envolvido.
+    pub fn into_graph(self) -> G {
+        self.graph
+    }
+    /// Registra `observer`, que será chamado depois de cada modificação, na ordem em que os
+    /// observadores foram registrados.
+    pub fn observe(&mut self, observer: impl FnMut(&GraphEvent<'_, W>) + 'static) -> ObserverId {
+        let id = ObserverId(self.next_id);
+        self.next_id += 1;
+        self.observers.push((id, Box::new(observer)));
+        id
+    }
+    /// Remove o observador `id`, retornando se ele ainda estava registrado.
+    pub fn unobserve(&mut self, id: ObserverId) -> bool {
+        let len = self.observers.len();
+        self.observers.retain(|(other, _)| *other != id);
+        self.observers.len() != len
+    }
+
+    fn notify(&mut self, event: GraphEvent<'_, W>) {
+        for (_, observer) in &mut self.observers {
+            observer(&event);
+        }
+    }
+}
+
+impl<W: Num, G: GraphRef<W>> GraphRef<W> for ObservedGraph<G, W> {
+    delegate_graph_queries!();
+}
+
+impl<W: Num, G: GraphMut<W>> GraphMut<W> for ObservedGraph<G, W> {
+    fn add_node(&mut self) -> Node {
+        let node = self.graph.add_node();
+        self.notify(GraphEvent::NodeAdded(node));
+        node
+    }
+    fn add_nodes(&mut self, count: usize) -> Range<Node> {
+        let nodes = self.graph.add_nodes(count);
+        for node in nodes.clone() {
+            self.notify(GraphEvent::NodeAdded(node));
+        }
+        nodes
+    }
+    fn add_edge(&mut self, a: Node, b: Node, weight: W) {
+        let previous = self.graph.get_edge_weight(a, b);
+        self.graph.add_edge(a, b, weight);
+        self.notify(match previous {
+            Some(previous) => GraphEvent::EdgeUpdated {
+                a,
+                b,
+                previous,
+                weight,
+            },
+            None => GraphEvent::EdgeAdded((a, b, weight)),
+        });
+    }
+    fn remove_node(&mut self, n: Node) {
+        // Em grafos não direcionados, `out_edges` já inclui todas as arestas de `n`. Nos
+        // direcionados, os laços aparecem nas duas listas, então só os de `out_edges` são usados.
+        let mut edges = self.graph.out_edges(n);
+        if self.graph.is_directed() {
+            edges.extend(self.graph.in_edges(n).into_iter().filter(|e| e.0 != n));
+        }
+        self.graph.remove_node(n);
+        for edge in edges {
+            self.notify(GraphEvent::EdgeRemoved(edge));
+        }
+        self.notify(GraphEvent::NodeRemoved(n));
+    }
+    fn remove_edge(&mut self, a: Node, b: Node) -> Option<W> {
+        let weight = self.graph.remove_edge(a, b)?;
+        self.notify(GraphEvent::EdgeRemoved((a, b, weight)));
+        Some(weight)
+    }
+    fn reverse(&mut self) {
+        if self.graph.is_directed() {
+            self.graph.reverse();
+            self.notify(GraphEvent::Reversed);
+        }
+    }
+    fn compact(&mut self) -> Map<Node, Node> {
+        let mapping = self.graph.compact();
+        self.notify(GraphEvent::Renumbered(&mapping));
+        mapping
+    }
+    fn clear(&mut self) {
+        self.graph.clear();
+        self.notify(GraphEvent::Cleared);
+    }
+}
@@ -0,0 +1,339 @@
+//! Versões paralelas, com o `rayon`, das análises em que cada nó pode ser processado de forma
+//! independente. Os resultados são sempre iguais aos das versões sequenciais, a não ser por
+//! arredondamentos em [`par_betweenness_centrality`], que soma os valores em outra ordem.
+//!
+//! Para as análises que o crate não tem, [`par_nodes`] e [`par_edges`] percorrem o grafo em
+//! paralelo, e podem ser combinados com os adaptadores do `rayon`.
+//!
+//! Para ser compartilhado entre as threads, o grafo precisa ser `Sync`, o que vale para todas as
+//! estruturas do crate.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use rayon::prelude::*;
+
+use crate::input::{has_data, parse_edge, parse_header, parse_line_bytes, parse_numbers};
+use crate::metrics::{betweenness_map, betweenness_sources, Brandes};
+use crate::paths::{delta_search, relaxations};
+use crate::{
+    clustering_coefficient, eccentricity, node_ids, BetweennessOptions, Edge, GraphMut, GraphRef,
+    InputOptions, Map, Node, Num, ParseGraphError, PropertyMap, ShortestPaths,
+};
+
+/// Retorna um iterador paralelo sobre os nós do grafo, em ordem crescente.
+///
+/// Os identificadores são coletados antes, em O(V), para que o `rayon` possa dividi-los em blocos
+/// de tamanhos conhecidos. Por exemplo, para calcular uma métrica local de cada nó:
+/// `par_nodes(&graph).map(|n| (n, metrica(&graph, n))).collect::<Vec<_>>()`.
+pub fn par_nodes<W: Num>(graph: &dyn GraphRef<W>) -> impl IndexedParallelIterator<Item = Node> {
+    node_ids(graph).collect::<Vec<_>>().into_par_iter()
+}
+
+/// Retorna um iterador paralelo sobre as arestas do grafo, as mesmas de [`GraphRef::edges`].
+///
+/// Os nós são divididos entre as threads, e cada uma percorre as arestas que saem dos seus nós com
+/// [`GraphRef::out_edges`]. Assim como em `edges()`, as arestas não direcionadas aparecem nos dois
+/// sentidos, e o iterador só mantém a ordem caso seja coletado em um vetor.
+pub fn par_edges<W: Num + Send + Sync>(
+    graph: &(dyn GraphRef<W> + Sync),
+) -> impl ParallelIterator<Item = Edge<W>> + '_ {
+    par_nodes(graph).flat_map_iter(|a| graph.out_edges(a))
+}
+
+/// Versão paralela de [`crate::connected_components`], com o mesmo resultado.
+///
+/// As arestas de cada nó são processadas em paralelo por um _union-find_ sem travas, em que cada
+/// união é feita com uma operação atômica de comparação e troca: a raiz de maior número passa a
+/// apontar para a de menor número, o que impede que duas threads criem um ciclo.
+pub fn par_connected_components<W: Num + Sync>(graph: &(dyn GraphRef<W> + Sync)) -> Vec<Vec<Node>> {
+    let nodes: Vec<_> = node_ids(graph).collect();
+    let bound = nodes.last().map_or(0, |&n| n as usize + 1);
+    let sets = AtomicUnionFind::new(bound);
+    nodes.par_iter().for_each(|&a| {
+        for b in graph.neighbors(a) {
+            sets.union(a, b);
+        }
+    });
+    let roots: Vec<_> = nodes.par_iter().map(|&n| sets.find(n)).collect();
+    // Como os nós estão em ordem crescente, a primeira vez que uma raiz aparece é no menor nó da
+    // sua componente, assim como na versão sequencial.
+    let mut index = Map::new();
+    let mut components: Vec<Vec<Node>> = Vec::new();
+    for (&n, root) in nodes.iter().zip(roots) {
+        let i = *index.entry(root).or_insert_with(|| {
+            components.push(Vec::new());
+            components.len() - 1
+        });
+        components[i].push(n);
+    }
+    components
+}
+
+/// Versão paralela de [`crate::diameter`], com uma busca em largura por thread.
+pub fn par_diameter<W: Num + Sync>(graph: &(dyn GraphRef<W> + Sync)) -> Option<usize> {
+    let nodes: Vec<_> = node_ids(graph).collect();
+    nodes
+        .par_iter()
+        .map(|&n| eccentricity(graph, n))
+        .try_reduce(|| 0, |a, b| Some(a.max(b)))
+}
+
+/// Versão paralela de [`crate::clustering_coefficients`].
+pub fn par_clustering_coefficients<W: Num + Sync>(
+    graph: &(dyn GraphRef<W> + Sync),
+) -> PropertyMap<f64> {
+    let nodes: Vec<_> = node_ids(graph).collect();
+    let values: Vec<_> = nodes
+        .par_iter()
+        .map(|&n| clustering_coefficient(graph, n))
+        .collect();
+    let mut coefficients = PropertyMap::new();
+    for (n, value) in nodes.into_iter().zip(values) {
+        coefficients.insert(n, value);
+    }
+    coefficients
+}
+
+/// Versão paralela de [`crate::betweenness_centrality`], com as origens divididas entre as threads.
+///
+/// Cada thread soma as dependências das suas origens em um vetor próprio, e os vetores são
+/// somados no fim. Como a ordem das somas muda, o resultado pode diferir do sequencial nos
+/// últimos dígitos.
+pub fn par_betweenness_centrality<W: Num + Sync>(
+    graph: &(dyn GraphRef<W> + Sync),
+    options: &BetweennessOptions,
+) -> PropertyMap<f64> {
+    let sources = betweenness_sources(graph, options);
+    let bound = node_ids(graph).last().map_or(0, |n| n as usize + 1);
+    let total = sources
+        .par_iter()
+        .fold(
+            || (Brandes::new(graph), vec![0.0; bound]),
+            |(mut brandes, mut total), &s| {
+                brandes.accumulate(graph, s, &mut total);
+                (brandes, total)
+            },
+        )
+        .map(|(_, total)| total)
+        .reduce(
+            || vec![0.0; bound],
+            |mut x, y| {
+                x.iter_mut().zip(y).for_each(|(x, y)| *x += y);
+                x
+            },
+        );
+    betweenness_map(graph, sources.len(), &total)
+}
+
+/// Versão paralela de [`crate::delta_stepping`], com o mesmo resultado.
+///
+/// Os nós de cada lote de um balde têm as suas arestas relaxadas em paralelo, e as relaxações
+/// são aplicadas depois, por uma única thread. O ganho depende de os baldes terem muitos nós, o
+/// que acontece em grafos grandes com um `delta` não muito pequeno.
+pub fn par_delta_stepping<W: Num + Send + Sync>(
+    graph: &(dyn GraphRef<W> + Sync),
+    source: Node,
+    delta: W,
+) -> ShortestPaths<W> {
+    delta_search(graph, source, delta, |nodes, light, distance| {
+        nodes
+            .par_iter()
+            .flat_map_iter(|&a| relaxations(graph, a, light, delta, distance))
+            .collect()
+    })
+}
+
+/// Versão paralela de [`crate::fill_graph_from_reader_with`], que recebe a entrada inteira já
+/// carregada na memória.
+///
+/// O texto depois da primeira linha é dividido em blocos, terminando sempre no fim de uma linha,
+/// e as arestas de cada bloco são lidas por uma thread. Os blocos são então juntados em ordem, de
+/// forma que as arestas, os erros e as linhas apontadas por eles são os mesmos da versão
+/// sequencial. Assim como nela, as linhas depois das `m` arestas prometidas na primeira linha são
+/// ignoradas, mesmo que sejam inválidas.
+///
+/// Diferente da versão sequencial, `graph` só é modificado caso a entrada inteira seja válida.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "par_read_input",
+        level = "debug",
+        skip_all,
+        fields(bytes = input.len())
+    )
+)]
+pub fn par_fill_graph(
+    input: &str,
+    graph: &mut dyn GraphMut,
+    options: &InputOptions,
+) -> Result<(), ParseGraphError> {
+    // A primeira linha é procurada sequencialmente, pulando comentários e linhas vazias.
+    let mut body = input;
+    let mut line = 0;
+    let mut header = None;
+    if !options.headerless {
+        while !body.is_empty() {
+            let (text, rest) = body.split_once('\n').unwrap_or((body, ""));
+            body = rest;
+            line += 1;
+            if has_data(text) {
+                header = Some(parse_header(&parse_numbers(text, line)?, line)?);
+                break;
+            }
+        }
+        if header.is_none() {
+            return Err(ParseGraphError::MissingHeader { line: 1 });
+        }
+    }
+    // Sem a quantidade de vértices, qualquer vértice é aceito, e a quantidade de arestas não é
+    // limitada.
+    let (vertex_count, edge_count) = header.unwrap_or((Node::MAX, usize::MAX));
+
+    let chunks = split_lines(body, rayon::current_num_threads() * 4);
+    // O número da primeira linha de cada bloco depende de quantas linhas existem antes dele.
+    let line_counts: Vec<_> = chunks
+        .par_iter()
+        .map(|chunk| chunk.bytes().filter(|&b| b == b'\n').count())
+        .collect();
+    let first_lines = line_counts.iter().scan(line + 1, |next, count| {
+        let first = *next;
+        *next += count;
+        Some(first)
+    });
+    let chunks: Vec<_> = chunks.into_iter().zip(first_lines).collect();
+    let self_loops = graph.self_loops();
+    // Cada bloco para na sua primeira linha inválida, retornando as arestas anteriores a ela.
+    let parsed: Vec<(Vec<Edge>, Option<ParseGraphError>)> = chunks
+        .par_iter()
+        .map(|&(chunk, first)| {
+            let mut edges = Vec::new();
+            // Os números de todas as linhas do bloco são convertidos para um mesmo vetor.
+            let mut numbers = Vec::new();
+            for (i, text) in chunk.as_bytes().split(|&b| b == b'\n').enumerate() {
+                let edge = match parse_line_bytes(text, first + i, &mut numbers) {
+                    Ok(false) => continue,
+                    Ok(true) => parse_edge(&numbers, first + i, vertex_count, self_loops, options),
+                    Err(e) => Err(e),
+                };
+                match edge {
+                    Ok(edge) => edges.push(edge),
+                    Err(e) => return (edges, Some(e)),
+                }
+            }
+            (edges, None)
+        })
+        .collect();
+
+    // Junta os blocos em ordem, até completar as `m` arestas. Um erro só importa caso a sua linha
+    // ainda esteja entre elas.
+    let mut edges = Vec::new();
+    for (chunk, error) in parsed {
+        let missing = edge_count - edges.len();
+        if chunk.len() >= missing {
+            edges.extend_from_slice(&chunk[..missing]);
+            break;
+        }
+        edges.extend(chunk);
+        if let Some(e) = error {
+            return Err(e);
+        }
+    }
+    if header.is_some() && edges.len() < edge_count {
+        return Err(ParseGraphError::MissingEdges {
+            expected: edge_count,
+            found: edges.len(),
+        });
+    }
+
+    let node_count = match header {
+        Some(_) => vertex_count as usize,
+        // Assim como na versão sequencial, são criados os nós até o maior vértice encontrado.
+        None => edges
+            .iter()
+            .map(|&(a, b, _)| a.max(b) as usize + 1)
+            .max()
+            .unwrap_or(0),
+    };
+    trace_event!(
+        "input parsed",
+        chunks = chunks.len(),
+        nodes = node_count,
+        edges = edges.len(),
+    );
+    let start = graph.add_nodes(node_count).start;
+    for (a, b, weight) in edges {
+        graph.add_edge(a + start, b + start, weight);
+    }
+    Ok(())
+}
+
+/// Divide `text` em até `count` blocos de tamanhos parecidos, cada um terminando no fim de uma
+/// linha.
+fn split_lines(text: &str, count: usize) -> Vec<&str> {
+    let size = text.len().div_ceil(count.max(1)).max(1);
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        // Avança até o próximo `\n` depois do tamanho desejado, que pertence ao bloco.
+        let end = match rest.as_bytes().iter().skip(size).position(|&b| b == b'\n') {
+            Some(i) => size + i + 1,
+            None => rest.len(),
+        };
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    chunks
+}
+
+/// _Union-find_ que pode ser usado por várias threads ao mesmo tempo.
+///
+/// Não usa união por tamanho, como [`crate::UnionFind`], já que o tamanho e o pai teriam que ser
+/// atualizados juntos. A compressão de caminhos (por divisão ao meio) continua deixando as buscas
+/// rápidas.
+struct AtomicUnionFind {
+    parent: Vec<AtomicU32>,
+}
+
+impl AtomicUnionFind {
+    fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len as Node).map(AtomicU32::new).collect(),
+        }
+    }
+    fn find(&self, mut n: Node) -> Node {
+        loop {
+            let parent = self.parent[n as usize].load(Ordering::Relaxed);
+            if parent == n {
+                return n;
+            }
+            let grandparent = self.parent[parent as usize].load(Ordering::Relaxed);
+            // Faz `n` pular um nível. Caso outra thread tenha mudado o pai de `n` nesse meio
+            // tempo, a troca falha, o que não tem problema: o caminho continua válido.
+            let _ = self.parent[n as usize].compare_exchange(
+                parent,
+                grandparent,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            );
+            n = grandparent;
+        }
+    }
+    fn union(&self, a: Node, b: Node) {
+        loop {
+            let (a, b) = (self.find(a), self.find(b));
+            if a == b {
+                return;
+            }
+            let (child, root) = if a > b { (a, b) } else { (b, a) };
+            // A troca só funciona se `child` ainda for uma raiz; caso contrário, outra thread uniu
+            // o seu conjunto primeiro, e as raízes são buscadas de novo.
+            if self.parent[child as usize]
+                .compare_exchange(child, root, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok()
+            {
+                return;
+            }
+        }
+    }
+}
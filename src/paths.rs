@@ -0,0 +1,414 @@
+//! Caminhos mínimos entre os nós de um grafo.
+
+use alloc::collections::BinaryHeap;
+use core::cmp::Ordering;
+
+use crate::prelude::*;
+use crate::{
+    breadth_first_search, node_ids, Control, GraphMut, GraphRef, GraphTiledMat, Map, Node, Num,
+    PropertyMap, Visitor, Weight,
+};
+
+/// Resultado de [`dijkstra`]: a distância de cada nó alcançável até a origem, e a árvore de
+/// caminhos mínimos que permite reconstruir os caminhos.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<W = Weight> {
+    pub(crate) source: Node,
+    pub(crate) distance: PropertyMap<W>,
+    /// Nó anterior a cada nó no seu caminho mínimo. A origem não tem anterior.
+    pub(crate) parent: PropertyMap<Node>,
+}
+
+impl<W: Num> ShortestPaths<W> {
+    /// Nó de origem dos caminhos.
+    pub fn source(&self) -> Node {
+        self.source
+    }
+    /// Distância da origem até `n`, ou `None` caso `n` não seja alcançável.
+    pub fn distance(&self, n: Node) -> Option<W> {
+        self.distance.get(n).copied()
+    }
+    /// Retorna os nós de um caminho mínimo da origem até `target`, incluindo os dois, ou `None`
+    /// caso `target` não seja alcançável.
+    pub fn path_to(&self, target: Node) -> Option<Vec<Node>> {
+        if !self.distance.contains(target) {
+            return None;
+        }
+        // Os anteriores levam de `target` até a origem, então o caminho é montado de trás para
+        // frente.
+        let mut path = vec![target];
+        let mut n = target;
+        while let Some(&previous) = self.parent.get(n) {
+            path.push(previous);
+            n = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Entrada da fila de prioridade de [`dijkstra`].
+///
+/// O `BinaryHeap` da biblioteca padrão retorna primeiro o maior elemento, então a comparação é
+/// invertida para que a menor distância saia primeiro. Como `W` só implementa `PartialOrd`,
+/// distâncias incomparáveis, como `NaN`, são tratadas como iguais.
+pub(crate) struct Entry<W>(pub(crate) W, pub(crate) Node);
+
+impl<W: Num> PartialEq for Entry<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: Num> Eq for Entry<W> {}
+
+impl<W: Num> PartialOrd for Entry<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Num> Ord for Entry<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.partial_cmp(&self.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Calcula os caminhos mínimos de `source` até todos os nós de `graph`, pelo algoritmo de
+/// Dijkstra.
+///
+/// Os nós são visitados em ordem crescente de distância, usando uma fila de prioridade: ao visitar
+/// um nó, a sua distância já é definitiva, e as arestas que saem dele podem melhorar a distância
+/// dos vizinhos. Com a fila, o custo é O((V + E) log V).
+///
+/// O algoritmo supõe que nenhum peso seja negativo; com pesos negativos, o resultado pode não ser
+/// mínimo. Causa um `panic` caso `source` não exista.
+pub fn dijkstra<W: Num>(graph: &dyn GraphRef<W>, source: Node) -> ShortestPaths<W> {
+    search(graph, source, None)
+}
+
+/// Calcula a distância entre todos os pares de nós de `graph`, no mesmo formato de
+/// [`GraphTiledMat::floyd_warshall`]: com `n` sendo o maior nó mais um, a distância de `a` até `b`
+/// fica na posição `a * n + b`, e é `None` caso `b` não seja alcançável a partir de `a`.
+///
+/// Em grafos esparsos, executa [`dijkstra`] a partir de cada nó, em O(V E log V). Em grafos densos,
+/// ou com pesos negativos, que Dijkstra não aceita, copia o grafo para um [`GraphTiledMat`] e usa
+/// Floyd-Warshall, em O(V³), que nesses casos é mais rápido.
+///
+/// Com um ciclo negativo, as distâncias dos nós alcançáveis por ele diminuem sem limite, então
+/// retorna `None`. Em grafos não direcionados, qualquer aresta negativa é um ciclo negativo, já que
+/// pode ser percorrida nos dois sentidos. A verificação usa Bellman-Ford, em O(V E), e só é feita
+/// quando há pesos negativos.
+pub fn distance_matrix<W: Num>(graph: &dyn GraphRef<W>) -> Option<Vec<Option<W>>> {
+    let n = node_ids(graph).last().map_or(0, |last| last as usize + 1);
+    let mut negative = false;
+    graph.for_each_edge(&mut |e| negative |= e.2 < W::ZERO);
+    // Dijkstra a partir de cada nó custa cerca de V E log V, e Floyd-Warshall, V³.
+    let log = (usize::BITS - n.leading_zeros()) as usize;
+    let dense = graph.edge_count() * log >= n * n;
+    trace_event!(
+        "distance matrix",
+        nodes = n,
+        dense = dense,
+        negative = negative
+    );
+    if negative && has_negative_cycle(graph, n) {
+        return None;
+    }
+    if dense || negative {
+        return Some(tiled_copy(graph, n).floyd_warshall());
+    }
+    let mut distances = vec![None; n * n];
+    for a in node_ids(graph) {
+        let row = a as usize * n;
+        for (b, &distance) in dijkstra(graph, a).distance.iter() {
+            distances[row + b as usize] = Some(distance);
+        }
+    }
+    Some(distances)
+}
+
+/// Verifica se `graph`, com nós menores que `n`, tem um ciclo negativo, pelo algoritmo de
+/// Bellman-Ford.
+///
+/// Todos os nós começam com distância zero, como se houvesse uma origem extra ligada a cada um
+/// deles por uma aresta de peso zero. Sem ciclos negativos, os caminhos a partir dela têm no
+/// máximo `n` arestas, então as distâncias param de mudar em até `n` rodadas.
+fn has_negative_cycle<W: Num>(graph: &dyn GraphRef<W>, n: usize) -> bool {
+    let directed = graph.is_directed();
+    let mut distance = vec![W::ZERO; n];
+    for _ in 0..=n {
+        let mut changed = false;
+        graph.for_each_edge(&mut |(a, b, weight)| {
+            let (a, b) = (a as usize, b as usize);
+            if distance[a] + weight < distance[b] {
+                distance[b] = distance[a] + weight;
+                changed = true;
+            }
+            if !directed && distance[b] + weight < distance[a] {
+                distance[a] = distance[b] + weight;
+                changed = true;
+            }
+        });
+        if !changed {
+            return false;
+        }
+    }
+    true
+}
+
+/// Copia `graph` para um [`GraphTiledMat`] com `n` nós, removendo os que não existem em `graph`
+/// para manter os mesmos identificadores.
+fn tiled_copy<W: Num>(graph: &dyn GraphRef<W>, n: usize) -> GraphTiledMat<W> {
+    let mut tiled = if graph.is_directed() {
+        GraphTiledMat::directed()
+    } else {
+        GraphTiledMat::default()
+    };
+    for node in tiled.add_nodes(n) {
+        if !graph.has_node(node) {
+            tiled.remove_node(node);
+        }
+    }
+    graph.for_each_edge(&mut |(a, b, weight)| tiled.add_edge(a, b, weight));
+    tiled
+}
+
+/// Calcula um caminho mínimo de `source` até `target`, retornando os seus nós, incluindo os dois,
+/// e o seu peso total, ou `None` caso `target` não seja alcançável.
+///
+/// Usa o mesmo algoritmo de [`dijkstra`], mas para assim que `target` é visitado. Causa um `panic`
+/// caso `source` não exista.
+pub fn shortest_path<W: Num>(
+    graph: &dyn GraphRef<W>,
+    source: Node,
+    target: Node,
+) -> Option<(Vec<Node>, W)> {
+    let paths = search(graph, source, Some(target));
+    Some((paths.path_to(target)?, paths.distance(target)?))
+}
+
+/// Indica se existe um caminho de `source` até `target`, seguindo a direção das arestas em grafos
+/// direcionados. Todo nó alcança a si mesmo.
+///
+/// Usa uma busca em largura, que ignora os pesos e para assim que encontra `target`, então é mais
+/// rápida que [`shortest_path`] quando só a existência do caminho importa. Causa um `panic` caso
+/// `source` ou `target` não existam.
+pub fn is_reachable<W: Num>(graph: &dyn GraphRef<W>, source: Node, target: Node) -> bool {
+    for n in [source, target] {
+        if !graph.has_node(n) {
+            panic!("Tried to search between inexistent node {n}");
+        }
+    }
+    // A busca é interrompida assim que `target` é descoberto.
+    struct Reach(Node);
+    impl<W: Num> Visitor<W> for Reach {
+        fn discover_node(&mut self, n: Node) -> Control {
+            match n == self.0 {
+                true => Control::Stop,
+                false => Control::Continue,
+            }
+        }
+    }
+    !breadth_first_search(graph, [source], &mut Reach(target))
+}
+
+/// Calcula os caminhos mínimos de `source` até todos os nós de `graph`, pelo algoritmo
+/// _delta-stepping_, com as mesmas distâncias de [`dijkstra`].
+///
+/// Em vez de uma fila de prioridade, os nós são separados em baldes pela distância: o balde `k`
+/// contém os nós com distância entre `k * delta` e `(k + 1) * delta`. Os baldes são processados em
+/// ordem, e todos os nós de um balde são expandidos juntos, sem uma ordem entre eles. As arestas
+/// leves (com peso até `delta`) podem devolver nós ao mesmo balde, que é expandido de novo até
+/// esvaziar; as pesadas só levam a baldes seguintes, então são relaxadas uma única vez, no fim.
+///
+/// Como cada balde é um lote de nós independentes, ele pode ser expandido em paralelo, o que é
+/// feito por [`crate::par_delta_stepping`] com a feature `rayon`. Um `delta` pequeno se aproxima de
+/// Dijkstra, com baldes de poucos nós; um grande se aproxima de Bellman-Ford, com nós expandidos
+/// várias vezes. Um bom ponto de partida é o peso médio das arestas.
+///
+/// Entre caminhos de mesmo peso, o escolhido pode ser diferente do de [`dijkstra`], mas é sempre
+/// o mesmo para um mesmo grafo. Assim como em [`dijkstra`], os pesos não podem ser negativos.
+/// Causa um `panic` caso `source` não exista ou `delta` não seja positivo.
+pub fn delta_stepping<W: Num>(graph: &dyn GraphRef<W>, source: Node, delta: W) -> ShortestPaths<W> {
+    delta_search(graph, source, delta, |nodes, light, distance| {
+        nodes
+            .iter()
+            .flat_map(|&a| relaxations(graph, a, light, delta, distance))
+            .collect()
+    })
+}
+
+/// Pedido para que a distância de um nó passe a ser a de um caminho pelo seu vizinho: contém o
+/// nó, a nova distância e o vizinho.
+pub(crate) type Relaxation<W> = (Node, W, Node);
+
+/// Relaxações das arestas leves (ou pesadas, caso `light` seja `false`) que saem de `a`, apenas
+/// as que melhoram a distância atual.
+pub(crate) fn relaxations<'a, W: Num>(
+    graph: &'a dyn GraphRef<W>,
+    a: Node,
+    light: bool,
+    delta: W,
+    distance: &'a PropertyMap<W>,
+) -> impl Iterator<Item = Relaxation<W>> + 'a {
+    let dist = distance[a];
+    graph
+        .out_edges(a)
+        .into_iter()
+        .filter(move |&(_, _, weight)| (weight <= delta) == light)
+        .map(move |(_, b, weight)| (b, dist + weight, a))
+        .filter(|&(b, candidate, _)| distance.get(b).is_none_or(|&current| candidate < current))
+}
+
+/// Implementação de [`delta_stepping`], em que `expand` calcula as relaxações das arestas leves
+/// ou pesadas de um lote de nós, sem modificar as distâncias.
+///
+/// As relaxações são aplicadas em ordem de nó, distância e vizinho, então o resultado não depende
+/// da ordem em que `expand` as retorna.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "delta_stepping",
+        level = "debug",
+        skip_all,
+        fields(source = source, delta = %delta)
+    )
+)]
+pub(crate) fn delta_search<W, F>(
+    graph: &dyn GraphRef<W>,
+    source: Node,
+    delta: W,
+    expand: F,
+) -> ShortestPaths<W>
+where
+    W: Num,
+    F: Fn(&[Node], bool, &PropertyMap<W>) -> Vec<Relaxation<W>>,
+{
+    if !graph.has_node(source) {
+        panic!("Tried to find paths from inexistent node {source}");
+    }
+    // Com `partial_cmp`, um `delta` incomparável, como `NaN`, também é rejeitado.
+    if delta.partial_cmp(&W::ZERO) != Some(Ordering::Greater) {
+        panic!("Delta must be positive, but is {delta}");
+    }
+    let bucket = |dist: W| (dist.to_f64() / delta.to_f64()) as usize;
+    let mut distance = PropertyMap::new();
+    let mut parent = PropertyMap::new();
+    // Só os baldes não vazios são guardados, indexados pela posição. Um vetor com todos os baldes
+    // até a maior distância ocuparia memória proporcional a ela, e não ao grafo.
+    let mut buckets = Map::from([(0, vec![source])]);
+    distance.insert(source, W::ZERO);
+
+    // Aplica as relaxações, colocando cada nó melhorado no balde da sua nova distância.
+    let mut apply = |mut relaxations: Vec<Relaxation<W>>,
+                     distance: &mut PropertyMap<W>,
+                     buckets: &mut Map<usize, Vec<Node>>| {
+        relaxations.sort_unstable_by(|x, y| {
+            (x.0.cmp(&y.0))
+                .then(x.1.partial_cmp(&y.1).unwrap_or(Ordering::Equal))
+                .then(x.2.cmp(&y.2))
+        });
+        for (b, candidate, a) in relaxations {
+            if distance.get(b).is_none_or(|&current| candidate < current) {
+                distance.insert(b, candidate);
+                parent.insert(b, a);
+                buckets.entry(bucket(candidate)).or_default().push(b);
+            }
+        }
+    };
+
+    let (mut expanded_buckets, mut batches, mut expansions) = (0usize, 0usize, 0usize);
+    // As relaxações só levam ao balde atual ou aos seguintes, então o primeiro balde é sempre o
+    // próximo a ser expandido.
+    while let Some((&k, _)) = buckets.first_key_value() {
+        let mut expanded = Vec::new();
+        while let Some(mut batch) = buckets.remove(&k) {
+            // Um nó pode ter sido colocado no balde mais de uma vez, ou ter saído dele para um
+            // balde anterior, ao ter a distância melhorada.
+            batch.retain(|&n| bucket(distance[n]) == k);
+            batch.sort_unstable();
+            batch.dedup();
+            let relaxations = expand(&batch, true, &distance);
+            apply(relaxations, &mut distance, &mut buckets);
+            batches += 1;
+            expansions += batch.len();
+            expanded.extend(batch);
+        }
+        // As distâncias dos nós expandidos já são definitivas, e as arestas pesadas só levam a
+        // baldes seguintes.
+        expanded.sort_unstable();
+        expanded.dedup();
+        let relaxations = expand(&expanded, false, &distance);
+        apply(relaxations, &mut distance, &mut buckets);
+        expanded_buckets += 1;
+    }
+    // Um nó pode ser expandido mais de uma vez, caso a sua distância melhore dentro do balde.
+    trace_event!(
+        "delta-stepping finished",
+        reached = distance.len(),
+        buckets = expanded_buckets,
+        batches = batches,
+        expansions = expansions,
+    );
+    ShortestPaths {
+        source,
+        distance,
+        parent,
+    }
+}
+
+/// Implementação de [`dijkstra`], que para ao visitar `target`, caso ele seja informado.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "dijkstra",
+        level = "debug",
+        skip_all,
+        fields(source = source, target = ?target)
+    )
+)]
+fn search<W: Num>(graph: &dyn GraphRef<W>, source: Node, target: Option<Node>) -> ShortestPaths<W> {
+    if !graph.has_node(source) {
+        panic!("Tried to find paths from inexistent node {source}");
+    }
+    let mut distance = PropertyMap::new();
+    let mut parent = PropertyMap::new();
+    let mut visited = PropertyMap::new();
+    let mut queue = BinaryHeap::new();
+    // Contadores para o evento do `tracing` no final.
+    let (mut popped, mut relaxed) = (0usize, 0usize);
+    distance.insert(source, W::ZERO);
+    queue.push(Entry(W::ZERO, source));
+    while let Some(Entry(dist, a)) = queue.pop() {
+        popped += 1;
+        // Um nó pode entrar na fila várias vezes, uma para cada melhora na sua distância; só a
+        // primeira saída, com a menor distância, é considerada.
+        if visited.insert(a, ()).is_some() {
+            continue;
+        }
+        if target == Some(a) {
+            break;
+        }
+        for (_, b, weight) in graph.out_edges(a) {
+            let candidate = dist + weight;
+            if distance.get(b).is_none_or(|&current| candidate < current) {
+                distance.insert(b, candidate);
+                parent.insert(b, a);
+                queue.push(Entry(candidate, b));
+                relaxed += 1;
+            }
+        }
+    }
+    trace_event!(
+        "dijkstra finished",
+        visited = visited.len(),
+        popped = popped,
+        relaxed = relaxed,
+    );
+    ShortestPaths {
+        source,
+        distance,
+        parent,
+    }
+}
@@ -0,0 +1,85 @@
+//! Conversões entre os grafos deste crate e o [`petgraph::Graph`], disponíveis com a feature
+//! `petgraph`.
+//!
+//! Assim, um grafo montado aqui pode usar um algoritmo que só o `petgraph` tem, e o resultado pode
+//! ser trazido de volta.
+
+use petgraph::graph::{Graph, NodeIndex};
+use petgraph::EdgeType;
+
+use crate::{node_ids, GraphAdj, GraphMat, GraphMut, GraphRef, Node, Num};
+
+/// Copia `graph` para um [`petgraph::Graph`], direcionado (`DiGraph`) ou não (`UnGraph`) de acordo
+/// com `Ty`.
+///
+/// Os índices do `petgraph` não têm buracos, então os nós são renumerados na ordem dos seus
+/// identificadores. O peso de cada nó do `petgraph` é o identificador original, para que os
+/// resultados possam ser relacionados com o grafo de origem.
+///
+/// Em um `UnGraph`, cada aresta de um grafo não direcionado aparece uma única vez. Já um grafo
+/// direcionado copiado para um `UnGraph` mantém as duas arestas de um par `a -> b` e `b -> a`, e
+/// um não direcionado copiado para um `DiGraph` ganha os dois sentidos de cada aresta.
+pub fn to_petgraph<W: Num, Ty: EdgeType>(graph: &dyn GraphRef<W>) -> Graph<Node, W, Ty> {
+    let mut out = Graph::with_capacity(graph.node_count(), 0);
+    let mut index = vec![NodeIndex::end(); node_ids(graph).last().map_or(0, |n| n as usize + 1)];
+    for n in node_ids(graph) {
+        index[n as usize] = out.add_node(n);
+    }
+    for (a, b, weight) in graph.edges_iter() {
+        if Ty::is_directed() || graph.is_directed() || a <= b {
+            out.add_edge(index[a as usize], index[b as usize], weight);
+        }
+    }
+    out
+}
+
+/// Copia um [`petgraph::Graph`] para `target`, que já deve estar vazio.
+///
+/// O nó de índice `i` vira o nó `i`, e o grafo é direcionado caso `Ty` seja. Caso o `petgraph`
+/// tenha arestas paralelas, fica o peso da última.
+fn from_petgraph<N, W: Num, Ty: EdgeType>(graph: &Graph<N, W, Ty>, target: &mut dyn GraphMut<W>) {
+    target.add_nodes(graph.node_count());
+    for edge in graph.raw_edges() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        target.add_edge(a as Node, b as Node, edge.weight);
+    }
+}
+
+impl<W: Num, Ty: EdgeType> From<&GraphAdj<W>> for Graph<Node, W, Ty> {
+    fn from(graph: &GraphAdj<W>) -> Self {
+        to_petgraph(graph)
+    }
+}
+
+impl<W: Num, Ty: EdgeType> From<&GraphMat<W>> for Graph<Node, W, Ty> {
+    fn from(graph: &GraphMat<W>) -> Self {
+        to_petgraph(graph)
+    }
+}
+
+/// Converte um [`petgraph::Graph`] em uma lista de adjacência. Veja [`to_petgraph`] para a
+/// conversão inversa.
+impl<N, W: Num, Ty: EdgeType> From<&Graph<N, W, Ty>> for GraphAdj<W> {
+    fn from(graph: &Graph<N, W, Ty>) -> Self {
+        let mut adj = if Ty::is_directed() {
+            GraphAdj::directed()
+        } else {
+            GraphAdj::default()
+        };
+        from_petgraph(graph, &mut adj);
+        adj
+    }
+}
+
+/// Converte um [`petgraph::Graph`] em uma matriz de adjacência.
+impl<N, W: Num, Ty: EdgeType> From<&Graph<N, W, Ty>> for GraphMat<W> {
+    fn from(graph: &Graph<N, W, Ty>) -> Self {
+        let mut mat = if Ty::is_directed() {
+            GraphMat::directed()
+        } else {
+            GraphMat::default()
+        };
+        from_petgraph(graph, &mut mat);
+        mat
+    }
+}
@@ -0,0 +1,70 @@
+//! Produtos de grafos, usados para gerar topologias como grades (o produto cartesiano de dois
+//! caminhos) a partir de grafos menores.
+//!
+//! Os nós do produto correspondem a pares `(u, v)`, com `u` de `g` e `v` de `h`. Sendo `n` a
+//! quantidade de nós de `h`, o par formado pelo `i`-ésimo nó de `g` e pelo `j`-ésimo nó de `h` (em
+//! ordem crescente) vira o nó `i * n + j`. Em grafos sem nós removidos, o `i`-ésimo nó é o próprio
+//! nó `i`, e o par `(u, v)` é simplesmente o nó `u * n + v`.
+//!
+//! O produto é direcionado caso `g` ou `h` seja.
+
+use crate::{compact_mapping, GraphAdj, GraphMut, GraphRef, Map, Node, Num};
+
+/// Constrói o produto cartesiano de `g` e `h`.
+///
+/// `(u, v)` e `(u', v')` são ligados quando `u = u'` e existe a aresta `v -> v'` em `h`, com o
+/// peso dela, ou quando `v = v'` e existe a aresta `u -> u'` em `g`, com o peso dela.
+pub fn cartesian_product<W: Num>(g: &dyn GraphRef<W>, h: &dyn GraphRef<W>) -> GraphAdj<W> {
+    let (mut product, g_ids, h_ids) = empty_product(g, h);
+    let n = h_ids.len() as Node;
+    for (u, u2, weight) in g.edges_iter() {
+        for &v in h_ids.values() {
+            product.add_edge(g_ids[&u] * n + v, g_ids[&u2] * n + v, weight);
+        }
+    }
+    for (v, v2, weight) in h.edges_iter() {
+        for &u in g_ids.values() {
+            product.add_edge(u * n + h_ids[&v], u * n + h_ids[&v2], weight);
+        }
+    }
+    product
+}
+
+/// Constrói o produto tensorial (ou categórico) de `g` e `h`.
+///
+/// `(u, v)` e `(u', v')` são ligados quando existem as arestas `u -> u'` em `g` e `v -> v'` em
+/// `h`. O peso da nova aresta é `combine(peso em g, peso em h)`.
+pub fn tensor_product<W: Num>(
+    g: &dyn GraphRef<W>,
+    h: &dyn GraphRef<W>,
+    combine: impl Fn(W, W) -> W,
+) -> GraphAdj<W> {
+    let (mut product, g_ids, h_ids) = empty_product(g, h);
+    let n = h_ids.len() as Node;
+    let h_edges = h.edges();
+    for (u, u2, g_weight) in g.edges_iter() {
+        for &(v, v2, h_weight) in &h_edges {
+            let a = g_ids[&u] * n + h_ids[&v];
+            let b = g_ids[&u2] * n + h_ids[&v2];
+            product.add_edge(a, b, combine(g_weight, h_weight));
+        }
+    }
+    product
+}
+
+/// Cria o grafo do produto, ainda sem arestas, junto com os índices dos nós de cada grafo.
+fn empty_product<W: Num>(
+    g: &dyn GraphRef<W>,
+    h: &dyn GraphRef<W>,
+) -> (GraphAdj<W>, Map<Node, Node>, Map<Node, Node>) {
+    // Os índices são os mesmos usados por `compact`.
+    let g_ids = compact_mapping(g);
+    let h_ids = compact_mapping(h);
+    let mut product = if g.is_directed() || h.is_directed() {
+        GraphAdj::directed()
+    } else {
+        GraphAdj::default()
+    };
+    product.add_nodes(g_ids.len() * h_ids.len());
+    (product, g_ids, h_ids)
+}
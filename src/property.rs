@@ -0,0 +1,205 @@
+use core::ops::{Index, IndexMut};
+
+use crate::prelude::*;
+use crate::{node_ids, GraphRef, Map, Node, Num};
+
+/// Associa um valor do tipo `T` a cada nó, como cores, distâncias ou marcações usadas por
+/// algoritmos.
+///
+/// Diferente de [`crate::NodeData`], não envolve o grafo: é uma estrutura à parte, que pode ser
+/// criada e descartada por um algoritmo sem modificar o grafo. Os valores são armazenados em um
+/// vetor indexado pelo nó, então o acesso é O(1).
+///
+/// Como o mapa não é avisado quando o grafo muda, depois de remover nós use
+/// [`PropertyMap::sync`], e depois de [`crate::GraphMut::compact`] use [`PropertyMap::remap`].
+#[derive(Debug, Clone)]
+pub struct PropertyMap<T> {
+    values: Vec<Option<T>>,
+}
+
+impl<T> Default for PropertyMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PropertyMap<T> {
+    /// Cria um mapa vazio.
+    pub fn new() -> Self {
+        Self { values: Vec::new() }
+    }
+    /// Cria um mapa em que todos os nós de `graph` recebem `value`.
+    pub fn filled<W: Num>(graph: &dyn GraphRef<W>, value: T) -> Self
+    where
+        T: Clone,
+    {
+        let mut map = Self::new();
+        for n in node_ids(graph) {
+            map.insert(n, value.clone());
+        }
+        map
+    }
+    /// Retorna o valor do nó `n`, caso exista.
+    pub fn get(&self, n: Node) -> Option<&T> {
+        self.values.get(n as usize)?.as_ref()
+    }
+    /// Retorna uma referência mutável ao valor do nó `n`, caso exista.
+    pub fn get_mut(&mut self, n: Node) -> Option<&mut T> {
+        self.values.get_mut(n as usize)?.as_mut()
+    }
+    /// Indica se o nó `n` possui um valor.
+    pub fn contains(&self, n: Node) -> bool {
+        self.get(n).is_some()
+    }
+    /// Atribui `value` ao nó `n`, retornando o valor anterior, caso houvesse um.
+    pub fn insert(&mut self, n: Node, value: T) -> Option<T> {
+        let n = n as usize;
+        if n >= self.values.len() {
+            self.values.resize_with(n + 1, || None);
+        }
+        self.values[n].replace(value)
+    }
+    /// Remove o valor do nó `n`, retornando-o caso existisse.
+    pub fn remove(&mut self, n: Node) -> Option<T> {
+        self.values.get_mut(n as usize)?.take()
+    }
+    /// Retorna a quantidade de nós com valor.
+    pub fn len(&self) -> usize {
+        self.values.iter().flatten().count()
+    }
+    /// Indica se nenhum nó possui valor.
+    pub fn is_empty(&self) -> bool {
+        self.values.iter().all(Option::is_none)
+    }
+    /// Retorna um iterador sobre os nós com valor e seus valores, em ordem crescente de nó.
+    pub fn iter(&self) -> impl Iterator<Item = (Node, &T)> + '_ {
+        self.values
+            .iter()
+            .enumerate()
+            .filter_map(|(n, value)| Some((n as Node, value.as_ref()?)))
+    }
+    /// Remove os valores dos nós que não existem mais em `graph`.
+    pub fn sync<W: Num>(&mut self, graph: &dyn GraphRef<W>) {
+        for (n, value) in self.values.iter_mut().enumerate() {
+            if !graph.has_node(n as Node) {
+                *value = None;
+            }
+        }
+    }
+    /// Renumera os nós de acordo com `mapping`, como o retornado por
+    /// [`crate::GraphMut::compact`]. Valores de nós fora do mapeamento são descartados.
+    pub fn remap(&mut self, mapping: &Map<Node, Node>) {
+        let values = core::mem::take(&mut self.values);
+        for (old, value) in values.into_iter().enumerate() {
+            if let (Some(&new), Some(value)) = (mapping.get(&(old as Node)), value) {
+                self.insert(new, value);
+            }
+        }
+    }
+}
+
+/// Permite ler o valor de um nó com `map[n]`. Causa um `panic` caso o nó não tenha valor.
+impl<T> Index<Node> for PropertyMap<T> {
+    type Output = T;
+
+    fn index(&self, n: Node) -> &T {
+        self.get(n)
+            .unwrap_or_else(|| panic!("Tried to read property of node {n}, which has none"))
+    }
+}
+
+impl<T> IndexMut<Node> for PropertyMap<T> {
+    fn index_mut(&mut self, n: Node) -> &mut T {
+        self.get_mut(n)
+            .unwrap_or_else(|| panic!("Tried to read property of node {n}, which has none"))
+    }
+}
+
+/// Associa um valor do tipo `T` a cada aresta, como fluxos ou marcações usadas por algoritmos.
+///
+/// Assim como [`PropertyMap`], é uma estrutura à parte do grafo. Em grafos não direcionados,
+/// `(a, b)` e `(b, a)` se referem ao mesmo valor, por isso a direção do grafo é informada na
+/// criação do mapa.
+#[derive(Debug, Clone)]
+pub struct EdgePropertyMap<T> {
+    values: Map<(Node, Node), T>,
+    directed: bool,
+}
+
+impl<T> EdgePropertyMap<T> {
+    /// Cria um mapa vazio para as arestas de `graph`.
+    pub fn new<W: Num>(graph: &dyn GraphRef<W>) -> Self {
+        Self {
+            values: Map::new(),
+            directed: graph.is_directed(),
+        }
+    }
+    /// Cria um mapa em que todas as arestas de `graph` recebem `value`.
+    pub fn filled<W: Num>(graph: &dyn GraphRef<W>, value: T) -> Self
+    where
+        T: Clone,
+    {
+        let mut map = Self::new(graph);
+        for (a, b, _) in graph.edges_iter() {
+            map.insert(a, b, value.clone());
+        }
+        map
+    }
+    /// Retorna a chave usada para a aresta entre `a` e `b`.
+    fn key(&self, a: Node, b: Node) -> (Node, Node) {
+        if self.directed || a <= b {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+    /// Retorna o valor da aresta entre `a` e `b`, caso exista.
+    pub fn get(&self, a: Node, b: Node) -> Option<&T> {
+        self.values.get(&self.key(a, b))
+    }
+    /// Retorna uma referência mutável ao valor da aresta entre `a` e `b`, caso exista.
+    pub fn get_mut(&mut self, a: Node, b: Node) -> Option<&mut T> {
+        let key = self.key(a, b);
+        self.values.get_mut(&key)
+    }
+    /// Indica se a aresta entre `a` e `b` possui um valor.
+    pub fn contains(&self, a: Node, b: Node) -> bool {
+        self.get(a, b).is_some()
+    }
+    /// Atribui `value` à aresta entre `a` e `b`, retornando o valor anterior, caso houvesse um.
+    pub fn insert(&mut self, a: Node, b: Node, value: T) -> Option<T> {
+        let key = self.key(a, b);
+        self.values.insert(key, value)
+    }
+    /// Remove o valor da aresta entre `a` e `b`, retornando-o caso existisse.
+    pub fn remove(&mut self, a: Node, b: Node) -> Option<T> {
+        let key = self.key(a, b);
+        self.values.remove(&key)
+    }
+    /// Retorna a quantidade de arestas com valor.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+    /// Indica se nenhuma aresta possui valor.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+    /// Retorna um iterador sobre as arestas com valor e seus valores. Em grafos não direcionados,
+    /// cada aresta aparece uma única vez, com `a <= b`.
+    pub fn iter(&self) -> impl Iterator<Item = ((Node, Node), &T)> + '_ {
+        self.values.iter().map(|(&key, value)| (key, value))
+    }
+    /// Remove os valores das arestas que não existem mais em `graph`.
+    pub fn sync<W: Num>(&mut self, graph: &dyn GraphRef<W>) {
+        self.values.retain(|&(a, b), _| graph.has_edge(a, b));
+    }
+    /// Renumera os nós de acordo com `mapping`, como o retornado por
+    /// [`crate::GraphMut::compact`]. Valores de arestas fora do mapeamento são descartados.
+    pub fn remap(&mut self, mapping: &Map<Node, Node>) {
+        let values = core::mem::take(&mut self.values);
+        self.values = values
+            .into_iter()
+            .filter_map(|((a, b), value)| Some(((*mapping.get(&a)?, *mapping.get(&b)?), value)))
+            .collect();
+    }
+}
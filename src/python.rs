@@ -0,0 +1,247 @@
+//! Módulo Python `ex1`, com a feature `python`, gerado pelo PyO3.
+//!
+//! Compilado como uma biblioteca dinâmica (veja o README), o crate pode ser importado com
+//! `import ex1`, e os grafos e algoritmos podem ser usados em notebooks sem reescrevê-los em
+//! Python. As classes `GraphAdj` e `GraphMat` guardam pesos `u32`, os nós começam em `0`, e os
+//! algoritmos são funções do módulo que aceitam qualquer uma das duas.
+//!
+//! Um `panic` dentro de uma função chamada pelo Python vira um `PanicException`, que não deveria
+//! ser capturado, então os nós são verificados antes de chamar a biblioteca, e um nó inexistente
+//! resulta em um `ValueError`.
+
+use pyo3::exceptions::{PyOSError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::{
+    betweenness_centrality, clustering_coefficients, connected_components, diameter, dijkstra,
+    eccentricity, fill_graph_from_reader, is_reachable, minimum_spanning_tree, node_ids,
+    open_input, shortest_path, BetweennessOptions, Edge, GraphMut, GraphRef, Map, Node,
+    ParseGraphError, Weight,
+};
+
+/// Converte um erro de leitura da entrada para a exceção correspondente do Python.
+fn parse_error(error: ParseGraphError) -> PyErr {
+    match error {
+        ParseGraphError::Io(e) => PyOSError::new_err(e.to_string()),
+        e => PyValueError::new_err(e.to_string()),
+    }
+}
+
+fn check_nodes(graph: &dyn GraphRef, nodes: &[Node]) -> PyResult<()> {
+    match nodes.iter().find(|&&n| !graph.has_node(n)) {
+        Some(n) => Err(PyValueError::new_err(format!("node {n} does not exist"))),
+        None => Ok(()),
+    }
+}
+
+/// Gera uma classe Python para um grafo da biblioteca, com os mesmos métodos para todas as
+/// representações. O PyO3 não aceita classes genéricas, então cada representação precisa da sua.
+macro_rules! py_graph {
+    ($name:ident, $py_name:literal, $graph:ty) => {
+        #[doc = concat!("O [`", stringify!($graph), "`] visto pelo Python como `", $py_name, "`.")]
+        #[pyclass(name = $py_name)]
+        pub struct $name {
+            graph: $graph,
+        }
+
+        #[pymethods]
+        impl $name {
+            #[new]
+            #[pyo3(signature = (directed = false))]
+            fn new(directed: bool) -> Self {
+                let graph = if directed {
+                    <$graph>::directed()
+                } else {
+                    <$graph>::default()
+                };
+                Self { graph }
+            }
+            /// Lê um grafo no formato de entrada do exercício a partir de um texto.
+            #[staticmethod]
+            #[pyo3(signature = (text, directed = false))]
+            fn from_input(text: &str, directed: bool) -> PyResult<Self> {
+                let mut graph = Self::new(directed);
+                fill_graph_from_reader(text.as_bytes(), &mut graph.graph).map_err(parse_error)?;
+                Ok(graph)
+            }
+            /// Lê um grafo no formato de entrada do exercício a partir de um arquivo, que pode
+            /// estar comprimido, como em `open_input`.
+            #[staticmethod]
+            #[pyo3(signature = (path, directed = false))]
+            fn read(path: std::path::PathBuf, directed: bool) -> PyResult<Self> {
+                let mut graph = Self::new(directed);
+                let reader = open_input(&path).map_err(|e| PyOSError::new_err(e.to_string()))?;
+                fill_graph_from_reader(reader, &mut graph.graph).map_err(parse_error)?;
+                Ok(graph)
+            }
+            fn add_node(&mut self) -> Node {
+                self.graph.add_node()
+            }
+            /// Cria `count` nós, retornando os seus identificadores.
+            fn add_nodes(&mut self, count: usize) -> Vec<Node> {
+                self.graph.add_nodes(count).collect()
+            }
+            #[pyo3(signature = (a, b, weight = 1))]
+            fn add_edge(&mut self, a: Node, b: Node, weight: Weight) -> PyResult<()> {
+                check_nodes(&self.graph, &[a, b])?;
+                self.graph.add_edge(a, b, weight);
+                Ok(())
+            }
+            fn remove_node(&mut self, n: Node) -> PyResult<()> {
+                check_nodes(&self.graph, &[n])?;
+                self.graph.remove_node(n);
+                Ok(())
+            }
+            /// Remove a aresta `a - b`, retornando o seu peso, ou `None` caso ela não exista.
+            fn remove_edge(&mut self, a: Node, b: Node) -> Option<Weight> {
+                self.graph.remove_edge(a, b)
+            }
+            fn has_node(&self, n: Node) -> bool {
+                self.graph.has_node(n)
+            }
+            fn get_edge_weight(&self, a: Node, b: Node) -> Option<Weight> {
+                self.graph.get_edge_weight(a, b)
+            }
+            fn node_count(&self) -> usize {
+                self.graph.node_count()
+            }
+            fn edge_count(&self) -> usize {
+                self.graph.edge_count()
+            }
+            #[getter]
+            fn directed(&self) -> bool {
+                self.graph.is_directed()
+            }
+            /// Retorna as arestas como tuplas `(a, b, peso)`, ordenadas.
+            fn edges(&self) -> Vec<Edge> {
+                self.graph.edges()
+            }
+            fn neighbors(&self, n: Node) -> PyResult<Vec<Node>> {
+                check_nodes(&self.graph, &[n])?;
+                Ok(self.graph.neighbors(n).collect())
+            }
+            fn __repr__(&self) -> String {
+                format!(
+                    "{}(nodes={}, edges={}, directed={})",
+                    $py_name,
+                    self.graph.node_count(),
+                    self.graph.edge_count(),
+                    if self.graph.is_directed() {
+                        "True"
+                    } else {
+                        "False"
+                    }
+                )
+            }
+        }
+    };
+}
+
+py_graph!(PyGraphAdj, "GraphAdj", crate::GraphAdj);
+py_graph!(PyGraphMat, "GraphMat", crate::GraphMat);
+
+/// Qualquer uma das classes de grafo, aceita pelas funções do módulo.
+#[derive(FromPyObject)]
+enum AnyGraph<'py> {
+    Adj(PyRef<'py, PyGraphAdj>),
+    Mat(PyRef<'py, PyGraphMat>),
+}
+
+impl AnyGraph<'_> {
+    fn graph(&self) -> &dyn GraphRef {
+        match self {
+            AnyGraph::Adj(graph) => &graph.graph,
+            AnyGraph::Mat(graph) => &graph.graph,
+        }
+    }
+}
+
+/// Retorna a distância mínima de `source` até cada nó alcançável, como em [`dijkstra`].
+#[pyfunction(name = "dijkstra")]
+fn py_dijkstra(graph: AnyGraph<'_>, source: Node) -> PyResult<Map<Node, Weight>> {
+    let graph = graph.graph();
+    check_nodes(graph, &[source])?;
+    let paths = dijkstra(graph, source);
+    let reached = node_ids(graph).filter_map(|n| Some((n, paths.distance(n)?)));
+    Ok(reached.collect())
+}
+
+/// Retorna os nós de um caminho mínimo e o seu peso, ou `None` caso `target` não seja alcançável.
+#[pyfunction(name = "shortest_path")]
+fn py_shortest_path(
+    graph: AnyGraph<'_>,
+    source: Node,
+    target: Node,
+) -> PyResult<Option<(Vec<Node>, Weight)>> {
+    let graph = graph.graph();
+    check_nodes(graph, &[source, target])?;
+    Ok(shortest_path(graph, source, target))
+}
+
+#[pyfunction(name = "is_reachable")]
+fn py_is_reachable(graph: AnyGraph<'_>, source: Node, target: Node) -> PyResult<bool> {
+    let graph = graph.graph();
+    check_nodes(graph, &[source, target])?;
+    Ok(is_reachable(graph, source, target))
+}
+
+#[pyfunction(name = "connected_components")]
+fn py_connected_components(graph: AnyGraph<'_>) -> Vec<Vec<Node>> {
+    connected_components(graph.graph())
+}
+
+#[pyfunction(name = "minimum_spanning_tree")]
+fn py_minimum_spanning_tree(graph: AnyGraph<'_>) -> Vec<Edge> {
+    minimum_spanning_tree(graph.graph())
+}
+
+#[pyfunction(name = "eccentricity")]
+fn py_eccentricity(graph: AnyGraph<'_>, source: Node) -> PyResult<Option<usize>> {
+    let graph = graph.graph();
+    check_nodes(graph, &[source])?;
+    Ok(eccentricity(graph, source))
+}
+
+#[pyfunction(name = "diameter")]
+fn py_diameter(graph: AnyGraph<'_>) -> Option<usize> {
+    diameter(graph.graph())
+}
+
+/// Retorna o coeficiente de agrupamento de cada nó.
+#[pyfunction(name = "clustering_coefficients")]
+fn py_clustering_coefficients(graph: AnyGraph<'_>) -> Map<Node, f64> {
+    let coefficients = clustering_coefficients(graph.graph());
+    coefficients.iter().map(|(n, &c)| (n, c)).collect()
+}
+
+/// Retorna a centralidade de intermediação de cada nó, como em [`betweenness_centrality`].
+#[pyfunction(name = "betweenness_centrality")]
+#[pyo3(signature = (graph, sample_sources = None, seed = 0))]
+fn py_betweenness_centrality(
+    graph: AnyGraph<'_>,
+    sample_sources: Option<usize>,
+    seed: u64,
+) -> Map<Node, f64> {
+    let options = BetweennessOptions {
+        sample_sources,
+        seed,
+    };
+    let centrality = betweenness_centrality(graph.graph(), &options);
+    centrality.iter().map(|(n, &c)| (n, c)).collect()
+}
+
+#[pymodule]
+fn ex1(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyGraphAdj>()?;
+    module.add_class::<PyGraphMat>()?;
+    module.add_function(wrap_pyfunction!(py_dijkstra, module)?)?;
+    module.add_function(wrap_pyfunction!(py_shortest_path, module)?)?;
+    module.add_function(wrap_pyfunction!(py_is_reachable, module)?)?;
+    module.add_function(wrap_pyfunction!(py_connected_components, module)?)?;
+    module.add_function(wrap_pyfunction!(py_minimum_spanning_tree, module)?)?;
+    module.add_function(wrap_pyfunction!(py_eccentricity, module)?)?;
+    module.add_function(wrap_pyfunction!(py_diameter, module)?)?;
+    module.add_function(wrap_pyfunction!(py_clustering_coefficients, module)?)?;
+    module.add_function(wrap_pyfunction!(py_betweenness_centrality, module)?)?;
+    Ok(())
+}
@@ -0,0 +1,36 @@
+/// Gerador de números pseudoaleatórios SplitMix64.
+///
+/// Não serve para criptografia, mas é rápido, tem boa qualidade estatística e não depende de
+/// nenhuma biblioteca externa. Como o algoritmo é fixo, os grafos gerados com uma semente não
+/// mudam entre versões do crate.
+#[derive(Debug, Clone)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+    /// Embaralha `items` pelo algoritmo de Fisher-Yates: cada posição, do fim para o início, troca
+    /// com uma das anteriores, ou com ela mesma. Todas as ordens têm a mesma probabilidade.
+    pub(crate) fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.next_u64() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+    /// Retorna um número em `[0, 1)`.
+    #[cfg(feature = "std")]
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        // Os 53 bits mais altos preenchem exatamente a mantissa de um `f64`.
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -0,0 +1,112 @@
+//! Implementações de `Serialize` e `Deserialize` do `serde` para [`GraphAdj`] e [`GraphMat`],
+//! disponíveis com a feature `serde`. Uma [`Edge`] é uma tupla, então já é serializável.
+//!
+//! Os grafos não são serializados campo a campo: a matriz de um `GraphMat`, por exemplo, depende
+//! da capacidade reservada, que não faz parte do grafo. As duas representações usam o mesmo
+//! formato, com a orientação, as políticas, o próximo identificador de nó, os nós removidos e as
+//! arestas, então um `GraphAdj` serializado pode ser lido como um `GraphMat` e vice-versa. Assim,
+//! os identificadores dos nós, inclusive os dos nós criados depois, se mantêm após a leitura.
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::prelude::*;
+use crate::{Edge, GraphAdj, GraphMat, GraphMut, GraphRef, Node, Num, SelfLoops};
+
+/// O formato serializado dos grafos.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Graph")]
+struct GraphData<W> {
+    directed: bool,
+    self_loops: SelfLoops,
+    /// Equivale a [`GraphAdj::with_id_reuse`]. O `GraphMat` nunca reutiliza identificadores, e
+    /// ignora este campo na leitura.
+    reuse_ids: bool,
+    /// O identificador que o próximo nó criado com `add_node` receberá, caso nenhum seja
+    /// reutilizado. Os nós existentes são os de `0..next_node` que não estão em `removed`.
+    next_node: Node,
+    removed: Vec<Node>,
+    /// As arestas, com as não direcionadas aparecendo uma única vez, com `a <= b`.
+    edges: Vec<Edge<W>>,
+}
+
+impl<W: Num> GraphData<W> {
+    fn new(graph: &dyn GraphRef<W>, reuse_ids: bool, next_node: Node) -> Self {
+        let directed = graph.is_directed();
+        Self {
+            directed,
+            self_loops: graph.self_loops(),
+            reuse_ids,
+            next_node,
+            removed: (0..next_node).filter(|&n| !graph.has_node(n)).collect(),
+            edges: graph
+                .edges_iter()
+                .filter(|&(a, b, _)| directed || a <= b)
+                .collect(),
+        }
+    }
+    /// Recria o grafo em `graph`, que precisa estar vazio, retornando uma mensagem de erro caso
+    /// os dados sejam inconsistentes.
+    fn fill(self, graph: &mut dyn GraphMut<W>) -> Result<(), String> {
+        graph.add_nodes(self.next_node as usize);
+        for n in self.removed {
+            if !graph.has_node(n) {
+                return Err(format!(
+                    "removed node {n} is not below next_node or is repeated"
+                ));
+            }
+            graph.remove_node(n);
+        }
+        for (a, b, weight) in self.edges {
+            if let Some(n) = [a, b].into_iter().find(|&n| !graph.has_node(n)) {
+                return Err(format!("edge {a} - {b} uses inexistent node {n}"));
+            }
+            if a == b && self.self_loops == SelfLoops::Reject {
+                return Err(format!(
+                    "self-loop on node {a} in a graph that rejects them"
+                ));
+            }
+            graph.add_edge(a, b, weight);
+        }
+        Ok(())
+    }
+}
+
+impl<W: Num + Serialize> Serialize for GraphAdj<W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GraphData::new(self, self.reuse_ids, self.next_node).serialize(serializer)
+    }
+}
+
+impl<'de, W: Num + Deserialize<'de>> Deserialize<'de> for GraphAdj<W> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = GraphData::deserialize(deserializer)?;
+        let mut graph = GraphAdj {
+            directed: data.directed,
+            self_loops: data.self_loops,
+            reuse_ids: data.reuse_ids,
+            ..Default::default()
+        };
+        data.fill(&mut graph).map_err(D::Error::custom)?;
+        Ok(graph)
+    }
+}
+
+impl<W: Num + Serialize> Serialize for GraphMat<W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GraphData::new(self, false, self.node_count as Node).serialize(serializer)
+    }
+}
+
+impl<'de, W: Num + Deserialize<'de>> Deserialize<'de> for GraphMat<W> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = GraphData::deserialize(deserializer)?;
+        let mut graph = GraphMat {
+            directed: data.directed,
+            self_loops: data.self_loops,
+            ..Default::default()
+        };
+        data.fill(&mut graph).map_err(D::Error::custom)?;
+        Ok(graph)
+    }
+}
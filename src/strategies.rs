@@ -0,0 +1,77 @@
+//! Estratégias do `proptest` que geram grafos aleatórios, disponíveis com a feature `proptest`.
+//!
+//! Os grafos gerados incluem os casos que geradores escritos à mão costumam esquecer: grafos
+//! vazios, nós isolados, laços e arestas repetidas (das quais só a última é mantida). Quando um
+//! teste falha, o `proptest` reduz o grafo primeiro removendo arestas e depois nós, até encontrar
+//! um exemplo mínimo.
+//!
+//! As pontas de cada aresta são sorteadas entre todos os nós possíveis e convertidas em nós
+//! existentes com o resto da divisão pela quantidade de nós. Assim, as arestas continuam válidas
+//! quando a quantidade de nós diminui, e as que só usam os primeiros nós não mudam, o que equivale
+//! a remover os últimos nós.
+
+use std::fmt::Debug;
+
+use proptest::arbitrary::{any, Arbitrary};
+use proptest::collection::vec;
+use proptest::strategy::{BoxedStrategy, Strategy};
+
+use crate::{GraphAdj, GraphMat, GraphMut, Node, Num, SelfLoops};
+
+/// Gera grafos a partir de `graph`, com até `max_nodes` nós, até `max_edges` arestas e pesos
+/// sorteados por `weights`.
+///
+/// O grafo `graph` é clonado e preenchido, então deve estar vazio, e define a orientação e as
+/// políticas dos grafos gerados: `arb_graph(GraphAdj::directed(), 8, 16, 1..10u32)` gera grafos
+/// direcionados, por exemplo. Caso ele rejeite laços, as arestas sorteadas com `a == b` são
+/// ignoradas.
+pub fn arb_graph<G, W>(
+    graph: G,
+    max_nodes: usize,
+    max_edges: usize,
+    weights: impl Strategy<Value = W>,
+) -> impl Strategy<Value = G>
+where
+    G: GraphMut<W> + Clone + Debug,
+    W: Num,
+{
+    // A tupla é reduzida na ordem dos seus elementos, então as arestas são reduzidas antes dos
+    // nós.
+    let ends = 0..max_nodes.max(1) as Node;
+    let edges = vec((ends.clone(), ends, weights), 0..=max_edges);
+    (edges, 0..=max_nodes).prop_map(move |(edges, nodes)| {
+        let mut graph = graph.clone();
+        graph.add_nodes(nodes);
+        if nodes == 0 {
+            return graph;
+        }
+        for (a, b, weight) in edges {
+            let (a, b) = (a % nodes as Node, b % nodes as Node);
+            if a != b || graph.self_loops() == SelfLoops::Allow {
+                graph.add_edge(a, b, weight);
+            }
+        }
+        graph
+    })
+}
+
+/// Permite usar `any::<GraphAdj<W>>()`, que gera grafos não direcionados com até 16 nós e 32
+/// arestas com [`arb_graph`].
+impl<W: Num + Arbitrary> Arbitrary for GraphAdj<W> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        arb_graph(GraphAdj::default(), 16, 32, any::<W>()).boxed()
+    }
+}
+
+/// Permite usar `any::<GraphMat<W>>()`, como em [`GraphAdj`].
+impl<W: Num + Arbitrary> Arbitrary for GraphMat<W> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        arb_graph(GraphMat::default(), 16, 32, any::<W>()).boxed()
+    }
+}
@@ -0,0 +1,401 @@
+use core::ops::Range;
+
+use crate::prelude::*;
+use crate::{
+    compact_mapping, map_bytes, validate_edges, vec_bytes, Edge, GraphError, GraphMut, GraphRef,
+    Map, Node, Num, SelfLoops, Set, Weight,
+};
+
+/// Lado de cada bloco da matriz. Um bloco de `Option<u32>` ocupa 8 KiB, então os três blocos
+/// usados de cada vez por [`GraphTiledMat::floyd_warshall`] cabem no cache L1.
+const TILE: usize = 32;
+
+/// Struct que representa um grafo por uma matriz de adjacência dividida em blocos.
+///
+/// A matriz é a mesma de [`crate::GraphMat`], mas é armazenada bloco a bloco: ela é dividida em
+/// quadrados de `32 × 32` posições, e as posições de cada quadrado ficam juntas na memória, linha a
+/// linha. Em uma matriz guardada linha a linha, percorrer uma coluna salta uma linha inteira a cada
+/// passo, e com matrizes maiores que o cache cada passo é uma nova leitura da memória. Aqui, os
+/// passos de uma coluna ficam dentro de um mesmo bloco a cada 32 nós, e algoritmos que processam
+/// a matriz bloco a bloco, como [`GraphTiledMat::floyd_warshall`], trabalham sempre sobre poucos
+/// blocos que cabem no cache.
+///
+/// A matriz completa é armazenada mesmo em grafos não direcionados, e a sua dimensão é arredondada
+/// para um múltiplo de 32, então grafos pequenos ocupam mais memória que em `GraphMat`.
+#[derive(Default, Debug, Clone)]
+pub struct GraphTiledMat<W = Weight> {
+    node_count: usize,
+    /// Quantidade de blocos em cada linha (e em cada coluna) da matriz.
+    tiles_per_side: usize,
+    /// Blocos armazenados linha a linha, cada um com `TILE * TILE` posições (veja
+    /// [`GraphTiledMat::cell`]).
+    links: Vec<Option<W>>,
+    directed: bool,
+    /// Nós removidos. Suas linhas e colunas continuam na matriz, mas ficam vazias.
+    removed: Set<Node>,
+    self_loops: SelfLoops,
+    /// Quantidade de posições ocupadas e quantas delas estão na diagonal, assim como em
+    /// [`crate::GraphAdj`].
+    entry_count: usize,
+    loop_count: usize,
+}
+
+impl<W: Num> GraphTiledMat<W> {
+    /// Cria um grafo direcionado vazio.
+    ///
+    /// `GraphTiledMat::default()` continua criando um grafo não direcionado.
+    pub fn directed() -> Self {
+        Self {
+            directed: true,
+            ..Default::default()
+        }
+    }
+    /// Define a política do grafo para laços. Só afeta as arestas adicionadas depois.
+    pub fn with_self_loops(mut self, policy: SelfLoops) -> Self {
+        self.self_loops = policy;
+        self
+    }
+    /// Retorna quantos bytes o grafo ocupa na heap, sem contar o próprio `GraphTiledMat`.
+    pub fn memory_usage(&self) -> usize {
+        vec_bytes(&self.links) + map_bytes::<Node, ()>(self.removed.len())
+    }
+    /// Calcula a distância entre todos os pares de nós, pelo algoritmo de Floyd-Warshall.
+    ///
+    /// O resultado tem `n × n` posições, sendo `n` o maior nó mais um: a distância de `a` até `b`
+    /// fica na posição `a * n + b`, e é `None` caso `b` não seja alcançável a partir de `a`. As
+    /// linhas e colunas de nós removidos ficam vazias. Pesos negativos são aceitos, desde que não
+    /// formem um ciclo negativo.
+    ///
+    /// O algoritmo é a versão em blocos: para cada bloco `k` da diagonal, o bloco é atualizado
+    /// primeiro, depois os blocos da sua linha e da sua coluna, e por fim todos os outros, cada um a
+    /// partir de um bloco da linha e um da coluna `k`. O resultado é o mesmo da versão com três
+    /// laços sobre a matriz inteira, também em O(n³), mas cada etapa lê apenas três blocos.
+    pub fn floyd_warshall(&self) -> Vec<Option<W>> {
+        let mut dist = self.links.clone();
+        // Todo nó existente está a distância zero de si mesmo, a não ser que tenha um laço
+        // negativo.
+        for n in (0..self.node_count as Node).filter(|&n| self.has_node(n)) {
+            let cell = self.cell(n as usize, n as usize);
+            if dist[cell].is_none_or(|weight| weight > W::ZERO) {
+                dist[cell] = Some(W::ZERO);
+            }
+        }
+        let tiles = self.tiles_per_side;
+        let tile =
+            |i: usize, j: usize| (i * tiles + j) * TILE * TILE..(i * tiles + j + 1) * TILE * TILE;
+        for k in 0..tiles {
+            relax_tile(&mut dist[tile(k, k)], None, None);
+            let diagonal = dist[tile(k, k)].to_vec();
+            for j in (0..tiles).filter(|&j| j != k) {
+                relax_tile(&mut dist[tile(k, j)], Some(&diagonal), None);
+                relax_tile(&mut dist[tile(j, k)], None, Some(&diagonal));
+            }
+            // Os blocos da linha e da coluna `k` não mudam mais nesta etapa, então são copiados
+            // para poderem ser lidos enquanto os outros blocos são escritos.
+            let row: Vec<_> = (0..tiles).map(|j| dist[tile(k, j)].to_vec()).collect();
+            let column: Vec<_> = (0..tiles).map(|i| dist[tile(i, k)].to_vec()).collect();
+            for i in (0..tiles).filter(|&i| i != k) {
+                for j in (0..tiles).filter(|&j| j != k) {
+                    relax_tile(&mut dist[tile(i, j)], Some(&column[i]), Some(&row[j]));
+                }
+            }
+        }
+        let n = (0..self.node_count as Node)
+            .rev()
+            .find(|&n| self.has_node(n))
+            .map_or(0, |n| n as usize + 1);
+        let mut matrix = Vec::with_capacity(n * n);
+        for a in 0..n {
+            matrix.extend((0..n).map(|b| dist[self.cell(a, b)]));
+        }
+        matrix
+    }
+
+    /// Posição da aresta `a -> b` em `links`: o bloco `(a / 32, b / 32)` começa na posição
+    /// `(a / 32 * tiles_per_side + b / 32) * 32²`, e dentro dele a aresta está na linha `a % 32` e
+    /// coluna `b % 32`.
+    fn cell(&self, a: usize, b: usize) -> usize {
+        cell_index(self.tiles_per_side, a, b)
+    }
+    fn check_node(&self, n: Node, action: &str) {
+        if !self.has_node(n) {
+            panic!("Tried to {action} inexistent node {n}");
+        }
+    }
+    /// Percorre a linha `a`, com uma posição para cada nó, contando os removidos.
+    fn row_cells(&self, a: usize) -> impl Iterator<Item = (usize, Option<W>)> + '_ {
+        let n = if a < self.node_count {
+            self.node_count
+        } else {
+            0
+        };
+        (0..n).map(move |b| (b, self.links[self.cell(a, b)]))
+    }
+    /// Percorre a coluna `b`, com uma posição para cada nó, contando os removidos.
+    fn column_cells(&self, b: usize) -> impl Iterator<Item = (usize, Option<W>)> + '_ {
+        let n = if b < self.node_count {
+            self.node_count
+        } else {
+            0
+        };
+        (0..n).map(move |a| (a, self.links[self.cell(a, b)]))
+    }
+    /// Escreve `weight` na posição `a -> b`, atualizando a contagem de arestas.
+    fn set_entry(&mut self, a: usize, b: usize, weight: Option<W>) {
+        let cell = self.cell(a, b);
+        let was_set = core::mem::replace(&mut self.links[cell], weight).is_some();
+        let diagonal = usize::from(a == b);
+        match (was_set, weight.is_some()) {
+            (false, true) => {
+                self.entry_count += 1;
+                self.loop_count += diagonal;
+            }
+            (true, false) => {
+                self.entry_count -= 1;
+                self.loop_count -= diagonal;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Posição da aresta `a -> b` em uma matriz com `tiles_per_side` blocos por linha.
+fn cell_index(tiles_per_side: usize, a: usize, b: usize) -> usize {
+    let tile = a / TILE * tiles_per_side + b / TILE;
+    tile * TILE * TILE + a % TILE * TILE + b % TILE
+}
+
+/// Atualiza o bloco `c` com os caminhos que passam pelos nós do bloco `k` de Floyd-Warshall:
+/// `c[i][j] = min(c[i][j], a[i][k] + b[k][j])`, para cada `k` em ordem.
+///
+/// `a` é o bloco na linha de `c` e na coluna `k`, e `b` o bloco na linha `k` e na coluna de `c`.
+/// Com `None`, o bloco é o próprio `c`, o que acontece com os blocos da linha e da coluna `k`.
+fn relax_tile<W: Num>(c: &mut [Option<W>], a: Option<&[Option<W>]>, b: Option<&[Option<W>]>) {
+    for k in 0..TILE {
+        for i in 0..TILE {
+            let Some(ik) = a.map_or(c[i * TILE + k], |a| a[i * TILE + k]) else {
+                continue;
+            };
+            for j in 0..TILE {
+                let Some(kj) = b.map_or(c[k * TILE + j], |b| b[k * TILE + j]) else {
+                    continue;
+                };
+                let candidate = ik + kj;
+                let current = &mut c[i * TILE + j];
+                if current.is_none_or(|current| candidate < current) {
+                    *current = Some(candidate);
+                }
+            }
+        }
+    }
+}
+
+/// Converte uma matriz em uma matriz em blocos, mantendo os identificadores dos nós, a direção e a
+/// política de laços.
+impl<W: Num> From<&crate::GraphMat<W>> for GraphTiledMat<W> {
+    fn from(graph: &crate::GraphMat<W>) -> Self {
+        let base = if graph.is_directed() {
+            Self::directed()
+        } else {
+            Self::default()
+        };
+        let mut tiled = base.with_self_loops(graph.self_loops());
+        let bound = crate::node_ids(graph).last().map_or(0, |n| n as usize + 1);
+        tiled.add_nodes(bound);
+        for n in (0..bound as Node).filter(|&n| !graph.has_node(n)) {
+            tiled.removed.insert(n);
+        }
+        for (a, b, weight) in graph.edges_iter() {
+            tiled.set_entry(a as usize, b as usize, Some(weight));
+        }
+        tiled
+    }
+}
+
+impl<W: Num> GraphRef<W> for GraphTiledMat<W> {
+    fn validate(&self) -> Result<(), GraphError> {
+        let side = self.tiles_per_side * TILE;
+        if self.links.len() != side * side || self.node_count > side {
+            return Err(GraphError::InvalidStorage(format!(
+                "tiled matrix has {} entries for {} nodes",
+                self.links.len(),
+                self.node_count
+            )));
+        }
+        // As posições além de `node_count` precisam estar vazias, ou os próximos nós já nasceriam
+        // com arestas.
+        let cells = (0..side).flat_map(|a| (0..side).map(move |b| (a, b)));
+        let set: Vec<_> = cells
+            .filter(|&(a, b)| self.links[self.cell(a, b)].is_some())
+            .collect();
+        if let Some((a, b)) = set
+            .iter()
+            .find(|&&(a, b)| a >= self.node_count || b >= self.node_count)
+        {
+            return Err(GraphError::InvalidStorage(format!(
+                "edge {a} -> {b} is outside of the matrix"
+            )));
+        }
+        let loops = set.iter().filter(|&&(a, b)| a == b).count();
+        if (self.entry_count, self.loop_count) != (set.len(), loops) {
+            return Err(GraphError::InvalidStorage(format!(
+                "edge count says {} entries and {} self-loops, but {} and {loops} are stored",
+                self.entry_count,
+                self.loop_count,
+                set.len()
+            )));
+        }
+        validate_edges(self.edges_iter(), self)
+    }
+    fn edges(&self) -> Vec<Edge<W>> {
+        // As linhas são percorridas em ordem, então as arestas já saem ordenadas.
+        self.edges_iter().collect()
+    }
+    fn edges_iter(&self) -> Box<dyn Iterator<Item = Edge<W>> + '_> {
+        Box::new((0..self.node_count as Node).flat_map(move |a| {
+            self.row_cells(a as usize)
+                .filter_map(move |(b, weight)| Some((a, b as Node, weight?)))
+        }))
+    }
+    fn node_count(&self) -> usize {
+        self.node_count - self.removed.len()
+    }
+    fn edge_count(&self) -> usize {
+        if self.directed {
+            self.entry_count
+        } else {
+            (self.entry_count + self.loop_count) / 2
+        }
+    }
+    fn has_node(&self, n: Node) -> bool {
+        (n as usize) < self.node_count && !self.removed.contains(&n)
+    }
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+    fn self_loops(&self) -> SelfLoops {
+        self.self_loops
+    }
+    fn out_edges(&self, a: Node) -> Vec<Edge<W>> {
+        self.row_cells(a as usize)
+            .filter_map(|(b, weight)| Some((a, b as Node, weight?)))
+            .collect()
+    }
+    fn in_edges(&self, b: Node) -> Vec<Edge<W>> {
+        self.column_cells(b as usize)
+            .filter_map(|(a, weight)| Some((a as Node, b, weight?)))
+            .collect()
+    }
+    fn neighbors(&self, n: Node) -> Box<dyn Iterator<Item = Node> + '_> {
+        Box::new(
+            self.row_cells(n as usize)
+                .filter(|(_, weight)| weight.is_some())
+                .map(|(b, _)| b as Node),
+        )
+    }
+    fn in_neighbors(&self, n: Node) -> Box<dyn Iterator<Item = Node> + '_> {
+        Box::new(
+            self.column_cells(n as usize)
+                .filter(|(_, weight)| weight.is_some())
+                .map(|(a, _)| a as Node),
+        )
+    }
+    fn get_edge_weight(&self, a: Node, b: Node) -> Option<W> {
+        if a as usize >= self.node_count || b as usize >= self.node_count {
+            return None;
+        }
+        self.links[self.cell(a as usize, b as usize)]
+    }
+}
+
+impl<W: Num> GraphMut<W> for GraphTiledMat<W> {
+    fn add_node(&mut self) -> Node {
+        self.add_nodes(1).start
+    }
+    fn add_nodes(&mut self, count: usize) -> Range<Node> {
+        let first_node = self.node_count as Node;
+        let new_node_count = self.node_count + count;
+        let needed = new_node_count.div_ceil(TILE);
+        if needed > self.tiles_per_side {
+            // Assim como em `GraphMat`, a matriz cresce pelo menos 50% de cada vez, a não ser na
+            // primeira alocação.
+            let tiles = if self.node_count == 0 {
+                needed
+            } else {
+                needed.max(self.tiles_per_side + self.tiles_per_side / 2)
+            };
+            let side = tiles * TILE;
+            let mut links = vec![None; side * side];
+            for a in 0..self.node_count {
+                for b in 0..self.node_count {
+                    links[cell_index(tiles, a, b)] = self.links[self.cell(a, b)];
+                }
+            }
+            self.links = links;
+            self.tiles_per_side = tiles;
+        }
+        self.node_count = new_node_count;
+        first_node..new_node_count as Node
+    }
+    fn add_edge(&mut self, a: Node, b: Node, weight: W) {
+        self.self_loops.check(a, b);
+        self.check_node(a, "add edge to");
+        self.check_node(b, "add edge to");
+        self.set_entry(a as usize, b as usize, Some(weight));
+        if !self.directed && a != b {
+            self.set_entry(b as usize, a as usize, Some(weight));
+        }
+    }
+    fn remove_node(&mut self, n: Node) {
+        self.check_node(n, "remove");
+        self.removed.insert(n);
+        let n = n as usize;
+        for b in 0..self.node_count {
+            self.set_entry(n, b, None);
+            self.set_entry(b, n, None);
+        }
+    }
+    fn remove_edge(&mut self, a: Node, b: Node) -> Option<W> {
+        let weight = self.get_edge_weight(a, b)?;
+        self.set_entry(a as usize, b as usize, None);
+        if !self.directed {
+            self.set_entry(b as usize, a as usize, None);
+        }
+        Some(weight)
+    }
+    fn clear(&mut self) {
+        self.links.clear();
+        self.node_count = 0;
+        self.tiles_per_side = 0;
+        self.removed.clear();
+        self.entry_count = 0;
+        self.loop_count = 0;
+    }
+    fn compact(&mut self) -> Map<Node, Node> {
+        let mapping = compact_mapping(self);
+        let edges: Vec<_> = self.edges_iter().collect();
+        // Recria a matriz do zero, já com a quantidade final de nós.
+        *self = Self {
+            directed: self.directed,
+            self_loops: self.self_loops,
+            ..Default::default()
+        };
+        self.add_nodes(mapping.len());
+        for (a, b, weight) in edges {
+            self.set_entry(mapping[&a] as usize, mapping[&b] as usize, Some(weight));
+        }
+        mapping
+    }
+    fn reverse(&mut self) {
+        if !self.directed {
+            return;
+        }
+        // Transpõe a matriz trocando cada elemento acima da diagonal com o seu espelho.
+        for a in 0..self.node_count {
+            for b in a + 1..self.node_count {
+                let (ab, ba) = (self.cell(a, b), self.cell(b, a));
+                self.links.swap(ab, ba);
+            }
+        }
+    }
+}
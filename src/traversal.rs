@@ -0,0 +1,246 @@
+//! Buscas em largura e em profundidade com visitantes.
+//!
+//! Muitas análises são uma busca com um pouco de contabilidade: contar os nós de cada nível,
+//! encontrar um ciclo, ordenar os nós pelo término. Em vez de reescrever a busca a cada vez, basta
+//! implementar os métodos de [`Visitor`] que interessam, e [`breadth_first_search`] ou
+//! [`depth_first_search`] os chamam em cada etapa. Os métodos retornam um [`Control`], que permite
+//! podar a busca ou interrompê-la.
+
+use alloc::collections::VecDeque;
+
+use crate::prelude::*;
+use crate::{Edge, GraphRef, Node, Num, PropertyMap, Weight};
+
+/// Indica como uma busca deve continuar depois de chamar um método de [`Visitor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Control {
+    #[default]
+    Continue,
+    /// Em [`Visitor::discover_node`], não percorre as arestas do nó. Em [`Visitor::tree_edge`], não
+    /// descobre o nó pela aresta, que pode ser descoberto depois por outra. Nos outros métodos,
+    /// equivale a `Continue`.
+    Prune,
+    /// Interrompe a busca imediatamente.
+    Stop,
+}
+
+/// Os eventos de uma busca, todos retornando [`Control::Continue`] por padrão.
+///
+/// As arestas são recebidas com origem no nó sendo percorrido. Em grafos não direcionados, cada
+/// aresta é classificada uma única vez, pela primeira ponta a percorrê-la.
+pub trait Visitor<W: Num = Weight> {
+    /// Chamado quando `n` é alcançado pela primeira vez.
+    fn discover_node(&mut self, n: Node) -> Control {
+        let _ = n;
+        Control::Continue
+    }
+    /// Chamado para uma aresta `a -> b` em que `b` ainda não foi descoberto, antes de descobri-lo.
+    /// As arestas da árvore formam uma floresta com os caminhos da busca.
+    fn tree_edge(&mut self, edge: Edge<W>) -> Control {
+        let _ = edge;
+        Control::Continue
+    }
+    /// Chamado, apenas na busca em profundidade, para uma aresta `a -> b` em que `b` é um ancestral
+    /// de `a` (ou o próprio `a`), ou seja, uma aresta que fecha um ciclo.
+    ///
+    /// Em grafos não direcionados, a aresta da árvore que chegou em `a` não é considerada de volta.
+    fn back_edge(&mut self, edge: Edge<W>) -> Control {
+        let _ = edge;
+        Control::Continue
+    }
+    /// Chamado para as demais arestas, que chegam em um nó já descoberto. Na busca em profundidade
+    /// em grafos direcionados, são as arestas para descendentes já terminados ou para outros ramos.
+    fn non_tree_edge(&mut self, edge: Edge<W>) -> Control {
+        let _ = edge;
+        Control::Continue
+    }
+    /// Chamado quando todas as arestas de `n` foram percorridas. Na busca em profundidade, todos os
+    /// descendentes de `n` já terminaram.
+    fn finish_node(&mut self, n: Node) -> Control {
+        let _ = n;
+        Control::Continue
+    }
+}
+
+/// Estado de um nó descoberto durante a busca.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Descoberto, mas com arestas ainda por percorrer (na pilha ou na fila).
+    Open,
+    Finished,
+}
+
+fn check_root<W: Num>(graph: &dyn GraphRef<W>, root: Node) {
+    if !graph.has_node(root) {
+        panic!("Tried to search from inexistent node {root}");
+    }
+}
+
+/// Descobre `n` na busca em largura, colocando-o na fila. Cada nó na fila indica se as suas
+/// arestas devem ser percorridas. Retorna `false` caso `visitor` interrompa a busca.
+fn enqueue<W: Num>(
+    visitor: &mut impl Visitor<W>,
+    n: Node,
+    state: &mut PropertyMap<State>,
+    queue: &mut VecDeque<(Node, bool)>,
+) -> bool {
+    state.insert(n, State::Open);
+    let control = visitor.discover_node(n);
+    queue.push_back((n, control == Control::Continue));
+    control != Control::Stop
+}
+
+/// Busca em largura a partir de cada nó de `roots`, em ordem, chamando os métodos de `visitor`.
+///
+/// Os nós são descobertos em ordem de distância, em quantidade de arestas, até a raiz da sua
+/// busca. As raízes já descobertas por uma busca anterior são ignoradas, então passar todos os nós
+/// percorre o grafo inteiro, com uma árvore por raiz. Custa O(V + E), seguindo a direção das
+/// arestas em grafos direcionados.
+///
+/// Retorna `false` caso `visitor` tenha interrompido a busca com [`Control::Stop`]. Causa um
+/// `panic` caso alguma raiz não exista.
+pub fn breadth_first_search<W: Num>(
+    graph: &dyn GraphRef<W>,
+    roots: impl IntoIterator<Item = Node>,
+    visitor: &mut impl Visitor<W>,
+) -> bool {
+    let undirected = !graph.is_directed();
+    let mut state = PropertyMap::new();
+    let mut queue = VecDeque::new();
+    for root in roots {
+        check_root(graph, root);
+        if state.contains(root) {
+            continue;
+        }
+        if !enqueue(visitor, root, &mut state, &mut queue) {
+            return false;
+        }
+        while let Some((a, expand)) = queue.pop_front() {
+            let edges = if expand {
+                graph.out_edges(a)
+            } else {
+                Vec::new()
+            };
+            for edge in edges {
+                let control = match state.get(edge.1) {
+                    None => match visitor.tree_edge(edge) {
+                        Control::Continue if !enqueue(visitor, edge.1, &mut state, &mut queue) => {
+                            return false
+                        }
+                        control => control,
+                    },
+                    // Em grafos não direcionados, o nó terminado já percorreu a aresta.
+                    Some(State::Finished) if undirected => Control::Continue,
+                    Some(_) => visitor.non_tree_edge(edge),
+                };
+                if control == Control::Stop {
+                    return false;
+                }
+            }
+            state.insert(a, State::Finished);
+            if visitor.finish_node(a) == Control::Stop {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Nó na pilha da busca em profundidade, com as arestas que ainda faltam percorrer.
+struct Frame<W> {
+    node: Node,
+    parent: Option<Node>,
+    edges: Vec<Edge<W>>,
+    next: usize,
+}
+
+/// Descobre `n` na busca em profundidade, colocando-o na pilha. Retorna `false` caso `visitor`
+/// interrompa a busca.
+fn push<W: Num>(
+    graph: &dyn GraphRef<W>,
+    visitor: &mut impl Visitor<W>,
+    n: Node,
+    parent: Option<Node>,
+    state: &mut PropertyMap<State>,
+    stack: &mut Vec<Frame<W>>,
+) -> bool {
+    state.insert(n, State::Open);
+    let edges = match visitor.discover_node(n) {
+        Control::Stop => return false,
+        Control::Prune => Vec::new(),
+        Control::Continue => graph.out_edges(n),
+    };
+    stack.push(Frame {
+        node: n,
+        parent,
+        edges,
+        next: 0,
+    });
+    true
+}
+
+/// Busca em profundidade a partir de cada nó de `roots`, em ordem, chamando os métodos de
+/// `visitor`.
+///
+/// Assim como em [`breadth_first_search`], as raízes já descobertas são ignoradas. A busca usa uma
+/// pilha própria, e não recursão, então não estoura a pilha do programa em caminhos longos. Custa
+/// O(V + E), seguindo a direção das arestas em grafos direcionados.
+///
+/// Retorna `false` caso `visitor` tenha interrompido a busca com [`Control::Stop`]. Causa um
+/// `panic` caso alguma raiz não exista.
+pub fn depth_first_search<W: Num>(
+    graph: &dyn GraphRef<W>,
+    roots: impl IntoIterator<Item = Node>,
+    visitor: &mut impl Visitor<W>,
+) -> bool {
+    let undirected = !graph.is_directed();
+    let mut state = PropertyMap::new();
+    let mut stack = Vec::new();
+    for root in roots {
+        check_root(graph, root);
+        if state.contains(root) {
+            continue;
+        }
+        if !push(graph, visitor, root, None, &mut state, &mut stack) {
+            return false;
+        }
+        while let Some(frame) = stack.last_mut() {
+            let Some(&edge) = frame.edges.get(frame.next) else {
+                let n = frame.node;
+                stack.pop();
+                state.insert(n, State::Finished);
+                if visitor.finish_node(n) == Control::Stop {
+                    return false;
+                }
+                continue;
+            };
+            frame.next += 1;
+            let (a, b, _) = edge;
+            // Em grafos não direcionados, a aresta até o pai é a mesma que descobriu `a`. Como não
+            // há arestas repetidas, ela aparece uma única vez na lista.
+            if undirected && frame.parent == Some(b) {
+                frame.parent = None;
+                continue;
+            }
+            let control = match state.get(b) {
+                None => match visitor.tree_edge(edge) {
+                    Control::Continue
+                        if !push(graph, visitor, b, Some(a), &mut state, &mut stack) =>
+                    {
+                        return false
+                    }
+                    control => control,
+                },
+                Some(State::Open) => visitor.back_edge(edge),
+                // Em grafos não direcionados, o nó terminado já percorreu a aresta, como uma aresta
+                // de volta.
+                Some(State::Finished) if undirected => Control::Continue,
+                Some(State::Finished) => visitor.non_tree_edge(edge),
+            };
+            if control == Control::Stop {
+                return false;
+            }
+        }
+    }
+    true
+}
@@ -0,0 +1,113 @@
+//! Interface para JavaScript, com a feature `wasm`, gerada pelo `wasm-bindgen`.
+//!
+//! Compilado como uma biblioteca dinâmica para `wasm32-unknown-unknown`, e processado pelo
+//! `wasm-bindgen`, o crate vira um módulo que pode ser importado em uma página, rodando no
+//! navegador o mesmo código do programa (veja o README).
+//!
+//! Em WebAssembly, um `panic` encerra o módulo inteiro com um erro pouco explicativo, então os
+//! métodos verificam os nós antes de chamar a biblioteca, e retornam um erro do JavaScript caso
+//! algum não exista. Os nós começam em `0`, como na biblioteca, mesmo que no formato de entrada
+//! comecem em `1`.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    connected_components, fill_graph_from_reader, minimum_spanning_tree, shortest_path, Edge,
+    GraphAdj, GraphMut, GraphRef, Node, Weight,
+};
+
+/// Grafo acessível pelo JavaScript como a classe `Graph`, guardado como um [`GraphAdj`].
+#[wasm_bindgen(js_name = Graph)]
+pub struct WasmGraph {
+    graph: GraphAdj,
+}
+
+#[wasm_bindgen(js_class = Graph)]
+impl WasmGraph {
+    /// Cria um grafo vazio: `new Graph(false)` em JavaScript.
+    #[wasm_bindgen(constructor)]
+    pub fn new(directed: bool) -> Self {
+        let graph = if directed {
+            GraphAdj::directed()
+        } else {
+            GraphAdj::default()
+        };
+        Self { graph }
+    }
+    /// Lê um grafo no formato de entrada do exercício, como em [`fill_graph_from_reader`].
+    #[wasm_bindgen(js_name = fromInput)]
+    pub fn from_input(text: &str, directed: bool) -> Result<WasmGraph, JsError> {
+        let mut graph = Self::new(directed);
+        fill_graph_from_reader(text.as_bytes(), &mut graph.graph)?;
+        Ok(graph)
+    }
+    #[wasm_bindgen(js_name = addNode)]
+    pub fn add_node(&mut self) -> Node {
+        self.graph.add_node()
+    }
+    /// Adiciona a aresta `a - b`, substituindo o peso caso ela já exista.
+    #[wasm_bindgen(js_name = addEdge)]
+    pub fn add_edge(&mut self, a: Node, b: Node, weight: Weight) -> Result<(), JsError> {
+        self.check_nodes(&[a, b])?;
+        self.graph.add_edge(a, b, weight);
+        Ok(())
+    }
+    #[wasm_bindgen(getter, js_name = nodeCount)]
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+    #[wasm_bindgen(getter, js_name = edgeCount)]
+    pub fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+    /// Retorna as arestas como um `Uint32Array` com três posições por aresta: origem, destino e
+    /// peso. Assim como em [`GraphRef::edges`], as arestas não direcionadas aparecem nos dois
+    /// sentidos.
+    pub fn edges(&self) -> Vec<u32> {
+        flatten(self.graph.edges_iter())
+    }
+    /// Retorna os nós de um caminho mínimo de `source` até `target`, incluindo os dois, ou
+    /// `undefined` caso `target` não seja alcançável.
+    #[wasm_bindgen(js_name = shortestPath)]
+    pub fn shortest_path(&self, source: Node, target: Node) -> Result<Option<Vec<Node>>, JsError> {
+        self.check_nodes(&[source, target])?;
+        Ok(shortest_path(&self.graph, source, target).map(|(path, _)| path))
+    }
+    /// Retorna o peso de um caminho mínimo de `source` até `target`, ou `undefined` caso
+    /// `target` não seja alcançável.
+    pub fn distance(&self, source: Node, target: Node) -> Result<Option<Weight>, JsError> {
+        self.check_nodes(&[source, target])?;
+        Ok(shortest_path(&self.graph, source, target).map(|(_, weight)| weight))
+    }
+    /// Retorna as arestas de uma árvore geradora mínima, no formato de [`WasmGraph::edges`].
+    #[wasm_bindgen(js_name = minimumSpanningTree)]
+    pub fn minimum_spanning_tree(&self) -> Vec<u32> {
+        flatten(minimum_spanning_tree(&self.graph))
+    }
+    /// Retorna, para cada nó, o índice da sua componente conexa, na ordem de
+    /// [`connected_components`]. Os nós de uma mesma componente têm o mesmo índice.
+    #[wasm_bindgen(js_name = connectedComponents)]
+    pub fn connected_components(&self) -> Vec<u32> {
+        let components = connected_components(&self.graph);
+        let bound = components.iter().flatten().max().map_or(0, |&n| n + 1);
+        let mut labels = vec![0; bound as usize];
+        for (i, component) in components.iter().enumerate() {
+            for &n in component {
+                labels[n as usize] = i as u32;
+            }
+        }
+        labels
+    }
+
+    fn check_nodes(&self, nodes: &[Node]) -> Result<(), JsError> {
+        match nodes.iter().find(|&&n| !self.graph.has_node(n)) {
+            Some(n) => Err(JsError::new(&format!("node {n} does not exist"))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Junta as arestas em um único vetor, com três posições por aresta.
+fn flatten(edges: impl IntoIterator<Item = Edge>) -> Vec<u32> {
+    edges.into_iter().flat_map(|(a, b, w)| [a, b, w]).collect()
+}